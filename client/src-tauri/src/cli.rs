@@ -1,6 +1,4 @@
 use std::{
-    collections::HashMap,
-    fs::File,
     path::PathBuf,
     str::FromStr,
     sync::{mpsc, Arc},
@@ -8,10 +6,12 @@ use std::{
 
 use anyhow::{anyhow, Result};
 use clap::{Parser, Subcommand};
-use dats::context::{DatContext, ZoneName};
-use processor::processor::{DatProcessingState, DatProcessor};
+use processor::{
+    processor::{DatProcessingState, DatProcessor},
+    project::load_project_dat_context,
+};
 
-use crate::{DAT_GENERATION_DIR, LOOKUP_TABLE_DIR, RAW_DATA_DIR, ZONE_MAPPING_FILE};
+use crate::{DAT_GENERATION_DIR, RAW_DATA_DIR};
 
 #[derive(Parser, Debug)]
 #[command(version, about, long_about = None)]
@@ -62,19 +62,7 @@ pub fn export_all_dats(project_dir: String) -> Result<()> {
     let project_path = PathBuf::from_str(&project_dir)?;
     println!("Processing project: {}", project_dir);
 
-    let lookup_dir = project_path.join(LOOKUP_TABLE_DIR);
-
-    // Load zone mapping
-    let zone_map_file = lookup_dir.join(ZONE_MAPPING_FILE);
-    let zone_file = File::open(zone_map_file)
-        .map_err(|err| anyhow!("Unable to open zone mapping file: {}", err))?;
-    let zones_mapping: HashMap<u16, ZoneName> = serde_yaml::from_reader(zone_file)
-        .map_err(|err| anyhow!("Unable to read zone mapping file: {}", err))?;
-
-    let dat_context = Arc::new(DatContext::from_path_and_zone_mappings(
-        lookup_dir,
-        zones_mapping,
-    )?);
+    let dat_context = Arc::new(load_project_dat_context(&project_path)?);
 
     let in_dir = project_path.join(RAW_DATA_DIR);
     let out_dir = project_path.join(DAT_GENERATION_DIR);
@@ -5,7 +5,14 @@ use std::{
 };
 
 use anyhow::{anyhow, Result};
-use processor::{dat_descriptor::DatDescriptor, processor::DatProcessorMessage};
+use dats::base::ZoneId;
+use processor::{
+    dat_descriptor::DatDescriptor,
+    processor::DatProcessorMessage,
+    project::ProjectSettings,
+    search::{search_pattern, SearchMatch},
+    xref::ZoneXref,
+};
 use tracing_subscriber::fmt::MakeWriter;
 
 use crate::{
@@ -52,6 +59,39 @@ pub async fn browse_dats(state: AppState<'_>) -> Result<Vec<BrowseInfo>, AppErro
     Ok(dat_query::get_browse_info(dat_context).await)
 }
 
+#[tauri::command]
+#[specta::specta]
+pub async fn search_strings(
+    query: String,
+    is_regex: bool,
+    state: AppState<'_>,
+) -> Result<Vec<SearchMatch>, AppError> {
+    let dat_context = state
+        .read()
+        .dat_context
+        .clone()
+        .ok_or(anyhow!("No DAT context."))?;
+    let string_index = state.read().string_index.clone();
+    let string_index_path = state.read().string_index_path();
+
+    let pattern = search_pattern(&query, is_regex)?;
+
+    // Only the DATs which changed since the last search are decoded again.
+    let matches = tauri::async_runtime::spawn_blocking(move || {
+        let mut string_index = string_index.lock();
+        let update = string_index.update(&dat_context)?;
+        if update.decoded > 0 || update.lookup_tables_changed {
+            string_index.save(&string_index_path)?;
+        }
+
+        Ok::<_, anyhow::Error>(string_index.search(&pattern))
+    })
+    .await
+    .map_err(|err| anyhow!("Search failed: {}", err))??;
+
+    Ok(matches)
+}
+
 #[tauri::command]
 #[specta::specta]
 pub async fn get_zones_for_type(
@@ -67,6 +107,18 @@ pub async fn get_zones_for_type(
     Ok(dat_query::get_zone_ids_for_type(dat_descriptor, dat_context).await)
 }
 
+#[tauri::command]
+#[specta::specta]
+pub async fn get_zone_xref(zone_id: ZoneId, state: AppState<'_>) -> Result<ZoneXref, AppError> {
+    let dat_context = state
+        .read()
+        .dat_context
+        .clone()
+        .ok_or(anyhow!("No DAT context."))?;
+
+    Ok(ZoneXref::load(&dat_context, zone_id)?)
+}
+
 #[tauri::command]
 #[specta::specta]
 pub async fn get_misc_dats() -> Result<Vec<DatDescriptor>, AppError> {
@@ -202,8 +254,14 @@ pub async fn make_yaml(dat_descriptor: DatDescriptor, state: AppState<'_>) -> Re
         .clone();
 
     let processor = state.read().processor.clone();
+    let format = ProjectSettings::load(&project_path)?.raw_data_format;
 
-    processor.dat_to_yaml(dat_descriptor, dat_context, project_path.join(RAW_DATA_DIR));
+    processor.dat_to_yaml(
+        dat_descriptor,
+        dat_context,
+        project_path.join(RAW_DATA_DIR),
+        format,
+    );
 
     Ok(())
 }
@@ -13,71 +13,33 @@ use tauri::async_runtime;
 
 use crate::errors::AppError;
 
+fn standalone_dats_in(sub_dir: Option<&str>) -> impl Iterator<Item = DatDescriptor> + '_ {
+    DatDescriptor::all_standalone()
+        .into_iter()
+        .filter(move |dat_descriptor| dat_descriptor.sub_dir() == sub_dir)
+}
+
+/// The menu DAT and its views.
+fn is_misc_dat(dat_descriptor: &DatDescriptor) -> bool {
+    *dat_descriptor == DatDescriptor::DataMenu || dat_descriptor.view_base().is_some()
+}
+
 pub fn get_misc_dats() -> Vec<DatDescriptor> {
-    vec![DatDescriptor::DataMenu]
+    standalone_dats_in(None).filter(is_misc_dat).collect()
 }
 
 pub fn get_standalone_string_dats() -> Vec<DatDescriptor> {
-    vec![
-        DatDescriptor::AbilityNames,
-        DatDescriptor::AbilityDescriptions,
-        DatDescriptor::AreaNames,
-        DatDescriptor::AreaNamesAlt,
-        DatDescriptor::CharacterSelect,
-        DatDescriptor::ChatFilterTypes,
-        DatDescriptor::DayNames,
-        DatDescriptor::Directions,
-        DatDescriptor::EquipmentLocations,
-        DatDescriptor::ErrorMessages,
-        DatDescriptor::IngameMessages1,
-        // DatDescriptor::IngameMessages2, // TODO: XiStringTable parsing isn't fully supported yet
-        DatDescriptor::JobNames,
-        DatDescriptor::KeyItems,
-        DatDescriptor::MenuItemsDescription,
-        DatDescriptor::MenuItemsText,
-        DatDescriptor::MoonPhases,
-        // DatDescriptor::PolMessages,  // TODO: XiStringTable parsing isn't fully supported yet
-        DatDescriptor::RaceNames,
-        DatDescriptor::RegionNames,
-        DatDescriptor::SpellNames,
-        DatDescriptor::SpellDescriptions,
-        DatDescriptor::StatusInfo,
-        DatDescriptor::StatusNames,
-        // DatDescriptor::TimeAndPronouns,  // TODO: XiStringTable parsing isn't fully supported yet
-        DatDescriptor::Titles,
-        DatDescriptor::Misc1,
-        DatDescriptor::Misc2,
-        DatDescriptor::WeatherTypes,
-    ]
+    standalone_dats_in(None)
+        .filter(|dat_descriptor| !is_misc_dat(dat_descriptor))
+        .collect()
 }
 
 pub fn get_item_dats() -> Vec<DatDescriptor> {
-    vec![
-        DatDescriptor::Armor,
-        DatDescriptor::Armor2,
-        // DatDescriptor::Currency, // TODO: can't currently parse this
-        DatDescriptor::GeneralItems,
-        DatDescriptor::GeneralItems2,
-        DatDescriptor::PuppetItems,
-        DatDescriptor::UsableItems,
-        DatDescriptor::Weapons,
-        DatDescriptor::VouchersAndSlips,
-        // DatDescriptor::Monipulator, // TODO: fields seems to be very different compared to other items
-        DatDescriptor::Instincts,
-    ]
+    standalone_dats_in(Some("items")).collect()
 }
 
 pub fn get_global_dialog_dats() -> Vec<DatDescriptor> {
-    vec![
-        DatDescriptor::MonsterSkillNames,
-        DatDescriptor::StatusNamesDialog,
-        DatDescriptor::EmoteMessages,
-        DatDescriptor::SystemMessages1,
-        DatDescriptor::SystemMessages2,
-        DatDescriptor::SystemMessages3,
-        DatDescriptor::SystemMessages4,
-        DatDescriptor::UnityDialogs,
-    ]
+    standalone_dats_in(Some("global_dialog")).collect()
 }
 
 #[derive(Serialize, specta::Type)]
@@ -128,16 +90,15 @@ pub async fn get_zone_ids_for_type(
     dat_descriptor: DatDescriptor,
     dat_context: Arc<DatContext>,
 ) -> Vec<ZoneInfo> {
+    let mapping = DatIdMapping::get();
+
     match dat_descriptor {
         DatDescriptor::EntityNames(_) => {
-            get_zone_ids_from_dats(&DatIdMapping::get().entities, dat_context).await
-        }
-        DatDescriptor::Dialog(_) => {
-            get_zone_ids_from_dats(&DatIdMapping::get().dialog, dat_context).await
-        }
-        DatDescriptor::Dialog2(_) => {
-            get_zone_ids_from_dats(&DatIdMapping::get().dialog2, dat_context).await
+            get_zone_ids_from_dats(&mapping.entities, dat_context).await
         }
+        DatDescriptor::Dialog(_) => get_zone_ids_from_dats(&mapping.dialog, dat_context).await,
+        DatDescriptor::Dialog2(_) => get_zone_ids_from_dats(&mapping.dialog2, dat_context).await,
+        DatDescriptor::Events(_) => get_zone_ids_from_dats(&mapping.events, dat_context).await,
         _ => {
             vec![]
         }
@@ -19,10 +19,9 @@ use tracing_subscriber::EnvFilter;
 // #[cfg(debug_assertions)]
 // use tauri_specta::ts;
 
-pub const RAW_DATA_DIR: &'static str = "raw_data";
-pub const LOOKUP_TABLE_DIR: &'static str = "lookup_tables";
-pub const DAT_GENERATION_DIR: &'static str = "generated_dats";
-pub const ZONE_MAPPING_FILE: &'static str = "zones.yml";
+pub use processor::project::{
+    DAT_GENERATION_DIR, LOOKUP_TABLE_DIR, RAW_DATA_DIR, ZONE_MAPPING_FILE,
+};
 
 fn main() {
     check_cli();
@@ -38,7 +37,9 @@ fn main() {
             commands::get_item_dats,
             commands::get_global_dialog_dats,
             commands::browse_dats,
+            commands::search_strings,
             commands::get_zones_for_type,
+            commands::get_zone_xref,
             commands::get_working_files,
             commands::make_all_dats,
             commands::make_dat,
@@ -65,7 +66,9 @@ fn main() {
             commands::select_project_folder,
             commands::load_persistence_data,
             commands::browse_dats,
+            commands::search_strings,
             commands::get_zones_for_type,
+            commands::get_zone_xref,
             commands::get_misc_dats,
             commands::get_standalone_string_dats,
             commands::get_item_dats,
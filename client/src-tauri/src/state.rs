@@ -1,5 +1,4 @@
 use std::{
-    ffi::OsStr,
     path::PathBuf,
     sync::{mpsc, Arc},
     thread,
@@ -8,8 +7,13 @@ use std::{
 use anyhow::Result;
 use dats::context::DatContext;
 use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
-use parking_lot::RwLock;
-use processor::{dat_descriptor::DatDescriptor, processor::DatProcessor};
+use parking_lot::{Mutex, RwLock};
+use processor::{
+    dat_descriptor::DatDescriptor,
+    processor::DatProcessor,
+    raw_data_format::RawDataFormat,
+    string_index::{StringIndex, STRING_INDEX_FILE},
+};
 use serde::Serialize;
 use tauri::{async_runtime, App, AppHandle, Manager};
 
@@ -21,6 +25,7 @@ pub struct AppStateData {
     pub dat_context: Option<Arc<DatContext>>,
     pub processor: Arc<DatProcessor>,
     pub persistence: PersistenceData,
+    pub string_index: Arc<Mutex<StringIndex>>,
     watcher: RecommendedWatcher,
     local_data_dir: PathBuf,
 }
@@ -35,6 +40,7 @@ impl AppStateData {
     pub fn new(app: &App) -> Self {
         let local_data_dir = app.path().local_data_dir().unwrap();
         let persistence = PersistenceData::load(&local_data_dir);
+        let string_index = StringIndex::load_or_default(&local_data_dir.join(STRING_INDEX_FILE));
 
         let dat_context = persistence
             .ffxi_path
@@ -70,12 +76,17 @@ impl AppStateData {
             dat_context,
             project_path,
             persistence,
+            string_index: Arc::new(Mutex::new(string_index)),
             watcher,
             processor,
             local_data_dir,
         }
     }
 
+    pub fn string_index_path(&self) -> PathBuf {
+        self.local_data_dir.join(STRING_INDEX_FILE)
+    }
+
     pub fn set_ffxi_path(
         &mut self,
         ffxi_path: Option<PathBuf>,
@@ -177,7 +188,7 @@ impl AppStateData {
     }
 
     fn get_file_dat_descriptor(path: &PathBuf, app_state: &AppState) -> Option<DatDescriptor> {
-        if path.is_dir() || path.extension() != Some(OsStr::new("yml")) {
+        if path.is_dir() || RawDataFormat::from_path(path).is_none() {
             return None;
         }
 
@@ -76,6 +76,14 @@ try {
     else return { status: "error", error: e  as any };
 }
 },
+async searchStrings(query: string, isRegex: boolean) : Promise<Result<SearchMatch[], any>> {
+try {
+    return { status: "ok", data: await TAURI_INVOKE("search_strings", { query, isRegex }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async getZonesForType(datDescriptor: DatDescriptor) : Promise<Result<ZoneInfo[], any>> {
 try {
     return { status: "ok", data: await TAURI_INVOKE("get_zones_for_type", { datDescriptor }) };
@@ -84,6 +92,14 @@ try {
     else return { status: "error", error: e  as any };
 }
 },
+async getZoneXref(zoneId: number) : Promise<Result<ZoneXref, any>> {
+try {
+    return { status: "ok", data: await TAURI_INVOKE("get_zone_xref", { zoneId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async getWorkingFiles() : Promise<Result<DatDescriptor[], any>> {
 try {
     return { status: "ok", data: await TAURI_INVOKE("get_working_files") };
@@ -137,13 +153,25 @@ try {
 /** user-defined types **/
 
 export type BrowseInfo = { path: string; id: number }
-export type DatDescriptor = { type: "DataMenu" } | { type: "AbilityNames" } | { type: "AbilityDescriptions" } | { type: "AreaNames" } | { type: "AreaNamesAlt" } | { type: "CharacterSelect" } | { type: "ChatFilterTypes" } | { type: "DayNames" } | { type: "Directions" } | { type: "EquipmentLocations" } | { type: "ErrorMessages" } | { type: "IngameMessages1" } | { type: "IngameMessages2" } | { type: "JobNames" } | { type: "KeyItems" } | { type: "MenuItemsDescription" } | { type: "MenuItemsText" } | { type: "MoonPhases" } | { type: "PolMessages" } | { type: "RaceNames" } | { type: "RegionNames" } | { type: "SpellNames" } | { type: "SpellDescriptions" } | { type: "StatusInfo" } | { type: "StatusNames" } | { type: "TimeAndPronouns" } | { type: "Titles" } | { type: "Misc1" } | { type: "Misc2" } | { type: "WeatherTypes" } | { type: "Armor" } | { type: "Armor2" } | { type: "Currency" } | { type: "GeneralItems" } | { type: "GeneralItems2" } | { type: "PuppetItems" } | { type: "UsableItems" } | { type: "Weapons" } | { type: "VouchersAndSlips" } | { type: "Monipulator" } | { type: "Instincts" } | { type: "MonsterSkillNames" } | { type: "StatusNamesDialog" } | { type: "EmoteMessages" } | { type: "SystemMessages1" } | { type: "SystemMessages2" } | { type: "SystemMessages3" } | { type: "SystemMessages4" } | { type: "UnityDialogs" } | { type: "EntityNames"; index: number } | { type: "Dialog"; index: number } | { type: "Dialog2"; index: number }
+export type DatDescriptor = { type: "DataMenu" } | { type: "SpellInfo" } | { type: "AbilityInfo" } | { type: "AbilityNames" } | { type: "AbilityDescriptions" } | { type: "AreaNames" } | { type: "AreaNamesAlt" } | { type: "CharacterSelect" } | { type: "ChatFilterTypes" } | { type: "DayNames" } | { type: "Directions" } | { type: "EquipmentLocations" } | { type: "ErrorMessages" } | { type: "IngameMessages1" } | { type: "IngameMessages2" } | { type: "JobNames" } | { type: "KeyItems" } | { type: "MenuItemsDescription" } | { type: "MenuItemsText" } | { type: "MoonPhases" } | { type: "PolMessages" } | { type: "RaceNames" } | { type: "RegionNames" } | { type: "SpellNames" } | { type: "SpellDescriptions" } | { type: "StatusInfo" } | { type: "StatusNames" } | { type: "TimeAndPronouns" } | { type: "Titles" } | { type: "Misc1" } | { type: "Misc2" } | { type: "WeatherTypes" } | { type: "Armor" } | { type: "Armor2" } | { type: "Currency" } | { type: "GeneralItems" } | { type: "GeneralItems2" } | { type: "PuppetItems" } | { type: "UsableItems" } | { type: "Weapons" } | { type: "VouchersAndSlips" } | { type: "Monipulator" } | { type: "Instincts" } | { type: "MonsterSkillNames" } | { type: "StatusNamesDialog" } | { type: "EmoteMessages" } | { type: "SystemMessages1" } | { type: "SystemMessages2" } | { type: "SystemMessages3" } | { type: "SystemMessages4" } | { type: "UnityDialogs" } | { type: "QuestInfo"; index: number } | { type: "EntityNames"; index: number } | { type: "Dialog"; index: number } | { type: "Dialog2"; index: number } | { type: "Events"; index: number }
+export type DialogXref = { text: string; references: XrefLocation[] }
 export type DatProcessingState = "Working" | { Finished: string } | { Error: string }
 export type DatProcessorMessage = { dat_descriptor: DatDescriptor; output_kind: DatProcessorOutputKind; state: DatProcessingState }
-export type DatProcessorOutputKind = "Dat" | "Yaml"
+export type DatProcessorOutputKind = "Dat" | "Yaml" | "Json" | "Ron" | "RoundTrip"
+export type EntityXref = { name: string; references: XrefLocation[] }
 export type FileNotification = { dat_descriptor: DatDescriptor; is_delete: boolean }
 export type PersistenceData = { ffxi_path: string | null; recent_projects: string[] }
+/**
+ * A string in a DAT which matched a search.
+ */
+export type SearchMatch = { dat_descriptor: DatDescriptor; entry_id: number; snippet: string }
+/**
+ * An event of the zone. The instruction is only set for references from within the code,
+ * while entities are referenced by every event of their own block.
+ */
+export type XrefLocation = { actor_id: number; event_id: number; instruction: number | null }
 export type ZoneInfo = { id: number; name: string }
+export type ZoneXref = { zone_id: number; dialog: { [key in number]: DialogXref }; entities: { [key in number]: EntityXref } }
 
 /** tauri-specta globals **/
 
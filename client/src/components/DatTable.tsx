@@ -11,6 +11,7 @@ import {
 import fusejs from "fuse.js";
 import { commands, DatDescriptor } from "../bindings";
 import { useData } from "../store";
+import { rawDataOutputKinds } from "../store/processing";
 import { unwrap } from "../util";
 
 interface DatTableProps<T extends { [key in Column]: any }, Column extends keyof T> {
@@ -91,7 +92,7 @@ function DatTable<T extends { [key in Column]: any }, Column extends keyof T & s
       .filter((row) => {
         const descriptor = toDatDescriptor(row);
         const key = "index" in descriptor ? descriptor.index : 0;
-        return processing.Yaml?.[descriptor.type]?.[key] == true;
+        return rawDataOutputKinds.some((kind) => processing[kind]?.[descriptor.type]?.[key] == true);
       })
       .length;
   });
@@ -187,7 +188,7 @@ function DatTable<T extends { [key in Column]: any }, Column extends keyof T & s
                       >
                         <td>
                           <Switch>
-                            <Match when={isProcessing("Yaml", descriptor)}>
+                            <Match when={rawDataOutputKinds.some((kind) => isProcessing(kind, descriptor))}>
                               <span class="italic">Exporting...</span>
                             </Match>
 
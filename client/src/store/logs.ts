@@ -27,6 +27,8 @@ export function createLogsStore() {
             if ("Finished" in payload.state) {
                 if (payload.output_kind == "Dat") {
                     message = "Finished generation";
+                } else if (payload.output_kind == "RoundTrip") {
+                    message = "Finished round-trip check";
                 } else {
                     message = "Finished export";
                 }
@@ -14,9 +14,15 @@ type ProcessingState = {
 
 const defaultProcessingState: ProcessingState = {
   Dat: {},
-  Yaml: {}
+  Yaml: {},
+  Json: {},
+  Ron: {},
+  RoundTrip: {}
 }
 
+// Exports are reported in the raw data format of the project.
+export const rawDataOutputKinds: DatProcessorOutputKind[] = ["Yaml", "Json", "Ron"];
+
 export function createProcessingStore(
   folders: ReturnType<typeof createFoldersStore>
 ) {
@@ -0,0 +1,834 @@
+use std::{
+    path::{Path, PathBuf},
+    process::ExitCode,
+    sync::{
+        mpsc::{self, Receiver},
+        Arc,
+    },
+};
+
+use anyhow::{anyhow, Result};
+use clap::{Args, Parser, Subcommand};
+use dats::{base::ZoneId, context::DatContext};
+use processor::{
+    dat_descriptor::DatDescriptor,
+    install_diff::{DatChangeKind, InstallDiff},
+    processor::{DatProcessingState, DatProcessor, DatProcessorMessage},
+    project::{load_project_dat_context, ProjectSettings, DAT_GENERATION_DIR, RAW_DATA_DIR},
+    raw_data_format::RawDataFormat,
+    search::{search_dats, search_pattern, searchable_dats},
+    string_index::StringIndex,
+    translation::{export_translations, import_translations},
+    verification::{verify_dats, VerificationOutcome},
+    xref::{XrefLocation, ZoneXref},
+};
+
+/// Headless conversion of FFXI DATs to and from editable files.
+#[derive(Parser, Debug)]
+#[command(name = "xi-tinkerer", version, about, long_about = None)]
+struct Cli {
+    #[command(flatten)]
+    source: Source,
+
+    #[command(subcommand)]
+    command: Commands,
+}
+
+#[derive(Args, Debug)]
+struct Source {
+    /// Path to the FFXI install to read DATs from.
+    #[arg(long, global = true, value_name = "FFXI_PATH")]
+    ffxi_path: Option<PathBuf>,
+
+    /// Path to a project directory. Its lookup tables are used when no FFXI path is given.
+    #[arg(long, global = true, value_name = "PROJECT_DIR")]
+    project: Option<PathBuf>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Commands {
+    /// Convert DATs into YAML files.
+    DatToYaml {
+        /// DATs to convert, e.g. `items/weapons` or `dialog/243`.
+        #[arg(value_name = "DAT", required_unless_present = "all")]
+        dats: Vec<String>,
+
+        /// Convert every supported DAT, including all zones.
+        #[arg(long, conflicts_with = "dats")]
+        all: bool,
+
+        /// Directory to write the YAML files to. Defaults to the raw data of the project.
+        #[arg(long, value_name = "DIR")]
+        out_dir: Option<PathBuf>,
+
+        /// Format to write (yaml, json or ron). Defaults to the format of the project.
+        #[arg(long, value_name = "FORMAT")]
+        format: Option<RawDataFormat>,
+    },
+
+    /// Convert YAML files into DATs.
+    YamlToDat {
+        /// DATs to generate, e.g. `items/weapons` or `dialog/243`.
+        #[arg(value_name = "DAT", required = true)]
+        dats: Vec<String>,
+
+        /// Directory to read the YAML files from. Defaults to the raw data of the project.
+        #[arg(long, value_name = "DIR")]
+        raw_data_dir: Option<PathBuf>,
+
+        /// Directory to write the DATs to. Defaults to the generated DATs of the project.
+        #[arg(long, value_name = "DIR")]
+        out_dir: Option<PathBuf>,
+    },
+
+    /// Convert every YAML file in the raw data directory into DATs.
+    ExportAll {
+        /// Directory to read the YAML files from. Defaults to the raw data of the project.
+        #[arg(long, value_name = "DIR")]
+        raw_data_dir: Option<PathBuf>,
+
+        /// Directory to write the DATs to. Defaults to the generated DATs of the project.
+        #[arg(long, value_name = "DIR")]
+        out_dir: Option<PathBuf>,
+    },
+
+    /// Check that DATs are re-encoded byte-identically after being parsed.
+    VerifyRoundtrip {
+        /// DATs to check. Checks every supported DAT if none are given.
+        #[arg(value_name = "DAT")]
+        dats: Vec<String>,
+
+        /// Write a report of every checked DAT to this file (.json, .yml or .yaml).
+        #[arg(long, value_name = "FILE")]
+        report: Option<PathBuf>,
+    },
+
+    /// List the supported DATs and the names used to refer to them.
+    List,
+
+    /// Write the items of an item DAT to a spreadsheet, with one row per item.
+    ExportItemTable {
+        /// Item DAT to export, e.g. `items/weapons`.
+        #[arg(value_name = "DAT")]
+        dat: String,
+
+        /// CSV or TSV file to write, by its extension.
+        #[arg(value_name = "FILE")]
+        table: PathBuf,
+
+        /// Directory with the YAML files, which are exported instead of the DAT when present.
+        /// Defaults to the raw data of the project.
+        #[arg(long, value_name = "DIR")]
+        raw_data_dir: Option<PathBuf>,
+    },
+
+    /// Apply the rows of a spreadsheet to the items of an item DAT, and write them as YAML.
+    ImportItemTable {
+        /// Item DAT the rows belong to, e.g. `items/weapons`.
+        #[arg(value_name = "DAT")]
+        dat: String,
+
+        /// CSV or TSV file to read, by its extension.
+        #[arg(value_name = "FILE")]
+        table: PathBuf,
+
+        /// Directory of the YAML files to update. Defaults to the raw data of the project.
+        #[arg(long, value_name = "DIR")]
+        raw_data_dir: Option<PathBuf>,
+
+        /// Format to write if the DAT has no file yet. Defaults to the format of the project.
+        #[arg(long, value_name = "FORMAT")]
+        format: Option<RawDataFormat>,
+    },
+
+    /// Export the translatable strings of DATs as a PO or XLIFF file for translators.
+    ExportTranslations {
+        /// PO or XLIFF file to write, by its extension (.po, .pot, .xlf or .xliff).
+        #[arg(value_name = "FILE")]
+        file: PathBuf,
+
+        /// DATs to export, e.g. `items/weapons` or `dialog/243`. Defaults to every DAT with text.
+        #[arg(value_name = "DAT")]
+        dats: Vec<String>,
+
+        /// Directory with the YAML files, which are exported instead of the DATs when present.
+        /// Defaults to the raw data of the project.
+        #[arg(long, value_name = "DIR")]
+        raw_data_dir: Option<PathBuf>,
+    },
+
+    /// Write the translated strings of a PO or XLIFF file into the YAML files of their DATs.
+    ImportTranslations {
+        /// PO or XLIFF file to read, by its extension.
+        #[arg(value_name = "FILE")]
+        file: PathBuf,
+
+        /// Directory of the YAML files to update. Defaults to the raw data of the project.
+        #[arg(long, value_name = "DIR")]
+        raw_data_dir: Option<PathBuf>,
+
+        /// Format to write for DATs without a file yet. Defaults to the format of the project.
+        #[arg(long, value_name = "FORMAT")]
+        format: Option<RawDataFormat>,
+    },
+
+    /// Show the entries which were added, removed or changed between two versions of a DAT.
+    Diff {
+        /// Format of both files, e.g. `items/weapons` or `dialog/243`.
+        #[arg(value_name = "DAT")]
+        dat: String,
+
+        /// Path to the old version of the DAT.
+        #[arg(value_name = "OLD")]
+        old: PathBuf,
+
+        /// Path to the new version of the DAT.
+        #[arg(value_name = "NEW")]
+        new: PathBuf,
+
+        /// Print the changes as YAML instead of one line per change.
+        #[arg(long)]
+        yaml: bool,
+    },
+
+    /// Merge YAML edits of a DAT with the changes a game update made to it. Conflicting
+    /// entries are marked in the merged YAML.
+    Merge {
+        /// DAT to merge, e.g. `items/weapons` or `dialog/243`.
+        #[arg(value_name = "DAT")]
+        dat: String,
+
+        /// Path to the DAT the edits were made on, e.g. from before the update.
+        #[arg(value_name = "BASE")]
+        base: PathBuf,
+
+        /// YAML file with the edits. Defaults to the file in the raw data of the project.
+        #[arg(long, value_name = "FILE")]
+        ours: Option<PathBuf>,
+
+        /// Path to the updated DAT. Defaults to the DAT in the FFXI install.
+        #[arg(long, value_name = "FILE")]
+        theirs: Option<PathBuf>,
+
+        /// File to write the merged YAML to. Defaults to overwriting the edited YAML file.
+        #[arg(long, value_name = "FILE")]
+        out: Option<PathBuf>,
+    },
+
+    /// Report which DATs were added, removed or changed between two FFXI installs.
+    InstallDiff {
+        /// Path to the old FFXI install.
+        #[arg(value_name = "OLD_FFXI_PATH")]
+        old_ffxi_path: PathBuf,
+
+        /// Path to the new FFXI install.
+        #[arg(value_name = "NEW_FFXI_PATH")]
+        new_ffxi_path: PathBuf,
+
+        /// Write the full report to this file (.json, .yml or .yaml).
+        #[arg(long, value_name = "FILE")]
+        report: Option<PathBuf>,
+    },
+
+    /// Find the DATs whose strings contain the given text.
+    Search {
+        /// Text to search for, ignoring case.
+        #[arg(value_name = "QUERY")]
+        query: String,
+
+        /// Treat the query as a regular expression.
+        #[arg(long)]
+        regex: bool,
+
+        /// Cache the decoded strings in this file, so that only changed DATs are decoded again.
+        #[arg(long, value_name = "FILE")]
+        index: Option<PathBuf>,
+    },
+
+    /// Show which events of a zone use its dialog entries and entities.
+    Xref {
+        /// Zone to look up, by its ID or file name.
+        #[arg(value_name = "ZONE")]
+        zone: String,
+
+        /// Only show the references of this dialog entry.
+        #[arg(long, value_name = "ID", conflicts_with = "entity")]
+        dialog: Option<u32>,
+
+        /// Only show the references of this entity, by its full ID.
+        #[arg(long, value_name = "ID")]
+        entity: Option<u32>,
+    },
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    match run(cli) {
+        Ok(0) => ExitCode::SUCCESS,
+        Ok(error_count) => {
+            eprintln!("Finished with {} error(s)", error_count);
+            ExitCode::FAILURE
+        }
+        Err(err) => {
+            eprintln!("Error: {:#}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Runs the given command, and returns the amount of DATs that failed processing.
+fn run(cli: Cli) -> Result<usize> {
+    let (tx, rx) = mpsc::channel();
+    let mut processor = DatProcessor::new(tx);
+
+    match cli.command {
+        Commands::DatToYaml {
+            dats,
+            all,
+            out_dir,
+            format,
+        } => {
+            let dat_context = cli.source.load_dat_context()?;
+            let out_dir = cli.source.project_dir_or(out_dir, RAW_DATA_DIR)?;
+            let format = cli.source.raw_data_format(format)?;
+
+            if all {
+                let total_count = processor.all_dats_to_yaml(dat_context.clone(), &out_dir, format);
+                println!("Exporting {} DATs", total_count);
+
+                return wait_for_processing(&rx, total_count, &dat_context);
+            }
+
+            let dat_descriptors = parse_descriptors(&dats, &dat_context)?;
+
+            for dat_descriptor in &dat_descriptors {
+                processor.dat_to_yaml(
+                    *dat_descriptor,
+                    dat_context.clone(),
+                    out_dir.clone(),
+                    format,
+                );
+            }
+            wait_for_processing(&rx, dat_descriptors.len(), &dat_context)
+        }
+
+        Commands::YamlToDat {
+            dats,
+            raw_data_dir,
+            out_dir,
+        } => {
+            let dat_context = cli.source.load_dat_context()?;
+            let raw_data_dir = cli.source.project_dir_or(raw_data_dir, RAW_DATA_DIR)?;
+            let out_dir = cli.source.project_dir_or(out_dir, DAT_GENERATION_DIR)?;
+            let dat_descriptors = parse_descriptors(&dats, &dat_context)?;
+
+            for dat_descriptor in &dat_descriptors {
+                processor.yaml_to_dat(
+                    *dat_descriptor,
+                    dat_context.clone(),
+                    raw_data_dir.clone(),
+                    out_dir.clone(),
+                );
+            }
+            wait_for_processing(&rx, dat_descriptors.len(), &dat_context)
+        }
+
+        Commands::ExportAll {
+            raw_data_dir,
+            out_dir,
+        } => {
+            let dat_context = cli.source.load_dat_context()?;
+            let raw_data_dir = cli.source.project_dir_or(raw_data_dir, RAW_DATA_DIR)?;
+            let out_dir = cli.source.project_dir_or(out_dir, DAT_GENERATION_DIR)?;
+
+            let total_count =
+                processor.all_yaml_to_dats(dat_context.clone(), &raw_data_dir, &out_dir);
+            println!("Generating {} DATs", total_count);
+
+            wait_for_processing(&rx, total_count, &dat_context)
+        }
+
+        Commands::VerifyRoundtrip { dats, report } => {
+            let dat_context = cli.source.load_dat_context()?;
+            let dat_descriptors = if dats.is_empty() {
+                all_descriptors(&dat_context)
+            } else {
+                parse_descriptors(&dats, &dat_context)?
+            };
+
+            let total_count = dat_descriptors.len();
+            println!("Checking {} DATs", total_count);
+
+            let mut processed = 0;
+            let verification_report = verify_dats(dat_descriptors, dat_context.clone(), |result| {
+                processed += 1;
+                match &result.outcome {
+                    VerificationOutcome::Passed { path } => println!(
+                        "[{}/{}] {}: {}",
+                        processed,
+                        total_count,
+                        result.name,
+                        path.display()
+                    ),
+                    VerificationOutcome::Mismatch {
+                        first_diff_offset, ..
+                    } => eprintln!(
+                        "[{}/{}] {}: mismatch at offset {}",
+                        processed,
+                        total_count,
+                        result.name,
+                        first_diff_offset
+                            .map(|offset| offset.to_string())
+                            .unwrap_or("<end of data>".to_string())
+                    ),
+                    VerificationOutcome::Error { message } => eprintln!(
+                        "[{}/{}] {}: {}",
+                        processed, total_count, result.name, message
+                    ),
+                }
+            });
+
+            println!(
+                "{} passed, {} failed",
+                verification_report.passed, verification_report.failed
+            );
+            if let Some(report_path) = report {
+                verification_report.write_to_path(&report_path)?;
+                println!("Wrote report to {}", report_path.display());
+            }
+
+            Ok(verification_report.failed)
+        }
+
+        Commands::List => {
+            let dat_context = cli.source.load_dat_context()?;
+            for dat_descriptor in all_descriptors(&dat_context) {
+                println!("{}", display_name(&dat_descriptor, &dat_context));
+            }
+            Ok(0)
+        }
+
+        Commands::ExportItemTable {
+            dat,
+            table,
+            raw_data_dir,
+        } => {
+            let dat_context = cli.source.load_dat_context()?;
+            let dat_descriptor = parse_descriptor(&dat, &dat_context)?;
+            let raw_data_dir = cli.source.project_dir_or(raw_data_dir, RAW_DATA_DIR).ok();
+
+            let table_path = dat_descriptor.export_item_table(&dat_context, raw_data_dir, table)?;
+            println!("Wrote items to {}", table_path.display());
+
+            Ok(0)
+        }
+
+        Commands::ImportItemTable {
+            dat,
+            table,
+            raw_data_dir,
+            format,
+        } => {
+            let dat_context = cli.source.load_dat_context()?;
+            let dat_descriptor = parse_descriptor(&dat, &dat_context)?;
+            let raw_data_dir = cli.source.project_dir_or(raw_data_dir, RAW_DATA_DIR)?;
+            let format = cli.source.raw_data_format(format)?;
+
+            let raw_data_path =
+                dat_descriptor.import_item_table(&dat_context, raw_data_dir, table, format)?;
+            println!("Wrote items to {}", raw_data_path.display());
+
+            Ok(0)
+        }
+
+        Commands::ExportTranslations {
+            file,
+            dats,
+            raw_data_dir,
+        } => {
+            let dat_context = cli.source.load_dat_context()?;
+            let dat_descriptors = if dats.is_empty() {
+                searchable_dats(&dat_context)
+            } else {
+                parse_descriptors(&dats, &dat_context)?
+            };
+            let raw_data_dir = cli.source.project_dir_or(raw_data_dir, RAW_DATA_DIR).ok();
+
+            let count = export_translations(
+                &dat_context,
+                &dat_descriptors,
+                raw_data_dir.as_deref(),
+                &file,
+            )?;
+            println!("Wrote {} strings to {}", count, file.display());
+
+            Ok(0)
+        }
+
+        Commands::ImportTranslations {
+            file,
+            raw_data_dir,
+            format,
+        } => {
+            let dat_context = cli.source.load_dat_context()?;
+            let raw_data_dir = cli.source.project_dir_or(raw_data_dir, RAW_DATA_DIR)?;
+            let format = cli.source.raw_data_format(format)?;
+
+            for (dat_descriptor, count) in
+                import_translations(&dat_context, &raw_data_dir, &file, format)?
+            {
+                println!("Translated {} strings of {:?}", count, dat_descriptor);
+            }
+
+            Ok(0)
+        }
+
+        Commands::Diff {
+            dat,
+            old,
+            new,
+            yaml,
+        } => {
+            let dat_context = cli.source.load_dat_context()?;
+            let dat_descriptor = parse_descriptor(&dat, &dat_context)?;
+            let dat_diff = dat_descriptor.diff(old, new)?;
+
+            if yaml {
+                print!("{}", dat_diff.to_yaml()?);
+            } else {
+                print!("{}", dat_diff.to_text());
+            }
+
+            Ok(0)
+        }
+
+        Commands::Merge {
+            dat,
+            base,
+            ours,
+            theirs,
+            out,
+        } => {
+            let dat_context = cli.source.load_dat_context()?;
+            let dat_descriptor = parse_descriptor(&dat, &dat_context)?;
+            let ours = match ours {
+                Some(ours) => ours,
+                None => dat_descriptor
+                    .find_raw_data_path(
+                        &dat_context,
+                        &cli.source.project_dir_or(None, RAW_DATA_DIR)?,
+                    )?
+                    .ok_or_else(|| anyhow!("The project has no file of {} to merge.", dat))?,
+            };
+            let theirs = match theirs {
+                Some(theirs) => theirs,
+                None => dat_descriptor.dat_path(&dat_context)?,
+            };
+            let out = out.unwrap_or_else(|| ours.clone());
+
+            let merge = dat_descriptor.merge(base, ours, theirs, out.clone())?;
+            for conflict in &merge.conflicts {
+                eprintln!("Conflict in {}", conflict);
+            }
+            println!(
+                "Merged into {} with {} conflict(s)",
+                out.display(),
+                merge.conflicts.len()
+            );
+
+            Ok(merge.conflicts.len())
+        }
+
+        // Comparing installs doesn't use the DATs of the given source.
+        Commands::InstallDiff {
+            old_ffxi_path,
+            new_ffxi_path,
+            report,
+        } => diff_installs(&old_ffxi_path, &new_ffxi_path, report.as_deref()),
+
+        Commands::Search {
+            query,
+            regex,
+            index,
+        } => {
+            let dat_context = cli.source.load_dat_context()?;
+            let pattern = search_pattern(&query, regex)?;
+            let matches = match index {
+                Some(index_path) => {
+                    let mut string_index = StringIndex::load_or_default(&index_path);
+                    let update = string_index.update(&dat_context)?;
+                    string_index.save(&index_path)?;
+                    println!(
+                        "Decoded {} DATs, reused {} from the index",
+                        update.decoded, update.reused
+                    );
+
+                    string_index.search(&pattern)
+                }
+                None => search_dats(searchable_dats(&dat_context), dat_context.clone(), &pattern),
+            };
+
+            for found in &matches {
+                println!(
+                    "{} #{}: {:?}",
+                    display_name(&found.dat_descriptor, &dat_context),
+                    found.entry_id,
+                    found.snippet
+                );
+            }
+            println!("{} match(es)", matches.len());
+
+            Ok(0)
+        }
+
+        Commands::Xref {
+            zone,
+            dialog,
+            entity,
+        } => {
+            let dat_context = cli.source.load_dat_context()?;
+            let zone_id = parse_zone(&zone, &dat_context)?;
+            let zone_xref = ZoneXref::load(&dat_context, zone_id)?;
+
+            match (dialog, entity) {
+                (Some(dialog_id), _) => {
+                    let entry = zone_xref.dialog.get(&dialog_id).ok_or(anyhow!(
+                        "Zone {} has no dialog entry {}.",
+                        zone_id,
+                        dialog_id
+                    ))?;
+                    println!("{}: {:?}", dialog_id, entry.text);
+                    print_locations(&entry.references, &zone_xref);
+                }
+                (None, Some(entity_id)) => {
+                    let entry = zone_xref.entities.get(&entity_id).ok_or(anyhow!(
+                        "Zone {} has no entity {:#010X}.",
+                        zone_id,
+                        entity_id
+                    ))?;
+                    println!("{:#010X}: {}", entity_id, entry.name);
+                    print_locations(&entry.references, &zone_xref);
+                }
+                (None, None) => {
+                    for (dialog_id, entry) in &zone_xref.dialog {
+                        println!(
+                            "dialog {}: {} reference(s), {:?}",
+                            dialog_id,
+                            entry.references.len(),
+                            entry.text
+                        );
+                    }
+                    for (entity_id, entry) in &zone_xref.entities {
+                        println!(
+                            "entity {:#010X}: {} reference(s), {}",
+                            entity_id,
+                            entry.references.len(),
+                            entry.name
+                        );
+                    }
+                }
+            }
+
+            Ok(0)
+        }
+    }
+}
+
+impl Source {
+    fn load_dat_context(&self) -> Result<Arc<DatContext>> {
+        let dat_context = match (&self.ffxi_path, &self.project) {
+            (Some(ffxi_path), _) => DatContext::from_ffxi_path(ffxi_path.clone()),
+            (None, Some(project_path)) => load_project_dat_context(project_path),
+            (None, None) => Err(anyhow!(
+                "Either an FFXI path (--ffxi-path) or a project (--project) has to be given."
+            )),
+        }?;
+
+        Ok(Arc::new(dat_context))
+    }
+
+    fn project_dir_or(&self, dir: Option<PathBuf>, project_sub_dir: &str) -> Result<PathBuf> {
+        dir.or_else(|| {
+            self.project
+                .as_ref()
+                .map(|project_path| project_path.join(project_sub_dir))
+        })
+        .ok_or(anyhow!(
+            "No project given, so the {} directory has to be specified.",
+            project_sub_dir
+        ))
+    }
+
+    /// The given format, or else the one selected in the project's settings.
+    fn raw_data_format(&self, format: Option<RawDataFormat>) -> Result<RawDataFormat> {
+        match (format, &self.project) {
+            (Some(format), _) => Ok(format),
+            (None, Some(project_path)) => Ok(ProjectSettings::load(project_path)?.raw_data_format),
+            (None, None) => Ok(RawDataFormat::default()),
+        }
+    }
+}
+
+fn all_descriptors(dat_context: &DatContext) -> Vec<DatDescriptor> {
+    let mut dat_descriptors = DatDescriptor::all_standalone();
+    dat_descriptors.extend(DatDescriptor::all_quests(dat_context));
+    dat_descriptors.extend(DatDescriptor::all_zoned(dat_context));
+    dat_descriptors
+}
+
+fn parse_descriptors(names: &[String], dat_context: &DatContext) -> Result<Vec<DatDescriptor>> {
+    names
+        .iter()
+        .map(|name| parse_descriptor(name, dat_context))
+        .collect()
+}
+
+/// Maps a name like `items/weapons`, `dialog/Port Jeuno` or `dialog/243` to a DAT descriptor.
+fn parse_descriptor(name: &str, dat_context: &DatContext) -> Result<DatDescriptor> {
+    if let Some(dat_descriptor) =
+        DatDescriptor::from_path(&PathBuf::from(name), &PathBuf::new(), dat_context)
+    {
+        return Ok(dat_descriptor);
+    }
+
+    name.split_once('/')
+        .and_then(|(dir_name, zone_id)| {
+            let zone_id = zone_id.parse().ok()?;
+            match dir_name {
+                "entity_names" => Some(DatDescriptor::EntityNames(zone_id)),
+                "dialog" => Some(DatDescriptor::Dialog(zone_id)),
+                "dialog2" => Some(DatDescriptor::Dialog2(zone_id)),
+                "events" => Some(DatDescriptor::Events(zone_id)),
+                _ => None,
+            }
+        })
+        .ok_or(anyhow!("Unknown DAT: {}", name))
+}
+
+/// Maps a zone ID or a zone's file name to its ID.
+fn parse_zone(zone: &str, dat_context: &DatContext) -> Result<ZoneId> {
+    zone.parse()
+        .ok()
+        .or_else(|| dat_context.zone_name_to_id_map.get(zone).copied())
+        .ok_or(anyhow!("Unknown zone: {}", zone))
+}
+
+fn print_locations(locations: &[XrefLocation], zone_xref: &ZoneXref) {
+    if locations.is_empty() {
+        println!("  Not referenced by any event");
+    }
+
+    for location in locations {
+        let actor = zone_xref
+            .entities
+            .get(&location.actor_id)
+            .map(|entity| entity.name.as_str())
+            .unwrap_or("<unnamed>");
+        match location.instruction {
+            Some(instruction) => println!(
+                "  {:#010X} ({}) event {}, instruction {}",
+                location.actor_id, actor, location.event_id, instruction
+            ),
+            None => println!(
+                "  {:#010X} ({}) event {}",
+                location.actor_id, actor, location.event_id
+            ),
+        }
+    }
+}
+
+fn diff_installs(
+    old_ffxi_path: &Path,
+    new_ffxi_path: &Path,
+    report: Option<&Path>,
+) -> Result<usize> {
+    let old_context = DatContext::from_ffxi_path(old_ffxi_path.to_path_buf())?;
+    let new_context = DatContext::from_ffxi_path(new_ffxi_path.to_path_buf())?;
+    let install_diff = InstallDiff::between(&old_context, &new_context)?;
+
+    for change in &install_diff.changes {
+        let (marker, path) = match &change.kind {
+            DatChangeKind::Added { path } => ("+", path),
+            DatChangeKind::Removed { path } => ("-", path),
+            DatChangeKind::Changed { new_path, .. } => ("~", new_path),
+            DatChangeKind::Moved { new_path, .. } => (">", new_path),
+        };
+        let dat_context = match change.kind {
+            DatChangeKind::Removed { .. } => &old_context,
+            _ => &new_context,
+        };
+        let name = change
+            .dat_descriptor
+            .map(|dat_descriptor| display_name(&dat_descriptor, dat_context))
+            .unwrap_or_default();
+
+        println!(
+            "{} {} {} {}",
+            marker,
+            change.dat_id,
+            path.display(),
+            match &change.zone_name {
+                Some(zone_name) => format!("{} ({})", name, zone_name),
+                None => name,
+            }
+            .trim_end()
+        );
+    }
+    println!(
+        "{} added, {} removed, {} changed, {} moved",
+        install_diff.added, install_diff.removed, install_diff.changed, install_diff.moved
+    );
+
+    if let Some(report_path) = report {
+        install_diff.write_to_path(report_path)?;
+        println!("Wrote report to {}", report_path.display());
+    }
+
+    Ok(0)
+}
+
+fn display_name(dat_descriptor: &DatDescriptor, dat_context: &DatContext) -> String {
+    dat_descriptor
+        .get_relative_path(dat_context)
+        .unwrap_or_else(|_| format!("{:?}", dat_descriptor))
+}
+
+/// Waits for the given amount of DATs to be processed while printing their progress,
+/// and returns how many of them failed.
+fn wait_for_processing(
+    rx: &Receiver<DatProcessorMessage>,
+    total_count: usize,
+    dat_context: &DatContext,
+) -> Result<usize> {
+    let mut processed = 0;
+    let mut error_count = 0;
+
+    while processed < total_count {
+        let msg = rx.recv()?;
+        let name = display_name(&msg.dat_descriptor, dat_context);
+
+        match msg.state {
+            DatProcessingState::Working => {}
+            DatProcessingState::Finished(path) => {
+                processed += 1;
+                println!(
+                    "[{}/{}] {}: {}",
+                    processed,
+                    total_count,
+                    name,
+                    path.display()
+                );
+            }
+            DatProcessingState::Error(err) => {
+                processed += 1;
+                error_count += 1;
+                eprintln!("[{}/{}] {}: {}", processed, total_count, name, err);
+            }
+        }
+    }
+
+    println!("Done");
+
+    Ok(error_count)
+}
@@ -2,7 +2,7 @@ use std::{
     collections::{HashMap, HashSet},
     fs::File,
     io::{Read, Seek, SeekFrom},
-    path::PathBuf,
+    path::{Path, PathBuf},
 };
 
 use crate::{
@@ -168,6 +168,22 @@ impl DatContext {
         Ok(id_map)
     }
 
+    /// The VTABLE and FTABLE files of every ROM directory in the install, which map DAT IDs
+    /// to their paths.
+    pub fn lookup_table_paths(ffxi_path: &Path) -> Vec<PathBuf> {
+        let mut paths = vec![ffxi_path.join("VTABLE.DAT"), ffxi_path.join("FTABLE.DAT")];
+        for rom_id in 2u8.. {
+            let vtable_dat_path = ffxi_path.join(format!("ROM{}/VTABLE{}.DAT", rom_id, rom_id));
+            let ftable_dat_path = ffxi_path.join(format!("ROM{}/FTABLE{}.DAT", rom_id, rom_id));
+            if !vtable_dat_path.exists() || !ftable_dat_path.exists() {
+                break;
+            }
+            paths.extend([vtable_dat_path, ftable_dat_path]);
+        }
+
+        paths
+    }
+
     fn insert_into_id_map(
         id_map: &mut HashMap<DatId, DatPath>,
         rom_id: u8,
@@ -263,3 +279,35 @@ impl DatContext {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use crate::{
+        fixtures::{FixtureInstall, FIXTURE_ZONE_NAMES},
+        formats::dialog::Dialog,
+        id_mapping::DatIdMapping,
+    };
+
+    #[test]
+    fn fake_install() -> Result<()> {
+        let fixture = FixtureInstall::new()?;
+        let context = &fixture.dat_context;
+        assert_eq!(
+            context.id_map,
+            fixture.install.dat_paths().into_iter().collect()
+        );
+
+        assert_eq!(context.zone_id_to_name.len(), FIXTURE_ZONE_NAMES.len());
+        assert_eq!(context.zone_id_to_name[&0].display_name, "_unnamed_ID-0");
+        assert_eq!(context.zone_id_to_name[&1].display_name, "Test Zone");
+        assert_eq!(context.zone_name_to_id_map["Other_Zone"], 2);
+
+        let dialog_dat = DatIdMapping::get().dialog.get_result(&1)?;
+        let dialog = context.get_data_from_dat_checked(dialog_dat)?;
+        assert_eq!(dialog, Dialog::fixture());
+
+        Ok(())
+    }
+}
@@ -8,6 +8,7 @@ use common::{
 };
 use std::{
     cmp::min,
+    fmt::{self, Display, Formatter},
     fs::{self},
     path::PathBuf,
 };
@@ -66,42 +67,75 @@ pub trait DatFormat: Sized {
         let original_bytes = fs::read(path)?;
         let res = Self::from_path(path)?;
         let re_encoded_bytes = res.to_bytes()?;
-        if re_encoded_bytes.len() != original_bytes.len() || re_encoded_bytes != original_bytes {
-            let first_diff_idx = original_bytes
-                .iter()
-                .zip(re_encoded_bytes.iter())
-                .enumerate()
-                .find_map(|(idx, (original_byte, encoded_byte))| {
-                    if original_byte != encoded_byte {
-                        Some(idx)
-                    } else {
-                        None
-                    }
-                });
-
-            if first_diff_idx.is_none() {
-                return Err(anyhow!(
-                    "Round-trip test failed because of non-matching lengths: {} vs {}",
-                    original_bytes.len(),
-                    re_encoded_bytes.len()
-                ));
-            }
-            let first_diff_idx = first_diff_idx.unwrap();
-
-            let context_start = first_diff_idx.saturating_sub(10);
-            let context_end = min(
-                re_encoded_bytes.len(),
-                min(original_bytes.len(), first_diff_idx + 10),
+        if re_encoded_bytes != original_bytes {
+            return Err(
+                RoundTripMismatch::new(path.clone(), &original_bytes, &re_encoded_bytes).into(),
             );
+        }
+        Ok(res)
+    }
+}
 
-            return Err(anyhow!(
+/// Describes where a re-encoded DAT stopped matching the original DAT.
+#[derive(Debug, Clone, thiserror::Error)]
+pub struct RoundTripMismatch {
+    pub path: PathBuf,
+    pub original_len: usize,
+    pub re_encoded_len: usize,
+    /// Index of the first differing byte, or `None` if the shorter data is a prefix of the longer.
+    pub first_diff_idx: Option<usize>,
+    /// Index of the first byte included in the context slices.
+    pub context_start: usize,
+    pub original_context: Vec<u8>,
+    pub re_encoded_context: Vec<u8>,
+}
+
+impl RoundTripMismatch {
+    const CONTEXT_SIZE: usize = 10;
+
+    pub fn new(path: PathBuf, original_bytes: &[u8], re_encoded_bytes: &[u8]) -> Self {
+        let first_diff_idx = original_bytes
+            .iter()
+            .zip(re_encoded_bytes.iter())
+            .position(|(original_byte, encoded_byte)| original_byte != encoded_byte);
+
+        let common_len = min(original_bytes.len(), re_encoded_bytes.len());
+        let (context_start, context_end) = match first_diff_idx {
+            Some(idx) => (
+                idx.saturating_sub(Self::CONTEXT_SIZE),
+                min(common_len, idx + Self::CONTEXT_SIZE),
+            ),
+            None => (common_len.saturating_sub(Self::CONTEXT_SIZE), common_len),
+        };
+
+        Self {
+            path,
+            original_len: original_bytes.len(),
+            re_encoded_len: re_encoded_bytes.len(),
+            first_diff_idx,
+            context_start,
+            original_context: original_bytes[context_start..context_end].to_vec(),
+            re_encoded_context: re_encoded_bytes[context_start..context_end].to_vec(),
+        }
+    }
+}
+
+impl Display for RoundTripMismatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.first_diff_idx {
+            Some(first_diff_idx) => write!(
+                f,
                 "Failed round-trip test for '{}' at index {}:\n{:02X?}\n{:02X?}",
-                path.display(),
+                self.path.display(),
                 first_diff_idx,
-                &original_bytes[context_start..context_end],
-                &re_encoded_bytes[context_start..context_end],
-            ));
+                self.original_context,
+                self.re_encoded_context,
+            ),
+            None => write!(
+                f,
+                "Round-trip test failed because of non-matching lengths: {} vs {}",
+                self.original_len, self.re_encoded_len
+            ),
         }
-        Ok(res)
     }
 }
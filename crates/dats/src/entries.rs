@@ -0,0 +1,15 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+/// Formats made up of records with a key, so that two versions of a DAT can be compared
+/// record by record.
+pub trait DatEntries {
+    type Entry: Serialize;
+
+    /// Field of the serialized format which holds the entries, either as a map by key or as
+    /// a list ordered by key.
+    const ENTRIES_FIELD: &'static str;
+
+    fn entries(&self) -> BTreeMap<u32, &Self::Entry>;
+}
@@ -4,7 +4,9 @@
 use num_enum::{FromPrimitive, IntoPrimitive, TryFromPrimitive};
 use serde_derive::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, FromPrimitive, IntoPrimitive)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, FromPrimitive, IntoPrimitive,
+)]
 #[repr(u8)]
 pub enum SkillType {
     #[default]
@@ -113,7 +115,9 @@ pub enum PuppetSlot {
     Attachment = 3,
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, TryFromPrimitive, IntoPrimitive)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TryFromPrimitive, IntoPrimitive,
+)]
 #[repr(u16)]
 pub enum Element {
     Fire = 0x00,
@@ -128,7 +132,9 @@ pub enum Element {
     Undecided = 0xFFFF,
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, FromPrimitive, IntoPrimitive)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, FromPrimitive, IntoPrimitive,
+)]
 #[repr(u8)]
 pub enum AbilityType {
     General = 0,
@@ -161,7 +167,9 @@ pub enum AbilityType {
     Unknown(u8),
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, FromPrimitive, IntoPrimitive)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, FromPrimitive, IntoPrimitive,
+)]
 #[repr(u16)]
 pub enum MagicType {
     None = 0,
@@ -232,3 +240,48 @@ pub enum EnglishArticle {
     PairOf = 2,
     SuitsOf = 3,
 }
+
+/// Grammatical gender of French and German item names.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, FromPrimitive, IntoPrimitive,
+)]
+#[repr(u32)]
+pub enum Gender {
+    Masculine = 0,
+    Feminine = 1,
+    Neuter = 2,
+    #[num_enum(catch_all)]
+    #[serde(untagged)]
+    Unknown(u32),
+}
+
+/// Definite article of French item names, which follows the gender and number of the name.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, FromPrimitive, IntoPrimitive,
+)]
+#[repr(u32)]
+pub enum FrenchArticle {
+    Le = 0,
+    La = 1,
+    Les = 2,
+    /// Elided before names starting with a vowel.
+    L = 3,
+    #[num_enum(catch_all)]
+    #[serde(untagged)]
+    Unknown(u32),
+}
+
+/// Definite article of German item names, which follows the gender and number of the name.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, FromPrimitive, IntoPrimitive,
+)]
+#[repr(u32)]
+pub enum GermanArticle {
+    Der = 0,
+    Die = 1,
+    Das = 2,
+    DiePlural = 3,
+    #[num_enum(catch_all)]
+    #[serde(untagged)]
+    Unknown(u32),
+}
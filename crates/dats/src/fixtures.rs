@@ -0,0 +1,248 @@
+use std::{collections::BTreeMap, fs, path::Path, sync::Arc};
+
+use anyhow::Result;
+use tempfile::TempDir;
+
+use crate::{
+    base::{Dat, DatId, DatPath},
+    context::DatContext,
+    dat_format::DatFormat,
+    formats::{
+        dialog::Dialog,
+        dmsg2_string_table::{Dmsg2Content, Dmsg2StringList, Dmsg2StringTable},
+        dmsg3_string_table::Dmsg3StringTable,
+        entity_names::EntityNames,
+        item_info::ItemInfoTable,
+        menu_table::MenuTable,
+        status_info::StatusInfoTable,
+        xistring_table::XiStringTable,
+    },
+    id_mapping::DatIdMapping,
+};
+
+/// Names of the zones in the area names DAT of [`FakeInstall::with_fixtures`], indexed by zone ID.
+pub const FIXTURE_ZONE_NAMES: [&str; 3] = ["", "Test Zone", "Other Zone"];
+
+/// Builds a minimal FFXI install with VTABLE/FTABLE lookup tables, such that `DatContext`
+/// and everything on top of it can be used without a retail install.
+#[derive(Debug, Default)]
+pub struct FakeInstall {
+    dats: BTreeMap<DatId, Vec<u8>>,
+}
+
+impl FakeInstall {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Install with the area names of [`FIXTURE_ZONE_NAMES`], dialog and entity names
+    /// for the named zones, and a fixture for each of the standalone DAT formats.
+    pub fn with_fixtures() -> Result<Self> {
+        let mapping = DatIdMapping::get();
+        let mut install = Self::new();
+
+        install.add(&mapping.area_names, &area_names(&FIXTURE_ZONE_NAMES))?;
+        for zone_id in 1..FIXTURE_ZONE_NAMES.len() as u16 {
+            install.add(mapping.dialog.get_result(&zone_id)?, &Dialog::fixture())?;
+        }
+        install.add(mapping.entities.get_result(&1)?, &EntityNames::fixture())?;
+
+        install.add(&mapping.data_menu, &MenuTable::fixture())?;
+        install.add(&mapping.ability_names, &Dmsg3StringTable::fixture())?;
+        install.add(&mapping.ability_descriptions, &Dmsg3StringTable::fixture())?;
+        install.add(&mapping.spell_names, &Dmsg3StringTable::fixture())?;
+        install.add(&mapping.spell_descriptions, &Dmsg3StringTable::fixture())?;
+        install.add(&mapping.day_names, &Dmsg2StringTable::fixture())?;
+        install.add(&mapping.pol_messages, &XiStringTable::fixture())?;
+        install.add(&mapping.time_and_pronouns, &XiStringTable::fixture())?;
+        install.add(&mapping.status_info, &StatusInfoTable::fixture())?;
+        install.add(&mapping.weapons, &ItemInfoTable::fixture())?;
+        install.add(&mapping.monster_skill_names, &Dialog::fixture())?;
+
+        Ok(install)
+    }
+
+    pub fn add<T: DatFormat>(&mut self, dat: &Dat<T>, data: &T) -> Result<&mut Self> {
+        Ok(self.add_bytes(dat, data.to_bytes()?))
+    }
+
+    pub fn add_bytes(&mut self, dat_id: impl Into<DatId>, bytes: Vec<u8>) -> &mut Self {
+        self.dats.insert(dat_id.into(), bytes);
+        self
+    }
+
+    pub fn get_bytes(&self, dat_id: impl Into<DatId>) -> Option<&[u8]> {
+        self.dats.get(&dat_id.into()).map(|bytes| bytes.as_slice())
+    }
+
+    /// The path each DAT is written to, which are assigned sequentially in the first ROM folder.
+    pub fn dat_paths(&self) -> BTreeMap<DatId, DatPath> {
+        self.dats
+            .keys()
+            .enumerate()
+            .map(|(idx, dat_id)| {
+                (
+                    *dat_id,
+                    DatPath {
+                        rom_id: 1,
+                        folder_id: (idx / 0x80) as u16 + 1,
+                        file_id: (idx % 0x80) as u16,
+                    },
+                )
+            })
+            .collect()
+    }
+
+    /// Writes the lookup tables and DATs into the given directory.
+    pub fn write_to(&self, root: &Path) -> Result<()> {
+        let dat_paths = self.dat_paths();
+        let table_len = dat_paths
+            .keys()
+            .last()
+            .map(|dat_id| dat_id.get_inner() as usize + 1)
+            .unwrap_or_default();
+
+        let mut vtable = vec![0u8; table_len];
+        let mut ftable = vec![0u8; table_len * 2];
+
+        for (dat_id, dat_path) in &dat_paths {
+            let idx = dat_id.get_inner() as usize;
+            let combined_id = (dat_path.folder_id << 7) | dat_path.file_id;
+
+            vtable[idx] = dat_path.rom_id;
+            ftable[idx * 2..idx * 2 + 2].copy_from_slice(&combined_id.to_le_bytes());
+
+            let path = root.join(dat_path.to_path());
+            fs::create_dir_all(path.parent().unwrap())?;
+            fs::write(path, &self.dats[dat_id])?;
+        }
+
+        fs::create_dir_all(root)?;
+        fs::write(root.join("VTABLE.DAT"), vtable)?;
+        fs::write(root.join("FTABLE.DAT"), ftable)?;
+
+        Ok(())
+    }
+}
+
+/// A [`FakeInstall`] written to a temporary directory, with a context to read it through.
+/// The directory is removed when this is dropped.
+#[derive(Debug)]
+pub struct FixtureInstall {
+    pub install: FakeInstall,
+    pub dat_context: Arc<DatContext>,
+    dir: TempDir,
+}
+
+impl FixtureInstall {
+    /// Writes out [`FakeInstall::with_fixtures`].
+    pub fn new() -> Result<Self> {
+        Self::write(FakeInstall::with_fixtures()?)
+    }
+
+    pub fn write(install: FakeInstall) -> Result<Self> {
+        let dir = tempfile::tempdir()?;
+        install.write_to(dir.path())?;
+        let dat_context = Arc::new(DatContext::from_ffxi_path(dir.path().to_path_buf())?);
+
+        Ok(Self {
+            install,
+            dat_context,
+            dir,
+        })
+    }
+
+    /// The directory the install was written to.
+    pub fn path(&self) -> &Path {
+        self.dir.path()
+    }
+}
+
+/// Parses the bytes of the given data with the round-trip check of [`DatFormat::from_bytes_checked`].
+pub fn roundtrip<T: DatFormat>(data: &T) -> Result<T> {
+    T::from_bytes_checked(&data.to_bytes()?)
+}
+
+/// Area names table with one list per zone, as used for the zone mappings of a `DatContext`.
+pub fn area_names(zone_names: &[&str]) -> Dmsg2StringTable {
+    Dmsg2StringTable {
+        lists: zone_names
+            .iter()
+            .enumerate()
+            .map(|(zone_id, zone_name)| {
+                (
+                    zone_id as u32,
+                    Dmsg2StringList {
+                        content: vec![Dmsg2Content::String {
+                            string: zone_name.to_string(),
+                        }],
+                    },
+                )
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::{anyhow, Result};
+    use serde::Serialize;
+
+    use crate::{
+        dat_format::DatFormat,
+        formats::{
+            dialog::Dialog, dmsg2_string_table::Dmsg2StringTable,
+            dmsg3_string_table::Dmsg3StringTable, entity_names::EntityNames, event::EventFile,
+            item_info::ItemInfoTable, menu_table::MenuTable, quest_info::QuestInfoTable,
+            status_info::StatusInfoTable, xistring_table::XiStringTable,
+        },
+        image::XiImage,
+    };
+
+    use super::roundtrip;
+
+    type FixtureCheck = fn() -> Result<()>;
+
+    /// Round-trips a fixture through its bytes, which has to parse into the same data.
+    fn check_fixture<T: DatFormat + Serialize>(fixture: fn() -> T) -> Result<()> {
+        let res = roundtrip(&fixture())?;
+        if serde_yaml::to_string(&res)? != serde_yaml::to_string(&fixture())? {
+            return Err(anyhow!("Parsed data differs from the fixture."));
+        }
+
+        Ok(())
+    }
+
+    fn check_image_fixture() -> Result<()> {
+        let bytes = XiImage::fixture().to_bytes()?;
+        let res = XiImage::from_bytes(&bytes)?;
+        if res.to_bytes()? != bytes || res.to_rgba()? != XiImage::fixture().to_rgba()? {
+            return Err(anyhow!("Parsed image differs from the fixture."));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn every_fixture_roundtrips() {
+        let checks: [(&str, FixtureCheck); 11] = [
+            ("dialog", || check_fixture(Dialog::fixture)),
+            ("dmsg2", || check_fixture(Dmsg2StringTable::fixture)),
+            ("dmsg3", || check_fixture(Dmsg3StringTable::fixture)),
+            ("entity_names", || check_fixture(EntityNames::fixture)),
+            ("event", || check_fixture(EventFile::fixture)),
+            ("item_info", || check_fixture(ItemInfoTable::fixture)),
+            ("menu_table", || check_fixture(MenuTable::fixture)),
+            ("quest_info", || check_fixture(QuestInfoTable::fixture)),
+            ("status_info", || check_fixture(StatusInfoTable::fixture)),
+            ("xistring", || check_fixture(XiStringTable::fixture)),
+            ("image", check_image_fixture),
+        ];
+
+        for (name, check) in checks {
+            if let Err(err) = check() {
+                panic!("Fixture of {}: {:#}", name, err);
+            }
+        }
+    }
+}
@@ -2,7 +2,7 @@ use crate::serde_bitflags;
 use bitflags::bitflags;
 
 bitflags! {
-    #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Default)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
     pub struct ValidTargets: u16 {
         // Combined flags
         const Corpse = 0x9D; // CorpseOnly + NPC + Ally + Partymember + Self
@@ -23,7 +23,7 @@ bitflags! {
 serde_bitflags!(ValidTargets);
 
 bitflags! {
-    #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Default)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
     pub struct ItemFlag: u16 {
         // Combined Flags
         const Ex = 0x6040; // NoAuction + NoDelivery + NoTrade
@@ -50,7 +50,7 @@ bitflags! {
 serde_bitflags!(ItemFlag);
 
 bitflags! {
-    #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Default)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
     pub struct EquipmentSlot: u16 {
         // Combined
         const Ears = 0x1800;
@@ -79,7 +79,7 @@ bitflags! {
 serde_bitflags!(EquipmentSlot);
 
 bitflags! {
-    #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Default)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
     pub struct Race: u16 {
         const All = 0x01FE;
         // Gender grouping
@@ -104,7 +104,7 @@ bitflags! {
 serde_bitflags!(Race);
 
 bitflags! {
-    #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Default)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
     pub struct JobFlag: u32 {
         const All = 0x007FFFFE;
 
@@ -5,7 +5,11 @@ use common::{byte_walker::ByteWalker, get_padding, writing_byte_walker::WritingB
 use encoding::{decoder::Decoder, encoder::Encoder};
 use serde_derive::{Deserialize, Serialize};
 
-use crate::dat_format::DatFormat;
+use crate::{
+    dat_format::DatFormat,
+    entries::DatEntries,
+    strings::{DatStrings, DatStringsMut},
+};
 
 #[derive(Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
 pub struct Dialog {
@@ -146,6 +150,57 @@ impl DatFormat for Dialog {
     }
 }
 
+impl DatEntries for Dialog {
+    type Entry = String;
+    const ENTRIES_FIELD: &'static str = "entries";
+
+    fn entries(&self) -> BTreeMap<u32, &String> {
+        self.entries
+            .iter()
+            .map(|(id, entry)| (*id, entry))
+            .collect()
+    }
+}
+
+impl DatStrings for Dialog {
+    fn strings(&self) -> Vec<(u32, &str)> {
+        self.entries
+            .iter()
+            .map(|(id, string)| (*id, string.as_str()))
+            .collect()
+    }
+}
+
+impl DatStringsMut for Dialog {
+    fn strings_mut(&mut self) -> Vec<(u32, &mut String)> {
+        self.entries
+            .iter_mut()
+            .map(|(id, string)| (*id, string))
+            .collect()
+    }
+}
+
+#[cfg(any(test, feature = "fixtures"))]
+impl Dialog {
+    /// Plain text, tags with parameters, and a selection of lines.
+    pub fn fixture() -> Self {
+        Dialog {
+            entries: BTreeMap::from([
+                (0, "Welcome to the test zone.".to_string()),
+                (
+                    1,
+                    "Can ya imagine it, ${name-player}?\n${number: 1}${item-plural: 0[2]}...${prompt}"
+                        .to_string(),
+                ),
+                (
+                    2,
+                    "What do you speak of?\n${selection-lines}\nYes.\nNo.${prompt}".to_string(),
+                ),
+            ]),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::path::PathBuf;
@@ -5,7 +5,7 @@ use common::{byte_walker::ByteWalker, get_padding, writing_byte_walker::WritingB
 use encoding::{decoder::Decoder, encoder::Encoder};
 use serde_derive::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(transparent)]
 pub struct DmsgStringList {
     pub content: Vec<DmsgContent>,
@@ -27,6 +27,14 @@ pub struct DmsgStringListMetadata {
 const LIST_STRING_PADDING: u32 = 28;
 
 impl DmsgStringList {
+    /// The first string of the list, skipping any numbers before it.
+    pub fn first_string(&self) -> Option<&str> {
+        self.content.iter().find_map(|content| match content {
+            DmsgContent::String { string } => Some(string.as_str()),
+            DmsgContent::Number { .. } => None,
+        })
+    }
+
     pub fn parse<T: ByteWalker>(
         walker: &mut T,
         flip_bytes: bool,
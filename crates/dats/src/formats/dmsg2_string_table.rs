@@ -7,7 +7,11 @@ use common::{
 use encoding::{decoder::Decoder, encoder::Encoder};
 use serde_derive::{Deserialize, Serialize};
 
-use crate::dat_format::DatFormat;
+use crate::{
+    dat_format::DatFormat,
+    entries::DatEntries,
+    strings::{DatStrings, DatStringsMut},
+};
 
 #[derive(Debug)]
 struct Dmsg2StringTableHeaders {
@@ -386,3 +390,76 @@ impl DatFormat for Dmsg2StringTable {
         Ok(())
     }
 }
+
+impl DatEntries for Dmsg2StringTable {
+    type Entry = Dmsg2StringList;
+    const ENTRIES_FIELD: &'static str = "lists";
+
+    fn entries(&self) -> BTreeMap<u32, &Dmsg2StringList> {
+        self.lists.iter().map(|(idx, list)| (*idx, list)).collect()
+    }
+}
+
+impl DatStrings for Dmsg2StringTable {
+    fn strings(&self) -> Vec<(u32, &str)> {
+        self.lists
+            .iter()
+            .flat_map(|(id, list)| {
+                list.content
+                    .iter()
+                    .filter_map(move |content| match content {
+                        Dmsg2Content::String { string } => Some((*id, string.as_str())),
+                        Dmsg2Content::Flags { .. } => None,
+                    })
+            })
+            .collect()
+    }
+}
+
+impl DatStringsMut for Dmsg2StringTable {
+    fn strings_mut(&mut self) -> Vec<(u32, &mut String)> {
+        self.lists
+            .iter_mut()
+            .flat_map(|(id, list)| {
+                list.content
+                    .iter_mut()
+                    .filter_map(move |content| match content {
+                        Dmsg2Content::String { string } => Some((*id, string)),
+                        Dmsg2Content::Flags { .. } => None,
+                    })
+            })
+            .collect()
+    }
+}
+
+#[cfg(any(test, feature = "fixtures"))]
+impl Dmsg2StringTable {
+    /// Day names, with and without a description.
+    pub fn fixture() -> Self {
+        Dmsg2StringTable {
+            lists: BTreeMap::from([
+                (
+                    0,
+                    Dmsg2StringList {
+                        content: vec![Dmsg2Content::String {
+                            string: "Sunday".to_string(),
+                        }],
+                    },
+                ),
+                (
+                    1,
+                    Dmsg2StringList {
+                        content: vec![
+                            Dmsg2Content::String {
+                                string: "Monday".to_string(),
+                            },
+                            Dmsg2Content::String {
+                                string: "The first day of the week.".to_string(),
+                            },
+                        ],
+                    },
+                ),
+            ]),
+        }
+    }
+}
@@ -4,9 +4,13 @@ use anyhow::{anyhow, Result};
 use common::{byte_walker::ByteWalker, expect, writing_byte_walker::WritingByteWalker};
 use serde_derive::{Deserialize, Serialize};
 
-use crate::dat_format::DatFormat;
+use crate::{
+    dat_format::DatFormat,
+    entries::DatEntries,
+    strings::{DatStrings, DatStringsMut},
+};
 
-use super::dmsg::DmsgStringList;
+use super::dmsg::{DmsgContent, DmsgStringList};
 
 #[derive(Debug, Default, Serialize, Deserialize)]
 pub struct Dmsg3StringTable {
@@ -25,6 +29,11 @@ struct Dmsg3StringTableHeaders {
 const HEADER_SIZE: u32 = 0x40;
 
 impl Dmsg3StringTable {
+    /// The first string of the list at the given index, e.g. the name of a spell by its index.
+    pub fn string(&self, idx: u32) -> Option<&str> {
+        self.lists.get(&idx).and_then(|list| list.first_string())
+    }
+
     fn parse_headers<T: ByteWalker>(walker: &mut T) -> Result<Dmsg3StringTableHeaders> {
         walker.expect_utf8_str("d_msg")?;
         walker.expect_utf8_str("\0\0\0")?;
@@ -168,6 +177,84 @@ impl DatFormat for Dmsg3StringTable {
     }
 }
 
+impl DatEntries for Dmsg3StringTable {
+    type Entry = DmsgStringList;
+    const ENTRIES_FIELD: &'static str = "lists";
+
+    fn entries(&self) -> BTreeMap<u32, &DmsgStringList> {
+        self.lists.iter().map(|(idx, list)| (*idx, list)).collect()
+    }
+}
+
+impl DatStrings for Dmsg3StringTable {
+    fn strings(&self) -> Vec<(u32, &str)> {
+        self.lists
+            .iter()
+            .flat_map(|(id, list)| {
+                list.content
+                    .iter()
+                    .filter_map(move |content| match content {
+                        DmsgContent::String { string } => Some((*id, string.as_str())),
+                        DmsgContent::Number { .. } => None,
+                    })
+            })
+            .collect()
+    }
+}
+
+impl DatStringsMut for Dmsg3StringTable {
+    fn strings_mut(&mut self) -> Vec<(u32, &mut String)> {
+        self.lists
+            .iter_mut()
+            .flat_map(|(id, list)| {
+                list.content
+                    .iter_mut()
+                    .filter_map(move |content| match content {
+                        DmsgContent::String { string } => Some((*id, string)),
+                        DmsgContent::Number { .. } => None,
+                    })
+            })
+            .collect()
+    }
+}
+
+#[cfg(any(test, feature = "fixtures"))]
+impl Dmsg3StringTable {
+    /// Two lists mixing numbers and strings.
+    pub fn fixture() -> Self {
+        use super::dmsg::DmsgContent;
+
+        Dmsg3StringTable {
+            bytes_per_entry: 0x80,
+            flip_bytes: true,
+            lists: BTreeMap::from([
+                (
+                    0,
+                    DmsgStringList {
+                        content: vec![
+                            DmsgContent::Number { number: 1 },
+                            DmsgContent::String {
+                                string: "Zeruhn report".to_string(),
+                            },
+                        ],
+                    },
+                ),
+                (
+                    1,
+                    DmsgStringList {
+                        content: vec![
+                            DmsgContent::Number { number: 2 },
+                            DmsgContent::String {
+                                string: "Test pass".to_string(),
+                            },
+                        ],
+                    },
+                ),
+            ]),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::path::PathBuf;
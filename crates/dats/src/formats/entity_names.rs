@@ -1,4 +1,5 @@
 use std::{
+    collections::BTreeMap,
     fs::File,
     io::{Read, Seek, SeekFrom},
     path::PathBuf,
@@ -9,7 +10,11 @@ use common::{byte_walker::ByteWalker, writing_byte_walker::WritingByteWalker};
 use encoding::{decoder::Decoder, encoder::Encoder};
 use serde_derive::{Deserialize, Serialize};
 
-use crate::dat_format::DatFormat;
+use crate::{
+    dat_format::DatFormat,
+    entries::DatEntries,
+    strings::{DatStrings, DatStringsMut},
+};
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct EntityNames {
@@ -22,6 +27,16 @@ pub struct EntityName {
     name: String,
 }
 
+impl EntityName {
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
 pub fn get_entity_names_zone(path: &PathBuf) -> Option<u16> {
     let mut file = File::open(path).ok()?;
 
@@ -56,6 +71,14 @@ impl EntityNames {
         })
     }
 
+    /// Looks up the name of an entity by its full ID.
+    pub fn get_name(&self, id: u32) -> Option<&str> {
+        self.names
+            .iter()
+            .find(|name| name.id == id)
+            .map(|name| name.name.as_str())
+    }
+
     fn read_names<T: ByteWalker>(walker: &mut T) -> Result<Vec<EntityName>> {
         walker.goto(32);
 
@@ -106,3 +129,53 @@ impl DatFormat for EntityNames {
         Ok(())
     }
 }
+
+impl DatEntries for EntityNames {
+    type Entry = EntityName;
+    const ENTRIES_FIELD: &'static str = "names";
+
+    fn entries(&self) -> BTreeMap<u32, &EntityName> {
+        self.names.iter().map(|name| (name.id, name)).collect()
+    }
+}
+
+impl DatStrings for EntityNames {
+    fn strings(&self) -> Vec<(u32, &str)> {
+        self.names
+            .iter()
+            .map(|name| (name.id, name.name.as_str()))
+            .collect()
+    }
+}
+
+impl DatStringsMut for EntityNames {
+    fn strings_mut(&mut self) -> Vec<(u32, &mut String)> {
+        self.names
+            .iter_mut()
+            .map(|name| (name.id, &mut name.name))
+            .collect()
+    }
+}
+
+#[cfg(any(test, feature = "fixtures"))]
+impl EntityNames {
+    /// Three entities of zone 1.
+    pub fn fixture() -> Self {
+        EntityNames {
+            names: vec![
+                EntityName {
+                    id: 0x01001000,
+                    name: "Test Guard".to_string(),
+                },
+                EntityName {
+                    id: 0x01001001,
+                    name: "Moogle".to_string(),
+                },
+                EntityName {
+                    id: 0x01001002,
+                    name: "Door".to_string(),
+                },
+            ],
+        }
+    }
+}
@@ -0,0 +1,537 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use anyhow::{anyhow, Result};
+use common::{
+    byte_walker::{BufferedByteWalker, ByteWalker},
+    get_padding,
+    vec_byte_walker::VecByteWalker,
+    writing_byte_walker::WritingByteWalker,
+};
+use serde_derive::{Deserialize, Serialize};
+
+use crate::{dat_format::DatFormat, serde_hex};
+
+use super::{dialog::Dialog, entity_names::EntityNames};
+
+/// Event scripts of a zone, with one block of events for each entity that has any.
+/// The layout follows the event data described by XiEvents.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EventFile {
+    pub blocks: Vec<EventBlock>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EventBlock {
+    pub actor_id: u32,
+    /// Name of the actor in the zone's entity names, only for reference.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub actor_name: Option<String>,
+
+    /// Values which the code can refer to besides its own operands.
+    pub immediates: Vec<u32>,
+    /// Names of the zone's entities whose IDs are among the immediates, only for reference.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub immediate_entities: BTreeMap<u32, String>,
+
+    /// Code before the first event, which is only reachable through jumps.
+    #[serde(with = "serde_hex", default, skip_serializing_if = "Vec::is_empty")]
+    pub unreferenced_code: Vec<u8>,
+
+    /// Events in the order of the block's event table.
+    pub events: Vec<Event>,
+}
+
+/// Event with the code from its offset up until the offset of the next event. Events which
+/// start at the same offset as a previous one have no instructions of their own.
+///
+/// Jump targets and offsets are positions in the code of the whole block, so they have to
+/// be updated when changing the length of any instruction.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Event {
+    pub id: u16,
+    pub offset: u16,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub instructions: Vec<Instruction>,
+}
+
+/// A single opcode of the event code with its operands. Only the opcodes below are
+/// decoded, and the code of an event after any other opcode is kept as it is.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "op")]
+pub enum Instruction {
+    End,
+    Goto {
+        target: u16,
+    },
+    If {
+        left: u16,
+        right: u16,
+        condition: u8,
+        target: u16,
+    },
+    Set {
+        target: u16,
+        value: u16,
+    },
+    Message {
+        message_id: u16,
+        /// Text of the message in the zone's dialog, only for reference.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        text: Option<String>,
+    },
+    Unknown {
+        #[serde(with = "serde_hex")]
+        bytes: Vec<u8>,
+    },
+}
+
+impl Instruction {
+    /// Parses the next instruction. Returns `None` for unknown opcodes and incomplete
+    /// instructions, in which case the walker's position is unchanged.
+    fn parse<T: ByteWalker>(walker: &mut T) -> Option<Instruction> {
+        let start = walker.offset();
+        let instruction = Self::parse_known(walker);
+        if instruction.is_none() {
+            walker.goto_usize(start);
+        }
+        instruction
+    }
+
+    fn parse_known<T: ByteWalker>(walker: &mut T) -> Option<Instruction> {
+        let instruction = match walker.step::<u8>().ok()? {
+            0x00 => Instruction::End,
+            0x01 => Instruction::Goto {
+                target: walker.step().ok()?,
+            },
+            0x02 => Instruction::If {
+                left: walker.step().ok()?,
+                right: walker.step().ok()?,
+                condition: walker.step().ok()?,
+                target: walker.step().ok()?,
+            },
+            0x03 => Instruction::Set {
+                target: walker.step().ok()?,
+                value: walker.step().ok()?,
+            },
+            0x1D => Instruction::Message {
+                message_id: walker.step().ok()?,
+                text: None,
+            },
+            _ => return None,
+        };
+
+        Some(instruction)
+    }
+
+    fn write<T: WritingByteWalker>(&self, walker: &mut T) {
+        match self {
+            Instruction::End => walker.write::<u8>(0x00),
+            Instruction::Goto { target } => {
+                walker.write::<u8>(0x01);
+                walker.write(*target);
+            }
+            Instruction::If {
+                left,
+                right,
+                condition,
+                target,
+            } => {
+                walker.write::<u8>(0x02);
+                walker.write(*left);
+                walker.write(*right);
+                walker.write(*condition);
+                walker.write(*target);
+            }
+            Instruction::Set { target, value } => {
+                walker.write::<u8>(0x03);
+                walker.write(*target);
+                walker.write(*value);
+            }
+            Instruction::Message { message_id, .. } => {
+                walker.write::<u8>(0x1D);
+                walker.write(*message_id);
+            }
+            Instruction::Unknown { bytes } => walker.write_bytes(bytes),
+        }
+    }
+
+    fn parse_all(code: &[u8]) -> Vec<Instruction> {
+        let mut walker = BufferedByteWalker::on(code);
+        let mut instructions = vec![];
+        while walker.remaining() > 0 {
+            match Instruction::parse(&mut walker) {
+                Some(instruction) => instructions.push(instruction),
+                None => {
+                    let bytes = walker.take_bytes(walker.remaining()).unwrap().to_vec();
+                    instructions.push(Instruction::Unknown { bytes });
+                }
+            }
+        }
+        instructions
+    }
+}
+
+impl EventBlock {
+    fn parse<T: ByteWalker>(walker: &mut T) -> Result<EventBlock> {
+        let start = walker.offset();
+        let block_size = walker.step::<u32>()? as usize;
+        let actor_id = walker.step::<u32>()?;
+
+        let event_count = walker.step::<u32>()? as usize;
+        let offsets = (0..event_count)
+            .map(|_| walker.step::<u16>())
+            .collect::<Result<Vec<_>>>()?;
+        let ids = (0..event_count)
+            .map(|_| walker.step::<u16>())
+            .collect::<Result<Vec<_>>>()?;
+
+        let immediate_count = walker.step::<u32>()?;
+        let immediates = (0..immediate_count)
+            .map(|_| walker.step::<u32>())
+            .collect::<Result<Vec<_>>>()?;
+
+        let code_size = walker.step::<u32>()? as usize;
+        let code = walker.take_bytes(code_size)?.to_vec();
+
+        let padding = get_padding(walker.offset() - start);
+        walker.expect_n_msg::<u8>(0, padding, "Padding after event code")?;
+        if walker.offset() - start != block_size {
+            return Err(anyhow!(
+                "Expected event block of actor {:#010X} to be {} bytes, but it's {}.",
+                actor_id,
+                block_size,
+                walker.offset() - start
+            ));
+        }
+
+        // The code of each event lasts until the next event, in the order of their offsets.
+        let starts = offsets.iter().copied().collect::<BTreeSet<_>>();
+        let mut claimed = BTreeSet::new();
+        let mut events = Vec::with_capacity(event_count);
+        for (offset, id) in offsets.into_iter().zip(ids) {
+            let instructions = if claimed.insert(offset) {
+                let end = starts
+                    .range(offset + 1..)
+                    .next()
+                    .map(|end| *end as usize)
+                    .unwrap_or(code.len());
+                let event_code = code.get(offset as usize..end).ok_or(anyhow!(
+                    "Event {} starts at {}, after the end of the code.",
+                    id,
+                    offset
+                ))?;
+                Instruction::parse_all(event_code)
+            } else {
+                vec![]
+            };
+
+            events.push(Event {
+                id,
+                offset,
+                instructions,
+            });
+        }
+
+        let first_start = starts
+            .first()
+            .map(|start| *start as usize)
+            .unwrap_or(code.len());
+
+        Ok(EventBlock {
+            actor_id,
+            actor_name: None,
+            immediates,
+            immediate_entities: BTreeMap::new(),
+            unreferenced_code: code[..first_start].to_vec(),
+            events,
+        })
+    }
+
+    fn code(&self) -> Result<Vec<u8>> {
+        let mut walker = VecByteWalker::new();
+        walker.write_bytes(&self.unreferenced_code);
+
+        let mut events = self.events.iter().collect::<Vec<_>>();
+        events.sort_by_key(|event| event.offset);
+
+        let mut previous_offset = None;
+        for event in events {
+            // Events sharing the code of a previous event have nothing to add.
+            if previous_offset.replace(event.offset) == Some(event.offset)
+                && event.instructions.is_empty()
+            {
+                continue;
+            }
+
+            if event.offset as usize != walker.offset() {
+                return Err(anyhow!(
+                    "Event {} of actor {:#010X} starts at {}, but its offset is {}.",
+                    event.id,
+                    self.actor_id,
+                    walker.offset(),
+                    event.offset
+                ));
+            }
+
+            for instruction in &event.instructions {
+                instruction.write(&mut walker);
+            }
+        }
+
+        Ok(walker.into_vec())
+    }
+
+    fn write<T: WritingByteWalker>(&self, walker: &mut T) -> Result<()> {
+        let start = walker.offset();
+        let code = self.code()?;
+
+        walker.skip(4);
+        walker.write(self.actor_id);
+
+        walker.write(self.events.len() as u32);
+        for event in &self.events {
+            walker.write(event.offset);
+        }
+        for event in &self.events {
+            walker.write(event.id);
+        }
+
+        walker.write(self.immediates.len() as u32);
+        for immediate in &self.immediates {
+            walker.write(*immediate);
+        }
+
+        walker.write(code.len() as u32);
+        walker.write_bytes(&code);
+
+        let padding = get_padding(walker.offset() - start);
+        walker.write_bytes(&vec![0; padding]);
+
+        walker.write_at(start, (walker.offset() - start) as u32);
+
+        Ok(())
+    }
+}
+
+impl EventFile {
+    pub fn parse<T: ByteWalker>(walker: &mut T) -> Result<EventFile> {
+        let block_count = walker.step::<u32>()?;
+        let blocks = (0..block_count)
+            .map(|_| EventBlock::parse(walker))
+            .collect::<Result<Vec<_>>>()?;
+
+        if walker.remaining() != 0 {
+            return Err(anyhow!(
+                "Found {} bytes after the last event block.",
+                walker.remaining()
+            ));
+        }
+
+        Ok(EventFile { blocks })
+    }
+
+    /// The zone of the entities in this file, as encoded in their IDs.
+    pub fn zone_id(&self) -> Option<u16> {
+        self.blocks
+            .iter()
+            .map(|block| block.actor_id)
+            .find(|actor_id| *actor_id != 0)
+            .map(|actor_id| ((actor_id >> 12) & 0xFFF) as u16)
+    }
+
+    /// Adds the names of the actors and other entities, and the text of the messages,
+    /// for reference when editing.
+    pub fn resolve_names(&mut self, dialog: Option<&Dialog>, entities: Option<&EntityNames>) {
+        for block in &mut self.blocks {
+            block.actor_name = entities
+                .and_then(|entities| entities.get_name(block.actor_id))
+                .map(str::to_string);
+            block.immediate_entities = block
+                .immediates
+                .iter()
+                .filter_map(|immediate| {
+                    let name = entities?.get_name(*immediate)?;
+                    Some((*immediate, name.to_string()))
+                })
+                .collect();
+
+            let instructions = block
+                .events
+                .iter_mut()
+                .flat_map(|event| event.instructions.iter_mut());
+            for instruction in instructions {
+                if let Instruction::Message { message_id, text } = instruction {
+                    *text = dialog
+                        .and_then(|dialog| dialog.entries.get(&(*message_id as u32)))
+                        .cloned();
+                }
+            }
+        }
+    }
+}
+
+impl DatFormat for EventFile {
+    fn from<T: ByteWalker>(walker: &mut T) -> Result<Self> {
+        EventFile::parse(walker)
+    }
+
+    fn check_type<T: ByteWalker>(walker: &mut T) -> Result<()> {
+        let block_count = walker.step::<u32>()?;
+        if block_count > 0 {
+            let block_size = walker.step::<u32>()? as usize;
+            if !block_size.is_multiple_of(4) || block_size + 4 > walker.len() {
+                return Err(anyhow!("Invalid size of first event block: {}", block_size));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn write<T: WritingByteWalker>(&self, walker: &mut T) -> Result<()> {
+        walker.write(self.blocks.len() as u32);
+        for block in &self.blocks {
+            block.write(walker)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(any(test, feature = "fixtures"))]
+impl EventFile {
+    /// Events of the entities in `EntityNames::fixture`, as in zone 1.
+    pub fn fixture() -> Self {
+        EventFile {
+            blocks: vec![
+                EventBlock {
+                    actor_id: 0x01001000,
+                    actor_name: None,
+                    immediates: vec![1000, 0x7FFF],
+                    immediate_entities: BTreeMap::new(),
+                    unreferenced_code: vec![],
+                    events: vec![
+                        Event {
+                            id: 0,
+                            offset: 0,
+                            instructions: vec![
+                                Instruction::If {
+                                    left: 0x8000,
+                                    right: 0x8001,
+                                    condition: 2,
+                                    target: 15,
+                                },
+                                Instruction::Message {
+                                    message_id: 1,
+                                    text: None,
+                                },
+                                Instruction::End,
+                                Instruction::Goto { target: 0 },
+                            ],
+                        },
+                        Event {
+                            id: 1,
+                            offset: 15,
+                            instructions: vec![
+                                Instruction::Set {
+                                    target: 0x0010,
+                                    value: 0x8000,
+                                },
+                                Instruction::Unknown {
+                                    bytes: vec![0xF0, 0x01, 0x02, 0x00],
+                                },
+                            ],
+                        },
+                        Event {
+                            id: 2,
+                            offset: 15,
+                            instructions: vec![],
+                        },
+                    ],
+                },
+                EventBlock {
+                    actor_id: 0x01001001,
+                    actor_name: None,
+                    immediates: vec![0x01001000],
+                    immediate_entities: BTreeMap::new(),
+                    unreferenced_code: vec![0x00],
+                    events: vec![Event {
+                        id: 100,
+                        offset: 1,
+                        instructions: vec![Instruction::End],
+                    }],
+                },
+            ],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::{
+        dat_format::DatFormat,
+        formats::{dialog::Dialog, entity_names::EntityNames},
+    };
+
+    use super::{EventFile, Instruction};
+
+    #[test]
+    #[ignore = "needs the event DAT of a zone as resources/test/events.DAT, see the README"]
+    pub fn zone_events() {
+        let mut dat_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        dat_path.push("resources/test/events.DAT");
+
+        EventFile::check_path(&dat_path).unwrap();
+        let res = EventFile::from_path_checked(&dat_path).unwrap();
+
+        assert!(res.zone_id().is_some());
+    }
+
+    #[test]
+    pub fn unknown_opcodes_keep_the_rest() {
+        let mut bytes = EventFile::fixture().to_bytes().unwrap();
+        // Replace the `End` of the first event with an unknown opcode.
+        bytes[55] = 0xEE;
+
+        let res = EventFile::from_bytes_checked(&bytes).unwrap();
+        assert_eq!(
+            res.blocks[0].events[0].instructions[2],
+            Instruction::Unknown {
+                bytes: vec![0xEE, 0x01, 0x00, 0x00]
+            }
+        );
+    }
+
+    #[test]
+    pub fn changed_code_length() {
+        let mut events = EventFile::fixture();
+        events.blocks[0].events[0].instructions.pop();
+
+        assert!(events.to_bytes().is_err());
+    }
+
+    #[test]
+    pub fn resolve_names() {
+        let mut events = EventFile::fixture();
+        events.resolve_names(Some(&Dialog::fixture()), Some(&EntityNames::fixture()));
+
+        assert_eq!(events.blocks[0].actor_name.as_deref(), Some("Test Guard"));
+        assert_eq!(events.blocks[1].actor_name.as_deref(), Some("Moogle"));
+        assert!(events.blocks[0].immediate_entities.is_empty());
+        assert_eq!(
+            events.blocks[1].immediate_entities[&0x01001000],
+            "Test Guard"
+        );
+        assert!(matches!(
+            &events.blocks[0].events[0].instructions[1],
+            Instruction::Message { text: Some(text), .. } if *text == Dialog::fixture().entries[&1]
+        ));
+
+        // The names are only for reference, and don't change the written DAT.
+        assert_eq!(
+            events.to_bytes().unwrap(),
+            EventFile::fixture().to_bytes().unwrap()
+        );
+    }
+}
@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use anyhow::{anyhow, Ok, Result};
 use common::{
     byte_walker::{BufferedByteWalker, ByteWalker},
@@ -6,74 +8,129 @@ use common::{
     writing_byte_walker::WritingByteWalker,
 };
 use encoding::{decoder::Decoder, encoder::Encoder};
+use serde::{ser::SerializeStruct, Serializer};
 use serde_derive::{Deserialize, Serialize};
 
 use crate::{
     dat_format::DatFormat,
-    enums::{Element, EnglishArticle, ItemType, PuppetSlot, SkillType},
+    entries::DatEntries,
+    enums::{
+        Element, EnglishArticle, FrenchArticle, Gender, GermanArticle, ItemType, PuppetSlot,
+        SkillType,
+    },
     flags::{EquipmentSlot, ItemFlag, JobFlag, Race, ValidTargets},
-    serde_base64,
+    serde_icon::{self, RecordIcon},
+    strings::{DatStrings, DatStringsMut},
     utils::{get_nibble, rotate_all},
 };
 
-#[derive(Debug, Default, Serialize, Deserialize)]
+#[derive(Debug, Default, Deserialize)]
 pub struct ItemInfo {
-    id: u32,
+    pub(crate) id: u32,
 
-    #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
-    strings: Option<ItemStrings>,
+    pub(crate) strings: Option<ItemStrings>,
 
-    flags: ItemFlag,
-    stack_size: u16,
-    item_type: ItemType,
-    resource_id: u16,
-    valid_targets: ValidTargets,
+    pub(crate) flags: ItemFlag,
+    pub(crate) stack_size: u16,
+    pub(crate) item_type: ItemType,
+    pub(crate) resource_id: u16,
+    pub(crate) valid_targets: ValidTargets,
 
-    #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
-    equipment: Option<EquipmentData>,
+    pub(crate) equipment: Option<EquipmentData>,
 
-    #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
-    weapon: Option<WeaponData>,
+    pub(crate) weapon: Option<WeaponData>,
 
-    #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     puppet: Option<PuppetItemData>,
 
-    #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     instinct: Option<InstinctData>,
 
-    #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     furnishing: Option<FurnishingData>,
 
-    #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     usable_item: Option<UsableItemData>,
 
-    #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     currency: Option<CurrencyData>,
 
-    #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     slip: Option<SlipData>,
 
-    #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     monipulator: Option<MonipulatorData>,
 
-    #[serde(with = "serde_base64")]
+    #[serde(deserialize_with = "serde_icon::deserialize")]
     icon_bytes: Vec<u8>,
 }
 
+fn serialize_optional_field<S: SerializeStruct, T: serde::Serialize>(
+    state: &mut S,
+    key: &'static str,
+    value: &Option<T>,
+) -> std::result::Result<(), S::Error> {
+    match value {
+        Some(_) => state.serialize_field(key, value),
+        None => state.skip_field(key),
+    }
+}
+
+impl serde::Serialize for ItemInfo {
+    fn serialize<S: Serializer>(&self, s: S) -> std::result::Result<S::Ok, S::Error> {
+        let mut state = s.serialize_struct("ItemInfo", 17)?;
+        state.serialize_field("id", &self.id)?;
+        serialize_optional_field(&mut state, "strings", &self.strings)?;
+        state.serialize_field("flags", &self.flags)?;
+        state.serialize_field("stack_size", &self.stack_size)?;
+        state.serialize_field("item_type", &self.item_type)?;
+        state.serialize_field("resource_id", &self.resource_id)?;
+        state.serialize_field("valid_targets", &self.valid_targets)?;
+        serialize_optional_field(&mut state, "equipment", &self.equipment)?;
+        serialize_optional_field(&mut state, "weapon", &self.weapon)?;
+        serialize_optional_field(&mut state, "puppet", &self.puppet)?;
+        serialize_optional_field(&mut state, "instinct", &self.instinct)?;
+        serialize_optional_field(&mut state, "furnishing", &self.furnishing)?;
+        serialize_optional_field(&mut state, "usable_item", &self.usable_item)?;
+        serialize_optional_field(&mut state, "currency", &self.currency)?;
+        serialize_optional_field(&mut state, "slip", &self.slip)?;
+        serialize_optional_field(&mut state, "monipulator", &self.monipulator)?;
+        state.serialize_field(
+            "icon_bytes",
+            &RecordIcon {
+                id: &self.id,
+                bytes: &self.icon_bytes,
+            },
+        )?;
+        state.end()
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub enum ItemStrings {
+    // Untagged variants are tried in order, so the variant with the most fields has to come first.
     #[serde(untagged)]
-    Name { name: String },
+    German {
+        name: String,
+        gender: Gender,
+        article_type: GermanArticle,
+        /// The declined forms of the name used in log messages, in the order they're stored.
+        log_names: [String; 5],
+        description: String,
+    },
+
+    #[serde(untagged)]
+    French {
+        name: String,
+        gender: Gender,
+        article_type: FrenchArticle,
+        singular_name: String,
+        plural_name: String,
+        description: String,
+    },
 
     #[serde(untagged)]
     English {
@@ -83,6 +140,82 @@ pub enum ItemStrings {
         plural_name: String,
         description: String,
     },
+
+    #[serde(untagged)]
+    Name { name: String },
+}
+
+impl ItemStrings {
+    pub fn name(&self) -> &str {
+        match self {
+            ItemStrings::German { name, .. }
+            | ItemStrings::French { name, .. }
+            | ItemStrings::English { name, .. }
+            | ItemStrings::Name { name } => name,
+        }
+    }
+
+    /// All names and the description, in the order they're stored.
+    pub fn strings(&self) -> Vec<&str> {
+        match self {
+            ItemStrings::German {
+                name,
+                log_names,
+                description,
+                ..
+            } => [name.as_str()]
+                .into_iter()
+                .chain(log_names.iter().map(String::as_str))
+                .chain([description.as_str()])
+                .collect(),
+            ItemStrings::French {
+                name,
+                singular_name,
+                plural_name,
+                description,
+                ..
+            }
+            | ItemStrings::English {
+                name,
+                singular_name,
+                plural_name,
+                description,
+                ..
+            } => vec![name, singular_name, plural_name, description],
+            ItemStrings::Name { name } => vec![name],
+        }
+    }
+
+    /// The same strings as [`ItemStrings::strings`], in the same order.
+    pub fn strings_mut(&mut self) -> Vec<&mut String> {
+        match self {
+            ItemStrings::German {
+                name,
+                log_names,
+                description,
+                ..
+            } => [name]
+                .into_iter()
+                .chain(log_names.iter_mut())
+                .chain([description])
+                .collect(),
+            ItemStrings::French {
+                name,
+                singular_name,
+                plural_name,
+                description,
+                ..
+            }
+            | ItemStrings::English {
+                name,
+                singular_name,
+                plural_name,
+                description,
+                ..
+            } => vec![name, singular_name, plural_name, description],
+            ItemStrings::Name { name } => vec![name],
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -152,31 +285,31 @@ impl ItemCategory {
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct EquipmentData {
-    level: u16,
-    slots: EquipmentSlot,
-    races: Race,
-    jobs: JobFlag,
-    superior_level: u16,
-    shield_size: u16,
-
-    max_charges: u8,
-    casting_time: u8,
-    use_delay: u16,
-    reuse_delay: u32,
-    unknown1: u16,
-    ilevel: u8,
-    unknown2: u8,
-    unknown3: u32,
+    pub(crate) level: u16,
+    pub(crate) slots: EquipmentSlot,
+    pub(crate) races: Race,
+    pub(crate) jobs: JobFlag,
+    pub(crate) superior_level: u16,
+    pub(crate) shield_size: u16,
+
+    pub(crate) max_charges: u8,
+    pub(crate) casting_time: u8,
+    pub(crate) use_delay: u16,
+    pub(crate) reuse_delay: u32,
+    pub(crate) unknown1: u16,
+    pub(crate) ilevel: u8,
+    pub(crate) unknown2: u8,
+    pub(crate) unknown3: u32,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct WeaponData {
-    damage: u16,
-    delay: u16,
-    dps: u16,
-    skill_type: SkillType,
-    jug_size: u8,
-    unknown1: u32,
+    pub(crate) damage: u16,
+    pub(crate) delay: u16,
+    pub(crate) dps: u16,
+    pub(crate) skill_type: SkillType,
+    pub(crate) jug_size: u8,
+    pub(crate) unknown1: u32,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -386,16 +519,21 @@ impl ItemInfo {
         } else if item_category == ItemCategory::Slip {
             item_info.slip = Some(SlipData {
                 unknown1: data_walker.step::<u16>()?,
-                unknowns: core::array::from_fn(|_| data_walker.step::<u32>().unwrap_or_default()),
+                unknowns: Self::read_unknowns(&mut data_walker)?,
             });
         } else if item_category == ItemCategory::Monipulator {
             item_info.monipulator = Some(MonipulatorData {
                 unknown1: data_walker.step::<u16>()?,
-                unknowns: core::array::from_fn(|_| data_walker.step::<u32>().unwrap_or_default()),
+                unknowns: Self::read_unknowns(&mut data_walker)?,
             });
         }
 
-        // Parse string data
+        item_info.strings = Self::read_strings(&mut data_walker)?;
+
+        Ok(item_info)
+    }
+
+    fn read_strings<T: ByteWalker>(data_walker: &mut T) -> Result<Option<ItemStrings>> {
         let content_count = data_walker.step::<u32>()?;
         if content_count > 9 {
             return Err(anyhow!(
@@ -409,27 +547,71 @@ impl ItemInfo {
             metas.push((data_walker.step::<u32>()?, data_walker.step::<u32>()?));
         }
 
-        match content_count {
+        // Each meta has the offset of the content, and whether it's a string (0) or a number (1).
+        let content_types = metas.iter().map(|(_, kind)| *kind).collect::<Vec<_>>();
+        let expected_types: &[u32] = match content_count {
+            1 => &[0],
+            5 => &[0, 1, 0, 0, 0],
+            6 => &[0, 1, 1, 0, 0, 0],
+            9 => &[0, 1, 1, 0, 0, 0, 0, 0, 0],
+            _ => &[],
+        };
+        if !expected_types.is_empty() && content_types != expected_types {
+            return Err(anyhow!(
+                "Unexpected string content types for string count {}: {:?}",
+                content_count,
+                content_types
+            ));
+        }
+
+        let strings = match content_count {
             1 => {
                 // Just one string name
-                item_info.strings = Some(ItemStrings::Name {
-                    name: Self::read_string(&mut data_walker)?,
-                });
+                ItemStrings::Name {
+                    name: Self::read_string(data_walker)?,
+                }
             }
             5 => {
                 // English
-                item_info.strings = Some(ItemStrings::English {
-                    name: Self::read_string(&mut data_walker)?,
+                ItemStrings::English {
+                    name: Self::read_string(data_walker)?,
                     article_type: EnglishArticle::try_from(data_walker.step::<u32>()?)?,
-                    singular_name: Self::read_string(&mut data_walker)?,
-                    plural_name: Self::read_string(&mut data_walker)?,
-                    description: Self::read_string(&mut data_walker)?,
-                });
+                    singular_name: Self::read_string(data_walker)?,
+                    plural_name: Self::read_string(data_walker)?,
+                    description: Self::read_string(data_walker)?,
+                }
+            }
+            6 => {
+                // French
+                ItemStrings::French {
+                    name: Self::read_string(data_walker)?,
+                    gender: Gender::from(data_walker.step::<u32>()?),
+                    article_type: FrenchArticle::from(data_walker.step::<u32>()?),
+                    singular_name: Self::read_string(data_walker)?,
+                    plural_name: Self::read_string(data_walker)?,
+                    description: Self::read_string(data_walker)?,
+                }
+            }
+            9 => {
+                // German
+                ItemStrings::German {
+                    name: Self::read_string(data_walker)?,
+                    gender: Gender::from(data_walker.step::<u32>()?),
+                    article_type: GermanArticle::from(data_walker.step::<u32>()?),
+                    log_names: [
+                        Self::read_string(data_walker)?,
+                        Self::read_string(data_walker)?,
+                        Self::read_string(data_walker)?,
+                        Self::read_string(data_walker)?,
+                        Self::read_string(data_walker)?,
+                    ],
+                    description: Self::read_string(data_walker)?,
+                }
             }
             count => {
                 return Err(anyhow!("Unsupported string count: {}", count));
             }
-        }
+        };
 
         data_walker.expect_n_msg::<u32>(
             0,
@@ -437,7 +619,16 @@ impl ItemInfo {
             "Zero padding at end of data",
         )?;
 
-        Ok(item_info)
+        Ok(Some(strings))
+    }
+
+    fn read_unknowns<T: ByteWalker, const N: usize>(data_walker: &mut T) -> Result<[u32; N]> {
+        let mut unknowns = [0; N];
+        for unknown in &mut unknowns {
+            *unknown = data_walker.step::<u32>()?;
+        }
+
+        Ok(unknowns)
     }
 
     fn read_string<T: ByteWalker>(walker: &mut T) -> Result<String> {
@@ -454,13 +645,8 @@ impl ItemInfo {
         string
     }
 
-    pub fn write<T: WritingByteWalker>(&self, outer_walker: &mut T) -> Result<()> {
-        let mut walker = VecByteWalker::with_size(0xC00);
-
-        walker.write(self.id);
+    fn write_item_data(&self, walker: &mut VecByteWalker) {
         walker.write(self.flags.bits());
-
-        // Write item data
         walker.write(self.stack_size);
         walker.write::<u16>(self.item_type.into());
         walker.write(self.resource_id);
@@ -526,6 +712,13 @@ impl ItemInfo {
                 walker.write(unknown);
             }
         }
+    }
+
+    pub fn write<T: WritingByteWalker>(&self, outer_walker: &mut T) -> Result<()> {
+        let mut walker = VecByteWalker::with_size(0xC00);
+
+        walker.write(self.id);
+        self.write_item_data(&mut walker);
 
         // Write strings
         let mut string_content = vec![];
@@ -547,6 +740,36 @@ impl ItemInfo {
                 string_content.push(ItemStringContent::from_string(plural_name)?);
                 string_content.push(ItemStringContent::from_string(description)?);
             }
+            Some(ItemStrings::French {
+                name,
+                gender,
+                article_type,
+                singular_name,
+                plural_name,
+                description,
+            }) => {
+                string_content.push(ItemStringContent::from_string(name)?);
+                string_content.push(ItemStringContent::Number((*gender).into()));
+                string_content.push(ItemStringContent::from_article(*article_type));
+                string_content.push(ItemStringContent::from_string(singular_name)?);
+                string_content.push(ItemStringContent::from_string(plural_name)?);
+                string_content.push(ItemStringContent::from_string(description)?);
+            }
+            Some(ItemStrings::German {
+                name,
+                gender,
+                article_type,
+                log_names,
+                description,
+            }) => {
+                string_content.push(ItemStringContent::from_string(name)?);
+                string_content.push(ItemStringContent::Number((*gender).into()));
+                string_content.push(ItemStringContent::from_article(*article_type));
+                for log_name in log_names {
+                    string_content.push(ItemStringContent::from_string(log_name)?);
+                }
+                string_content.push(ItemStringContent::from_string(description)?);
+            }
             None => {}
         }
 
@@ -598,7 +821,7 @@ impl ItemInfo {
 
 #[derive(Debug, Default, Serialize, Deserialize)]
 pub struct ItemInfoTable {
-    items: Vec<ItemInfo>,
+    pub(crate) items: Vec<ItemInfo>,
 }
 
 const ENTRY_SIZE: usize = 0xC00;
@@ -650,13 +873,127 @@ impl DatFormat for ItemInfoTable {
     }
 }
 
+impl DatEntries for ItemInfoTable {
+    type Entry = ItemInfo;
+    const ENTRIES_FIELD: &'static str = "items";
+
+    fn entries(&self) -> BTreeMap<u32, &ItemInfo> {
+        self.items.iter().map(|item| (item.id, item)).collect()
+    }
+}
+
+impl DatStrings for ItemInfoTable {
+    fn strings(&self) -> Vec<(u32, &str)> {
+        self.items
+            .iter()
+            .flat_map(|item| {
+                item.strings
+                    .iter()
+                    .flat_map(ItemStrings::strings)
+                    .map(|string| (item.id, string))
+            })
+            .collect()
+    }
+}
+
+impl DatStringsMut for ItemInfoTable {
+    fn strings_mut(&mut self) -> Vec<(u32, &mut String)> {
+        self.items
+            .iter_mut()
+            .flat_map(|item| {
+                let id = item.id;
+                item.strings
+                    .iter_mut()
+                    .flat_map(ItemStrings::strings_mut)
+                    .map(move |string| (id, string))
+            })
+            .collect()
+    }
+}
+
+#[cfg(any(test, feature = "fixtures"))]
+impl ItemInfoTable {
+    /// A furnishing and a weapon with English strings.
+    pub fn fixture() -> Self {
+        ItemInfoTable {
+            items: vec![
+                ItemInfo {
+                    id: 0x0001,
+                    strings: Some(ItemStrings::English {
+                        name: "Chocobo Bedding".to_string(),
+                        article_type: EnglishArticle::A,
+                        singular_name: "chocobo bedding".to_string(),
+                        plural_name: "sets of chocobo bedding".to_string(),
+                        description: "A bed for chocobos.".to_string(),
+                    }),
+                    flags: ItemFlag::CanSendPOL | ItemFlag::CanTradeNPC,
+                    stack_size: 1,
+                    item_type: ItemType::Furnishing,
+                    furnishing: Some(FurnishingData {
+                        element: Element::Earth,
+                        storage_slots: 1,
+                        unknown3: 0,
+                    }),
+                    icon_bytes: vec![0x91, 0x00, 0x01, 0x02, 0x03],
+                    ..Default::default()
+                },
+                ItemInfo {
+                    id: 0x50E9,
+                    strings: Some(ItemStrings::English {
+                        name: "Excalipoor".to_string(),
+                        article_type: EnglishArticle::An,
+                        singular_name: "Excalipoor".to_string(),
+                        plural_name: "Excalipoors".to_string(),
+                        description: "DMG:1 Delay:240".to_string(),
+                    }),
+                    flags: ItemFlag::CanEquip | ItemFlag::Rare,
+                    stack_size: 1,
+                    item_type: ItemType::Weapon,
+                    valid_targets: ValidTargets::SelfTarget,
+                    equipment: Some(EquipmentData {
+                        level: 1,
+                        slots: EquipmentSlot::Main,
+                        races: Race::All,
+                        jobs: JobFlag::WAR | JobFlag::PLD,
+                        superior_level: 0,
+                        shield_size: 0,
+                        max_charges: 0,
+                        casting_time: 0,
+                        use_delay: 0,
+                        reuse_delay: 0,
+                        unknown1: 0,
+                        ilevel: 0,
+                        unknown2: 0,
+                        unknown3: 0,
+                    }),
+                    weapon: Some(WeaponData {
+                        damage: 1,
+                        delay: 240,
+                        dps: 25,
+                        skill_type: SkillType::Sword,
+                        jug_size: 0,
+                        unknown1: 0,
+                    }),
+                    icon_bytes: vec![0x91, 0x04, 0x05],
+                    ..Default::default()
+                },
+            ],
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::path::PathBuf;
 
-    use crate::{dat_format::DatFormat, enums::EnglishArticle};
+    use crate::{
+        dat_format::DatFormat,
+        enums::{Element, EnglishArticle, FrenchArticle, Gender, GermanArticle, ItemType},
+    };
 
-    use super::{ItemInfoTable, ItemStrings};
+    use super::{
+        CurrencyData, FurnishingData, ItemInfo, ItemInfoTable, ItemStrings, MonipulatorData,
+    };
 
     #[test]
     pub fn weapons() {
@@ -709,4 +1046,147 @@ mod tests {
             panic!("Expected english strings")
         }
     }
+
+    #[test]
+    pub fn currency() {
+        let mut dat_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        dat_path.push("resources/test/currency.DAT");
+
+        ItemInfoTable::check_path(&dat_path).unwrap();
+        let res = ItemInfoTable::from_path_checked(&dat_path).unwrap();
+
+        assert_eq!(res.items[0].id, 0xFFFF);
+        assert!(res.items[0].currency.is_some());
+    }
+
+    #[test]
+    pub fn monipulator() {
+        let mut dat_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        dat_path.push("resources/test/monipulator.DAT");
+
+        ItemInfoTable::check_path(&dat_path).unwrap();
+        let res = ItemInfoTable::from_path_checked(&dat_path).unwrap();
+
+        assert!(res.items.iter().all(|item| item.monipulator.is_some()));
+    }
+
+    #[test]
+    pub fn french_and_german_strings() {
+        let table = ItemInfoTable {
+            items: vec![
+                ItemInfo {
+                    id: 0x0002,
+                    strings: Some(ItemStrings::French {
+                        name: "Lit de chocobo".to_string(),
+                        gender: Gender::Masculine,
+                        article_type: FrenchArticle::Le,
+                        singular_name: "lit de chocobo".to_string(),
+                        plural_name: "lits de chocobo".to_string(),
+                        description: "Un lit pour les chocobos.".to_string(),
+                    }),
+                    furnishing: Some(FurnishingData {
+                        element: Element::Earth,
+                        storage_slots: 0,
+                        unknown3: 0,
+                    }),
+                    icon_bytes: vec![0x91],
+                    ..Default::default()
+                },
+                ItemInfo {
+                    id: 0x0003,
+                    strings: Some(ItemStrings::German {
+                        name: "Chocobo-Bett".to_string(),
+                        gender: Gender::Neuter,
+                        article_type: GermanArticle::Das,
+                        log_names: [
+                            "Chocobo-Bett".to_string(),
+                            "Chocobo-Betten".to_string(),
+                            "Chocobo-Bettes".to_string(),
+                            "Chocobo-Bett".to_string(),
+                            "Chocobo-Betten".to_string(),
+                        ],
+                        description: "Ein Bett für Chocobos.".to_string(),
+                    }),
+                    furnishing: Some(FurnishingData {
+                        element: Element::Earth,
+                        storage_slots: 0,
+                        unknown3: 0,
+                    }),
+                    icon_bytes: vec![0x91],
+                    ..Default::default()
+                },
+            ],
+        };
+
+        let bytes = table.to_bytes().unwrap();
+        let res = ItemInfoTable::from_bytes_checked(&bytes).unwrap();
+
+        // Make sure the variants are kept when going through YAML as well
+        let yaml = serde_yaml::to_string(&res).unwrap();
+        let res: ItemInfoTable = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(res.to_bytes().unwrap(), bytes);
+
+        assert!(matches!(
+            res.items[0].strings,
+            Some(ItemStrings::French {
+                gender: Gender::Masculine,
+                ..
+            })
+        ));
+        if let Some(ItemStrings::German { log_names, .. }) = &res.items[1].strings {
+            assert_eq!(log_names[2], "Chocobo-Bettes");
+        } else {
+            panic!("Expected german strings")
+        }
+    }
+
+    #[test]
+    pub fn currency_and_monipulator() {
+        let table = ItemInfoTable {
+            items: vec![
+                ItemInfo {
+                    id: 0xFFFF,
+                    strings: Some(ItemStrings::English {
+                        name: "gil".to_string(),
+                        article_type: EnglishArticle::A,
+                        singular_name: "gil".to_string(),
+                        plural_name: "gil".to_string(),
+                        description: "".to_string(),
+                    }),
+                    item_type: ItemType::Currency,
+                    currency: Some(CurrencyData { unknown1: 0 }),
+                    icon_bytes: vec![0x91],
+                    ..Default::default()
+                },
+                ItemInfo {
+                    id: 0x7800,
+                    strings: Some(ItemStrings::Name {
+                        name: "Rabbit".to_string(),
+                    }),
+                    stack_size: 1,
+                    monipulator: Some(MonipulatorData {
+                        unknown1: 2,
+                        unknowns: core::array::from_fn(|idx| idx as u32),
+                    }),
+                    icon_bytes: vec![0x91],
+                    ..Default::default()
+                },
+            ],
+        };
+
+        let bytes = table.to_bytes().unwrap();
+        let res = ItemInfoTable::from_bytes_checked(&bytes).unwrap();
+
+        let yaml = serde_yaml::to_string(&res).unwrap();
+        let res: ItemInfoTable = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(res.to_bytes().unwrap(), bytes);
+
+        assert!(res.items[0].currency.is_some());
+        assert_eq!(res.items[1].stack_size, 1);
+        assert_eq!(res.items[1].monipulator.as_ref().unwrap().unknowns[23], 23);
+        assert!(matches!(
+            &res.items[1].strings,
+            Some(ItemStrings::Name { name }) if name == "Rabbit"
+        ));
+    }
 }
@@ -2,7 +2,7 @@ use std::collections::BTreeMap;
 
 use crate::{
     enums::{Element, JobEnum, MagicType, SkillType},
-    serde_base64, serde_hex,
+    serde_hex,
     utils::{decode_data_block_masked, encode_data_block_masked},
 };
 use anyhow::{anyhow, Result};
@@ -19,9 +19,9 @@ use crate::{dat_format::DatFormat, enums::AbilityType, flags::ValidTargets};
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(tag = "type", content = "entries")]
 pub enum Section {
-    Mnc2(#[serde(with = "serde_base64")] Vec<u8>),
-    Mon_(#[serde(with = "serde_base64")] Vec<u8>),
-    Levc(#[serde(with = "serde_base64")] Vec<u8>),
+    Mnc2(Mnc2Table),
+    Mon_(Vec<MonInfo>),
+    Levc(LevelTable),
     Comm(Vec<AbilityInfo>),
     Mgc_(Vec<MagicInfo>),
 
@@ -72,9 +72,9 @@ impl Section {
         walker.expect_n_msg::<u8>(0, 8, "Padding after section size info")?;
 
         let section = match section_code.as_str() {
-            "mnc2" => Section::Mnc2(walker.take_bytes(section_size as usize)?.to_vec()),
-            "mon_" => Section::Mon_(walker.take_bytes(section_size as usize)?.to_vec()),
-            "levc" => Section::Levc(walker.take_bytes(section_size as usize)?.to_vec()),
+            "mnc2" => Section::Mnc2(Mnc2Table::parse(walker, section_size)?),
+            "mon_" => Section::Mon_(MonInfo::parse_all(walker, section_size)?),
+            "levc" => Section::Levc(LevelTable::parse(walker, section_size)?),
             "comm" => Section::Comm(AbilityInfo::parse_all(walker, section_size)?),
             "mgc_" => Section::Mgc_(MagicInfo::parse_all(walker, section_size)?),
             "end\0" => Section::End,
@@ -111,23 +111,26 @@ impl Section {
 
     pub fn write<T: WritingByteWalker>(&self, walker: &mut T) -> Result<()> {
         match self {
-            Section::Mnc2(bytes) => {
+            Section::Mnc2(mnc2) => {
                 walker.write_str("mnc2");
-                walker.write(self.get_section_info(bytes.len() as u32));
-                walker.skip(8);
-                walker.write_bytes(&bytes);
+                let size_info_offset = walker.offset();
+                walker.skip(12);
+                let content_len = mnc2.write(walker)?;
+                walker.write_at(size_info_offset, self.get_section_info(content_len));
             }
-            Section::Mon_(bytes) => {
+            Section::Mon_(mon) => {
                 walker.write_str("mon_");
-                walker.write(self.get_section_info(bytes.len() as u32));
-                walker.skip(8);
-                walker.write_bytes(&bytes);
+                let size_info_offset = walker.offset();
+                walker.skip(12);
+                let content_len = MonInfo::write_all(mon, walker)?;
+                walker.write_at(size_info_offset, self.get_section_info(content_len));
             }
-            Section::Levc(bytes) => {
+            Section::Levc(levels) => {
                 walker.write_str("levc");
-                walker.write(self.get_section_info(bytes.len() as u32));
-                walker.skip(8);
-                walker.write_bytes(&bytes);
+                let size_info_offset = walker.offset();
+                walker.skip(12);
+                let content_len = levels.write(walker)?;
+                walker.write_at(size_info_offset, self.get_section_info(content_len));
             }
             Section::Comm(comm) => {
                 walker.write_str("comm");
@@ -151,16 +154,16 @@ impl Section {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct AbilityInfo {
-    id: u16,
-    ability_type: AbilityType,
-    icon_id: u8,
-    mp_cost: u16,
-    unknown1: u16,
-    shared_timer_id: u16,
-    valid_targets: ValidTargets,
-    tp_cost: i16,
+    pub id: u16,
+    pub ability_type: AbilityType,
+    pub icon_id: u8,
+    pub mp_cost: u16,
+    pub unknown1: u16,
+    pub shared_timer_id: u16,
+    pub valid_targets: ValidTargets,
+    pub tp_cost: i16,
 
     #[serde(with = "serde_hex")]
     unknowns: Vec<u8>,
@@ -219,19 +222,19 @@ impl SectionInfo for AbilityInfo {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct MagicInfo {
-    index: u16,
-    magic_type: MagicType,
-    element: Element,
-    valid_targets: ValidTargets,
-    skill_type: SkillType,
-    mp_cost: u16,
-    cast_time: u8,
-    recast_time: u8,
-    level_required: BTreeMap<JobEnum, u16>,
-    id: u16,
-    icon_id: u8,
+    pub index: u16,
+    pub magic_type: MagicType,
+    pub element: Element,
+    pub valid_targets: ValidTargets,
+    pub skill_type: SkillType,
+    pub mp_cost: u16,
+    pub cast_time: u8,
+    pub recast_time: u8,
+    pub level_required: BTreeMap<JobEnum, u16>,
+    pub id: u16,
+    pub icon_id: u8,
 
     #[serde(with = "serde_hex")]
     unknowns: Vec<u8>,
@@ -321,31 +324,255 @@ impl SectionInfo for MagicInfo {
     }
 }
 
+/// Number of entries in the `mnc2` section, which are followed by its level tables.
+const MNC2_ENTRY_COUNT: usize = 1023;
+
+/// Number of levels of the level curve and level tables in the `mnc2` section.
+const MNC2_LEVEL_COUNT: usize = 31;
+
+/// The `mnc2` section, with a curve of values by level, entries which refer to up to three
+/// level tables each, and the level tables. Table 0 is unused, so that entries use 0 for
+/// none of them. Unused entries and level tables at the end are all zeroes, so only the
+/// number of unused level tables is kept.
 #[derive(Debug, Serialize, Deserialize)]
+pub struct Mnc2Table {
+    pub level_curve: Vec<u16>,
+    pub entries: Vec<Mnc2Entry>,
+    pub level_tables: Vec<Mnc2LevelTable>,
+    pub unused_level_tables: u32,
+}
+
+impl Mnc2Table {
+    fn parse<T: ByteWalker>(walker: &mut T, section_size: u32) -> Result<Self> {
+        let header_size = (MNC2_LEVEL_COUNT + 7) * 2 + MNC2_ENTRY_COUNT * Mnc2Entry::entry_size();
+        if (section_size as usize) < header_size {
+            return Err(anyhow!(
+                "Expected mnc2 section to be at least {} bytes long, but got {}.",
+                header_size,
+                section_size
+            ));
+        }
+
+        let level_curve = (0..MNC2_LEVEL_COUNT)
+            .map(|_| walker.step::<u16>())
+            .collect::<Result<Vec<_>>>()?;
+        walker.expect_n_msg::<u16>(0, 7, "Padding after level curve")?;
+
+        let mut entries = (0..MNC2_ENTRY_COUNT)
+            .map(|_| Mnc2Entry::parse(walker))
+            .collect::<Result<Vec<_>>>()?;
+        let unused_count = entries
+            .iter()
+            .rev()
+            .take_while(|entry| **entry == Mnc2Entry::default())
+            .count();
+        entries.truncate(entries.len() - unused_count);
+
+        let mut level_tables =
+            Mnc2LevelTable::parse_all(walker, section_size - header_size as u32)?;
+        let unused_count = level_tables
+            .iter()
+            .rev()
+            .take_while(|table| table.is_unused())
+            .count();
+        level_tables.truncate(level_tables.len() - unused_count);
+
+        Ok(Mnc2Table {
+            level_curve,
+            entries,
+            level_tables,
+            unused_level_tables: unused_count as u32,
+        })
+    }
+
+    fn write<T: WritingByteWalker>(&self, walker: &mut T) -> Result<u32> {
+        let start_offset = walker.offset();
+
+        if self.level_curve.len() != MNC2_LEVEL_COUNT {
+            return Err(anyhow!(
+                "Expected {} values in the level curve, but got {}.",
+                MNC2_LEVEL_COUNT,
+                self.level_curve.len()
+            ));
+        }
+        for value in &self.level_curve {
+            walker.write(*value);
+        }
+        walker.write_bytes(&[0; 14]);
+
+        if self.entries.len() > MNC2_ENTRY_COUNT {
+            return Err(anyhow!(
+                "Expected at most {} mnc2 entries, but got {}.",
+                MNC2_ENTRY_COUNT,
+                self.entries.len()
+            ));
+        }
+        Mnc2Entry::write_all(&self.entries, walker)?;
+        for _ in self.entries.len()..MNC2_ENTRY_COUNT {
+            Mnc2Entry::default().write(walker)?;
+        }
+
+        Mnc2LevelTable::write_all(&self.level_tables, walker)?;
+        walker.write_bytes(&vec![
+            0;
+            self.unused_level_tables as usize
+                * Mnc2LevelTable::entry_size()
+        ]);
+
+        Ok((walker.offset() - start_offset) as u32)
+    }
+}
+
+/// Entry of the `mnc2` section, with the indices of its level tables.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Mnc2Entry {
+    pub level_tables: [u16; 3],
+    pub flag: u16,
+}
+
+impl SectionInfo for Mnc2Entry {
+    #[inline]
+    fn entry_size() -> usize {
+        12
+    }
+
+    fn parse<T: ByteWalker>(walker: &mut T) -> Result<Mnc2Entry> {
+        let level_tables = [walker.step()?, walker.step()?, walker.step()?];
+        walker.expect_msg::<u16>(0, "Padding after level tables")?;
+        let flag = walker.step()?;
+        walker.expect_msg::<u16>(0, "End of mnc2 entry")?;
+
+        Ok(Mnc2Entry { level_tables, flag })
+    }
+
+    fn write<T: WritingByteWalker>(&self, walker: &mut T) -> Result<()> {
+        for index in self.level_tables {
+            walker.write(index);
+        }
+        walker.write(0u16);
+        walker.write(self.flag);
+        walker.write(0u16);
+
+        Ok(())
+    }
+}
+
+/// Table of the `mnc2` section with a value for each level, which may be negative.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Mnc2LevelTable {
+    pub unknowns: [u16; 6],
+    pub values: Vec<i16>,
+}
+
+impl Mnc2LevelTable {
+    fn is_unused(&self) -> bool {
+        self.unknowns == [0; 6] && self.values.iter().all(|value| *value == 0)
+    }
+}
+
+impl SectionInfo for Mnc2LevelTable {
+    #[inline]
+    fn entry_size() -> usize {
+        (6 + MNC2_LEVEL_COUNT + 1) * 2
+    }
+
+    fn parse<T: ByteWalker>(walker: &mut T) -> Result<Mnc2LevelTable> {
+        let mut unknowns = [0; 6];
+        for unknown in &mut unknowns {
+            *unknown = walker.step()?;
+        }
+        let values = (0..MNC2_LEVEL_COUNT)
+            .map(|_| walker.step::<i16>())
+            .collect::<Result<Vec<_>>>()?;
+        walker.expect_msg::<u16>(0, "End of level table")?;
+
+        Ok(Mnc2LevelTable { unknowns, values })
+    }
+
+    fn write<T: WritingByteWalker>(&self, walker: &mut T) -> Result<()> {
+        if self.values.len() != MNC2_LEVEL_COUNT {
+            return Err(anyhow!(
+                "Expected {} values in level table, but got {}.",
+                MNC2_LEVEL_COUNT,
+                self.values.len()
+            ));
+        }
+
+        for unknown in self.unknowns {
+            walker.write(unknown);
+        }
+        for value in &self.values {
+            walker.write(*value);
+        }
+        walker.write(0u16);
+
+        Ok(())
+    }
+}
+
+/// The `levc` section, with a value for each level starting at level 0.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LevelTable {
+    pub levels: BTreeMap<u16, u16>,
+}
+
+impl LevelTable {
+    fn parse<T: ByteWalker>(walker: &mut T, section_size: u32) -> Result<Self> {
+        if !section_size.is_multiple_of(2) {
+            return Err(anyhow!(
+                "Expected an even length of the levc section, but got {}.",
+                section_size
+            ));
+        }
+
+        let levels = (0..section_size as u16 / 2)
+            .map(|level| Ok((level, walker.step::<u16>()?)))
+            .collect::<Result<BTreeMap<_, _>>>()?;
+
+        Ok(LevelTable { levels })
+    }
+
+    fn write<T: WritingByteWalker>(&self, walker: &mut T) -> Result<u32> {
+        for (expected_level, (level, value)) in self.levels.iter().enumerate() {
+            if *level as usize != expected_level {
+                return Err(anyhow!(
+                    "Expected a value for every level, but level {} is missing.",
+                    expected_level
+                ));
+            }
+            walker.write(*value);
+        }
+
+        Ok(self.levels.len() as u32 * 2)
+    }
+}
+
+/// Entry of the monstrosity menu, which lists the entries of each category in menu order.
+/// The category is `0` or `1` for most entries, and entries of all zeroes are unused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct MonInfo {
-    #[serde(with = "serde_hex")]
-    unknowns: Vec<u8>,
+    pub index: u8,
+    pub category: u8,
 }
 
 impl SectionInfo for MonInfo {
     #[inline]
     fn entry_size() -> usize {
-        64
+        2
     }
 
     fn parse<T: ByteWalker>(walker: &mut T) -> Result<MonInfo> {
-        let data_bytes = walker.take_bytes(Self::entry_size())?.to_vec();
-        let mut data_walker = BufferedByteWalker::on(data_bytes);
-
-        let info = MonInfo {
-            unknowns: data_walker.take_bytes(data_walker.remaining())?.to_vec(),
-        };
-
-        Ok(info)
+        Ok(MonInfo {
+            index: walker.step()?,
+            category: walker.step()?,
+        })
     }
 
-    fn write<T: WritingByteWalker>(&self, _walker: &mut T) -> Result<()> {
-        todo!()
+    fn write<T: WritingByteWalker>(&self, walker: &mut T) -> Result<()> {
+        walker.write(self.index);
+        walker.write(self.category);
+
+        Ok(())
     }
 }
 
@@ -355,6 +582,36 @@ pub struct MenuTable {
 }
 
 impl MenuTable {
+    /// All spells of the `mgc_` sections, in the order they're stored in.
+    pub fn spells(&self) -> impl Iterator<Item = &MagicInfo> {
+        self.sections.iter().flat_map(|section| match section {
+            Section::Mgc_(magic) => magic.as_slice(),
+            _ => &[],
+        })
+    }
+
+    pub fn spells_mut(&mut self) -> impl Iterator<Item = &mut MagicInfo> {
+        self.sections.iter_mut().flat_map(|section| match section {
+            Section::Mgc_(magic) => magic.as_mut_slice(),
+            _ => &mut [],
+        })
+    }
+
+    /// All abilities of the `comm` sections, in the order they're stored in.
+    pub fn abilities(&self) -> impl Iterator<Item = &AbilityInfo> {
+        self.sections.iter().flat_map(|section| match section {
+            Section::Comm(abilities) => abilities.as_slice(),
+            _ => &[],
+        })
+    }
+
+    pub fn abilities_mut(&mut self) -> impl Iterator<Item = &mut AbilityInfo> {
+        self.sections.iter_mut().flat_map(|section| match section {
+            Section::Comm(abilities) => abilities.as_mut_slice(),
+            _ => &mut [],
+        })
+    }
+
     pub fn parse<T: ByteWalker>(walker: &mut T) -> Result<Self> {
         walker.expect_utf8_str("menu")?;
         walker.expect::<u32>(0x101)?;
@@ -407,13 +664,106 @@ impl DatFormat for MenuTable {
     }
 }
 
+#[cfg(any(test, feature = "fixtures"))]
+impl MenuTable {
+    /// One section of each type.
+    pub fn fixture() -> Self {
+        MenuTable {
+            sections: vec![
+                // Table 0 is unused, and the unused tables at the end pad the section to
+                // a multiple of 16 bytes.
+                Section::Mnc2(Mnc2Table {
+                    level_curve: (0..31).map(|level| 30 + 20 * level).collect(),
+                    entries: vec![
+                        Mnc2Entry {
+                            level_tables: [1, 0, 0],
+                            flag: 0,
+                        },
+                        Mnc2Entry {
+                            level_tables: [1, 1, 0],
+                            flag: 1,
+                        },
+                    ],
+                    level_tables: vec![
+                        Mnc2LevelTable {
+                            unknowns: [0; 6],
+                            values: vec![0; 31],
+                        },
+                        Mnc2LevelTable {
+                            unknowns: [4, 0, 0, 0, 30, 0],
+                            values: (0..31).map(|level| level / 2 - 1).collect(),
+                        },
+                    ],
+                    unused_level_tables: 2,
+                }),
+                // Unused entries are all zeroes, and precede the used ones.
+                Section::Mon_(
+                    [
+                        (0, 0),
+                        (0, 0),
+                        (1, 0),
+                        (0, 1),
+                        (1, 1),
+                        (2, 1),
+                        (2, 0),
+                        (3, 1),
+                    ]
+                    .into_iter()
+                    .map(|(index, category)| MonInfo { index, category })
+                    .collect(),
+                ),
+                Section::Levc(LevelTable {
+                    levels: [0, 50, 70, 100, 130, 160, 190, 210]
+                        .into_iter()
+                        .enumerate()
+                        .map(|(level, value)| (level as u16, value))
+                        .collect(),
+                }),
+                // Section sizes are stored in multiples of 16 bytes, so use 4 spells.
+                Section::Mgc_(
+                    (1..=4)
+                        .map(|index| MagicInfo {
+                            index,
+                            magic_type: MagicType::WhiteMagic,
+                            element: Element::Light,
+                            valid_targets: ValidTargets::SelfTarget | ValidTargets::PartyMember,
+                            skill_type: SkillType::from(0x21),
+                            mp_cost: 8 * index,
+                            cast_time: 8,
+                            recast_time: 20,
+                            level_required: BTreeMap::from([
+                                (JobEnum::WHM, index),
+                                (JobEnum::RDM, index + 2),
+                            ]),
+                            id: index,
+                            icon_id: 0,
+                            unknowns: vec![0; 34],
+                        })
+                        .collect(),
+                ),
+                Section::Comm(vec![AbilityInfo {
+                    id: 1,
+                    ability_type: AbilityType::Job,
+                    icon_id: 0,
+                    mp_cost: 0,
+                    unknown1: 0,
+                    shared_timer_id: 0,
+                    valid_targets: ValidTargets::SelfTarget,
+                    tp_cost: -1,
+                    unknowns: vec![0; 33],
+                }]),
+            ],
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::path::PathBuf;
 
     use crate::dat_format::DatFormat;
 
-    use super::MenuTable;
+    use super::{MenuTable, Mnc2Entry, MonInfo, Section};
 
     #[test]
     pub fn menu_table() {
@@ -421,6 +771,59 @@ mod tests {
         dat_path.push("resources/test/menu.DAT");
 
         MenuTable::check_path(&dat_path).unwrap();
-        MenuTable::from_path_checked(&dat_path).unwrap();
+        MenuTable::from_path_checked_during(&dat_path).unwrap();
+        let res = MenuTable::from_path_checked(&dat_path).unwrap();
+
+        let mon = res
+            .sections
+            .iter()
+            .find_map(|section| match section {
+                Section::Mon_(mon) => Some(mon),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(mon.len(), 512);
+        assert_eq!(
+            mon[352],
+            MonInfo {
+                index: 1,
+                category: 0
+            }
+        );
+        assert_eq!(
+            mon[353],
+            MonInfo {
+                index: 0,
+                category: 1
+            }
+        );
+
+        let Section::Mnc2(mnc2) = &res.sections[0] else {
+            panic!("Expected mnc2 as the first section.");
+        };
+        assert_eq!(mnc2.level_curve[..4], [30, 50, 80, 120]);
+        assert_eq!(mnc2.level_curve[30], 4750);
+        assert_eq!(mnc2.entries.len(), 284);
+        assert_eq!(
+            mnc2.entries[6],
+            Mnc2Entry {
+                level_tables: [1, 2, 7],
+                flag: 0
+            }
+        );
+        assert_eq!(mnc2.level_tables.len(), 264);
+        assert!(mnc2
+            .entries
+            .iter()
+            .flat_map(|entry| entry.level_tables)
+            .all(|index| (index as usize) < mnc2.level_tables.len()));
+        assert_eq!(mnc2.level_tables[1].unknowns, [128, 0, 0, 0, 1296, 0]);
+        assert_eq!(mnc2.level_tables[1].values[..4], [0, 1, 2, 4]);
+
+        let Section::Levc(levels) = &res.sections[2] else {
+            panic!("Expected levc as the third section.");
+        };
+        assert_eq!(levels.levels.len(), 256);
+        assert_eq!(levels.levels[&3], 100);
     }
 }
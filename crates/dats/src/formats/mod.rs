@@ -4,8 +4,10 @@ pub mod dmsg1_string_table;
 pub mod dmsg2_string_table;
 pub mod dmsg3_string_table;
 pub mod entity_names;
+pub mod event;
 pub mod item_info;
 pub mod menu_table;
+pub mod quest_info;
 pub mod status_info;
 pub mod string_table;
 pub mod xistring_table;
@@ -0,0 +1,240 @@
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+use common::{byte_walker::ByteWalker, writing_byte_walker::WritingByteWalker};
+use serde_derive::{Deserialize, Serialize};
+
+use crate::{
+    dat_format::DatFormat,
+    entries::DatEntries,
+    strings::{DatStrings, DatStringsMut},
+};
+
+use super::{
+    dmsg::{DmsgContent, DmsgStringList},
+    dmsg3_string_table::Dmsg3StringTable,
+};
+
+/// Quest log of one area. It's stored like other d_msg string tables, with the title and
+/// description of a quest as the first strings of its list.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct QuestInfoTable {
+    pub bytes_per_entry: u32,
+    pub flip_bytes: bool,
+    pub quests: BTreeMap<u32, QuestInfo>,
+}
+
+/// Strings of a quest, which are written back in the order they're read in.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuestInfo {
+    /// Numbers before the title.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub numbers: Vec<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// The string directly following the title.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Content following the description.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extra: Vec<DmsgContent>,
+}
+
+impl QuestInfo {
+    fn from_list(list: DmsgStringList) -> Self {
+        let mut content = list.content.into_iter().peekable();
+
+        let mut numbers = vec![];
+        while let Some(DmsgContent::Number { number }) =
+            content.next_if(|c| matches!(c, DmsgContent::Number { .. }))
+        {
+            numbers.push(number);
+        }
+
+        let mut next_string = || match content.next_if(|c| matches!(c, DmsgContent::String { .. }))
+        {
+            Some(DmsgContent::String { string }) => Some(string),
+            _ => None,
+        };
+        let title = next_string();
+        let description = title.as_ref().and_then(|_| next_string());
+
+        QuestInfo {
+            numbers,
+            title,
+            description,
+            extra: content.collect(),
+        }
+    }
+
+    fn to_list(&self) -> DmsgStringList {
+        let numbers = self
+            .numbers
+            .iter()
+            .map(|number| DmsgContent::Number { number: *number });
+        let strings = [&self.title, &self.description]
+            .into_iter()
+            .flatten()
+            .map(|string| DmsgContent::String {
+                string: string.clone(),
+            });
+
+        DmsgStringList {
+            content: numbers
+                .chain(strings)
+                .chain(self.extra.iter().cloned())
+                .collect(),
+        }
+    }
+
+    /// The title, description and any strings after them, in list order.
+    fn strings(&self) -> impl Iterator<Item = &str> {
+        let extra = self.extra.iter().filter_map(|content| match content {
+            DmsgContent::String { string } => Some(string.as_str()),
+            DmsgContent::Number { .. } => None,
+        });
+
+        [&self.title, &self.description]
+            .into_iter()
+            .flatten()
+            .map(String::as_str)
+            .chain(extra)
+    }
+
+    fn strings_mut(&mut self) -> impl Iterator<Item = &mut String> {
+        let extra = self.extra.iter_mut().filter_map(|content| match content {
+            DmsgContent::String { string } => Some(string),
+            DmsgContent::Number { .. } => None,
+        });
+
+        [&mut self.title, &mut self.description]
+            .into_iter()
+            .flatten()
+            .chain(extra)
+    }
+}
+
+impl QuestInfoTable {
+    pub fn from_string_table(table: Dmsg3StringTable) -> Self {
+        QuestInfoTable {
+            bytes_per_entry: table.bytes_per_entry,
+            flip_bytes: table.flip_bytes,
+            quests: table
+                .lists
+                .into_iter()
+                .map(|(idx, list)| (idx, QuestInfo::from_list(list)))
+                .collect(),
+        }
+    }
+
+    pub fn to_string_table(&self) -> Dmsg3StringTable {
+        Dmsg3StringTable {
+            bytes_per_entry: self.bytes_per_entry,
+            flip_bytes: self.flip_bytes,
+            lists: self
+                .quests
+                .iter()
+                .map(|(idx, quest)| (*idx, quest.to_list()))
+                .collect(),
+        }
+    }
+}
+
+impl DatFormat for QuestInfoTable {
+    fn from<T: ByteWalker>(walker: &mut T) -> Result<Self> {
+        Ok(Self::from_string_table(Dmsg3StringTable::parse(walker)?))
+    }
+
+    fn check_type<T: ByteWalker>(walker: &mut T) -> Result<()> {
+        Dmsg3StringTable::check_type(walker)
+    }
+
+    fn write<T: WritingByteWalker>(&self, walker: &mut T) -> Result<()> {
+        DatFormat::write(&self.to_string_table(), walker)
+    }
+}
+
+impl DatEntries for QuestInfoTable {
+    type Entry = QuestInfo;
+    const ENTRIES_FIELD: &'static str = "quests";
+
+    fn entries(&self) -> BTreeMap<u32, &QuestInfo> {
+        self.quests.iter().map(|(id, list)| (*id, list)).collect()
+    }
+}
+
+impl DatStrings for QuestInfoTable {
+    fn strings(&self) -> Vec<(u32, &str)> {
+        self.quests
+            .iter()
+            .flat_map(|(id, quest)| quest.strings().map(move |string| (*id, string)))
+            .collect()
+    }
+}
+
+impl DatStringsMut for QuestInfoTable {
+    fn strings_mut(&mut self) -> Vec<(u32, &mut String)> {
+        self.quests
+            .iter_mut()
+            .flat_map(|(id, quest)| quest.strings_mut().map(move |string| (*id, string)))
+            .collect()
+    }
+}
+
+#[cfg(any(test, feature = "fixtures"))]
+impl QuestInfoTable {
+    /// A quest with a title and description, and one with only a number.
+    pub fn fixture() -> Self {
+        QuestInfoTable {
+            bytes_per_entry: 0x100,
+            flip_bytes: true,
+            quests: BTreeMap::from([
+                (
+                    0,
+                    QuestInfo {
+                        numbers: vec![],
+                        title: Some("A Test of Patience".to_string()),
+                        description: Some("Wait for the test to pass.".to_string()),
+                        extra: vec![DmsgContent::Number { number: 1 }],
+                    },
+                ),
+                (
+                    1,
+                    QuestInfo {
+                        numbers: vec![3],
+                        ..Default::default()
+                    },
+                ),
+            ]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::{dat_format::DatFormat, formats::dmsg::DmsgContent};
+
+    use super::QuestInfoTable;
+
+    #[test]
+    pub fn key_items_as_quest_log() {
+        // No quest log DAT is checked in, but key items are stored the same way.
+        // Reading checks that every list is written back to the same bytes.
+        let mut dat_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        dat_path.push("resources/test/key_items.DAT");
+
+        QuestInfoTable::check_path(&dat_path).unwrap();
+        let res = QuestInfoTable::from_path_checked_during(&dat_path).unwrap();
+
+        let key_item = &res.quests[&1534];
+        assert_eq!(key_item.numbers, vec![619, 3]);
+        assert_eq!(
+            key_item.extra[0],
+            DmsgContent::String {
+                string: "All-You-Can-Ride Pass".to_string()
+            }
+        );
+    }
+}
@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use anyhow::{anyhow, Ok, Result};
 use common::{
     byte_walker::{BufferedByteWalker, ByteWalker},
@@ -5,25 +7,45 @@ use common::{
     writing_byte_walker::WritingByteWalker,
 };
 use encoding::{decoder::Decoder, encoder::Encoder};
+use serde::{ser::SerializeStruct, Serializer};
 use serde_derive::{Deserialize, Serialize};
 
-use crate::serde_base64;
 use crate::{
     dat_format::DatFormat,
+    entries::DatEntries,
+    serde_icon::{self, RecordIcon},
+    strings::{DatStrings, DatStringsMut},
     utils::{decode_data_block, encode_data_block},
 };
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Deserialize)]
 pub struct StatusInfo {
     id: u16,
     description: String,
 
     flag: u16,
 
-    #[serde(with = "serde_base64")]
+    #[serde(deserialize_with = "serde_icon::deserialize")]
     icon_bytes: Vec<u8>,
 }
 
+impl serde::Serialize for StatusInfo {
+    fn serialize<S: Serializer>(&self, s: S) -> std::result::Result<S::Ok, S::Error> {
+        let mut state = s.serialize_struct("StatusInfo", 4)?;
+        state.serialize_field("id", &self.id)?;
+        state.serialize_field("description", &self.description)?;
+        state.serialize_field("flag", &self.flag)?;
+        state.serialize_field(
+            "icon_bytes",
+            &RecordIcon {
+                id: &self.id,
+                bytes: &self.icon_bytes,
+            },
+        )?;
+        state.end()
+    }
+}
+
 impl StatusInfo {
     pub fn parse<T: ByteWalker>(walker: &mut T) -> Result<StatusInfo> {
         let mut data_bytes = walker.take_bytes(0x280)?.to_vec();
@@ -146,11 +168,67 @@ impl DatFormat for StatusInfoTable {
     }
 }
 
+impl DatEntries for StatusInfoTable {
+    type Entry = StatusInfo;
+    const ENTRIES_FIELD: &'static str = "status_infos";
+
+    fn entries(&self) -> BTreeMap<u32, &StatusInfo> {
+        self.status_infos
+            .iter()
+            .map(|info| (info.id as u32, info))
+            .collect()
+    }
+}
+
+impl DatStrings for StatusInfoTable {
+    fn strings(&self) -> Vec<(u32, &str)> {
+        self.status_infos
+            .iter()
+            .map(|info| (info.id as u32, info.description.as_str()))
+            .collect()
+    }
+}
+
+impl DatStringsMut for StatusInfoTable {
+    fn strings_mut(&mut self) -> Vec<(u32, &mut String)> {
+        self.status_infos
+            .iter_mut()
+            .map(|info| (info.id as u32, &mut info.description))
+            .collect()
+    }
+}
+
+#[cfg(any(test, feature = "fixtures"))]
+use crate::image::XiImage;
+
+#[cfg(any(test, feature = "fixtures"))]
+impl StatusInfoTable {
+    /// Two statuses, one of them with a decodable icon.
+    pub fn fixture() -> Self {
+        StatusInfoTable {
+            status_infos: vec![
+                StatusInfo {
+                    id: 0,
+                    description: "You have been knocked unconscious.".to_string(),
+                    flag: 0,
+                    icon_bytes: XiImage::fixture().to_bytes().unwrap(),
+                },
+                StatusInfo {
+                    id: 1,
+                    description: "You are feeling weak.".to_string(),
+                    flag: 1,
+                    icon_bytes: vec![0x91, 0x04, 0x05],
+                },
+            ],
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::path::PathBuf;
 
-    use crate::dat_format::DatFormat;
+    use crate::{dat_format::DatFormat, image::ImageFiles};
 
     use super::StatusInfoTable;
 
@@ -172,4 +250,26 @@ mod tests {
             "Ullegore is making you forget the true meaning of \"fun\"!".to_string()
         );
     }
+
+    #[test]
+    pub fn icons_as_png_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let yaml_path = dir.path().join("status_info.yml");
+        let table = StatusInfoTable::fixture();
+
+        let yaml =
+            ImageFiles::for_file(&yaml_path).scoped(|| serde_yaml::to_string(&table).unwrap());
+        assert!(yaml.contains("png: status_info/0.png"));
+        assert!(dir.path().join("status_info/0.png").exists());
+
+        // The second icon isn't a valid image, so it's kept as it is
+        assert!(!dir.path().join("status_info/1.png").exists());
+
+        let res: StatusInfoTable =
+            ImageFiles::for_file(&yaml_path).scoped(|| serde_yaml::from_str(&yaml).unwrap());
+        assert_eq!(res.to_bytes().unwrap(), table.to_bytes().unwrap());
+
+        // Reading PNG files requires knowing where they are
+        assert!(serde_yaml::from_str::<StatusInfoTable>(&yaml).is_err());
+    }
 }
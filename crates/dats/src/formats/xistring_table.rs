@@ -5,21 +5,68 @@ use common::{byte_walker::ByteWalker, expect_msg, writing_byte_walker::WritingBy
 use encoding::{decoder::Decoder, encoder::Encoder};
 use serde_derive::{Deserialize, Serialize};
 
-use crate::dat_format::DatFormat;
+use crate::{
+    dat_format::DatFormat,
+    entries::DatEntries,
+    strings::{DatStrings, DatStringsMut},
+};
 
 #[derive(Debug, Default, Serialize, Deserialize)]
 pub struct XiStringTable {
-    strings: BTreeMap<u32, String>,
+    // Differs between the tables, e.g. IngameMessages2 has 304231515.
+    #[serde(default = "default_unknown2")]
+    unknown2: u32,
+
+    strings: BTreeMap<u32, XiString>,
+}
+
+/// Strings are just the text in most tables, but some (like TimeAndPronouns)
+/// have an additional value in their metadata.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum XiString {
+    WithMeta { string: String, unknown1: u16 },
+    Plain(String),
+}
+
+impl XiString {
+    pub fn string(&self) -> &String {
+        match self {
+            XiString::WithMeta { string, .. } => string,
+            XiString::Plain(string) => string,
+        }
+    }
+
+    pub fn string_mut(&mut self) -> &mut String {
+        match self {
+            XiString::WithMeta { string, .. } => string,
+            XiString::Plain(string) => string,
+        }
+    }
+
+    fn unknown1(&self) -> u16 {
+        match self {
+            XiString::WithMeta { unknown1, .. } => *unknown1,
+            XiString::Plain(_) => 0,
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 struct XiStringMeta {
     offset: u32,
     size: u16,
+    unknown1: u16,
 }
 
 const HEADER_SIZE: u32 = 0x38;
 
+const DEFAULT_UNKNOWN2: u32 = 304091210;
+
+fn default_unknown2() -> u32 {
+    DEFAULT_UNKNOWN2
+}
+
 impl XiStringTable {
     fn parse<T: ByteWalker>(walker: &mut T) -> Result<Self> {
         walker.expect_utf8_str("XISTRING\0\0")?;
@@ -44,24 +91,22 @@ impl XiStringTable {
         }
 
         let unknown2: u32 = walker.step()?;
-        // TODO: this value is different in at least IngameMessages2, where it is 304231515
-        if unknown2 != 304091210 {
-            return Err(anyhow!("unknown2 is {}", unknown2));
-        }
 
         // Read metadata
         let mut metas = vec![];
         for _ in 0..entry_count {
             let offset: u32 = walker.step()?;
             let size: u16 = walker.step()?;
-
-            // TODO: TimeAndPronouns has a 1 here instead of 0
-            walker.expect_msg(0u16, "Unknown meta 1")?;
+            let unknown1: u16 = walker.step()?;
 
             walker.expect_msg(0u16, "Unknown meta 2")?;
             walker.expect_msg(0u16, "Unknown meta 3")?;
 
-            metas.push(XiStringMeta { offset, size });
+            metas.push(XiStringMeta {
+                offset,
+                size,
+                unknown1,
+            });
         }
 
         // Read the strings
@@ -75,10 +120,14 @@ impl XiStringTable {
 
             let string_bytes = walker.take_bytes(meta.size as usize)?;
             let string = Decoder::decode_simple(string_bytes)?;
+            let string = match meta.unknown1 {
+                0 => XiString::Plain(string),
+                unknown1 => XiString::WithMeta { string, unknown1 },
+            };
             strings.insert(idx as u32, string);
         }
 
-        Ok(XiStringTable { strings })
+        Ok(XiStringTable { unknown2, strings })
     }
 
     pub fn write<T: WritingByteWalker>(&self, walker: &mut T) -> Result<()> {
@@ -93,16 +142,16 @@ impl XiStringTable {
             .strings
             .iter()
             .map(|(idx, string)| {
-                let encoded = Encoder::encode_simple(&string)?;
+                let encoded = Encoder::encode_simple(string.string())?;
 
-                Ok((idx, encoded))
+                Ok((idx, (encoded, string.unknown1())))
             })
             .collect::<Result<BTreeMap<_, _>>>()?;
 
         let entry_count = self.strings.keys().max().copied().unwrap_or_default() as u32 + 1;
         let data_bytes = encoded_strings
             .iter()
-            .map(|(_, str)| str.len())
+            .map(|(_, (str, _))| str.len())
             .sum::<usize>() as u32
             + entry_count; // A zero-byte to end each entry
 
@@ -116,31 +165,30 @@ impl XiStringTable {
         walker.write(data_bytes);
 
         walker.write::<u32>(0); // unknown1
-        walker.write::<u32>(304091210); // unknown2
+        walker.write::<u32>(self.unknown2);
 
         // Write metadata for strings
         let mut current_string_offset = 0;
         for idx in 0..entry_count {
-            let string_len = encoded_strings
+            let (string_len, unknown1) = encoded_strings
                 .get(&idx)
-                .map(|str| str.len())
-                .unwrap_or_default()
-                + 1; // 1 extra byte for string end
+                .map(|(str, unknown1)| (str.len() + 1, *unknown1)) // 1 extra byte for string end
+                .unwrap_or((1, 0));
 
             walker.write(current_string_offset);
             walker.write(string_len as u16);
+            walker.write(unknown1);
 
             // Unknowns
             walker.write(0u16);
             walker.write(0u16);
-            walker.write(0u16);
 
             current_string_offset += string_len as u32;
         }
 
         // Write the strings
         for idx in 0..entry_count {
-            if let Some(encoded_string) = encoded_strings.get(&idx) {
+            if let Some((encoded_string, _)) = encoded_strings.get(&idx) {
                 walker.write_bytes(&encoded_string);
             }
             walker.write::<u8>(0); // End of string
@@ -166,6 +214,60 @@ impl DatFormat for XiStringTable {
     }
 }
 
+impl DatEntries for XiStringTable {
+    type Entry = XiString;
+    const ENTRIES_FIELD: &'static str = "strings";
+
+    fn entries(&self) -> BTreeMap<u32, &XiString> {
+        self.strings
+            .iter()
+            .map(|(idx, string)| (*idx, string))
+            .collect()
+    }
+}
+
+impl DatStrings for XiStringTable {
+    fn strings(&self) -> Vec<(u32, &str)> {
+        self.strings
+            .iter()
+            .map(|(id, string)| (*id, string.string().as_str()))
+            .collect()
+    }
+}
+
+impl DatStringsMut for XiStringTable {
+    fn strings_mut(&mut self) -> Vec<(u32, &mut String)> {
+        self.strings
+            .iter_mut()
+            .map(|(id, string)| (*id, string.string_mut()))
+            .collect()
+    }
+}
+
+#[cfg(any(test, feature = "fixtures"))]
+impl XiStringTable {
+    /// Strings with and without meta values, including an empty one.
+    pub fn fixture() -> Self {
+        XiStringTable {
+            unknown2: DEFAULT_UNKNOWN2,
+            strings: BTreeMap::from([
+                (
+                    0,
+                    XiString::Plain("Searching for lobby server.".to_string()),
+                ),
+                (1, XiString::Plain("".to_string())),
+                (
+                    2,
+                    XiString::WithMeta {
+                        string: "Select a character to play.".to_string(),
+                        unknown1: 1,
+                    },
+                ),
+            ]),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::path::PathBuf;
@@ -181,13 +283,29 @@ mod tests {
         let res = XiStringTable::from_path_checked(&dat_path).unwrap();
 
         assert_eq!(
-            res.strings.get(&0).unwrap(),
-            &"Searching for lobby server.".to_string()
+            res.strings.get(&0).unwrap().string(),
+            "Searching for lobby server."
         );
 
         assert_eq!(
-            res.strings.get(&104).unwrap(),
-            &"Select a character to play.".to_string()
+            res.strings.get(&104).unwrap().string(),
+            "Select a character to play."
         );
     }
+
+    #[test]
+    pub fn header_and_meta_values() {
+        let mut table = XiStringTable::fixture();
+        table.unknown2 = 304231515;
+
+        let bytes = table.to_bytes().unwrap();
+        let res = XiStringTable::from_bytes_checked(&bytes).unwrap();
+        assert_eq!(res.unknown2, 304231515);
+
+        // Strings with meta values have to stay distinct from plain ones through YAML
+        let yaml = serde_yaml::to_string(&res).unwrap();
+        let res: XiStringTable = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(res.strings, table.strings);
+        assert_eq!(res.to_bytes().unwrap(), bytes);
+    }
 }
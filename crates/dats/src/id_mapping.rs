@@ -1,11 +1,12 @@
-use std::sync::OnceLock;
+use std::{collections::BTreeMap, sync::OnceLock};
 
 use crate::{
     base::{Dat, DatByZone},
     formats::{
         dialog::Dialog, dmsg2_string_table::Dmsg2StringTable, dmsg3_string_table::Dmsg3StringTable,
-        entity_names::EntityNames, item_info::ItemInfoTable, menu_table::MenuTable,
-        status_info::StatusInfoTable, xistring_table::XiStringTable,
+        entity_names::EntityNames, event::EventFile, item_info::ItemInfoTable,
+        menu_table::MenuTable, quest_info::QuestInfoTable, status_info::StatusInfoTable,
+        xistring_table::XiStringTable,
     },
 };
 
@@ -14,6 +15,7 @@ pub struct DatIdMapping {
     pub entities: DatByZone<EntityNames>,
     pub dialog: DatByZone<Dialog>,
     pub dialog2: DatByZone<Dialog>,
+    pub events: DatByZone<EventFile>,
 
     // Global dialog
     pub monster_skill_names: Dat<Dialog>,
@@ -70,6 +72,10 @@ pub struct DatIdMapping {
     pub monipulator: Dat<ItemInfoTable>,
     pub instincts: Dat<ItemInfoTable>,
 
+    // Quest logs
+    /// By the index of the quest log.
+    pub quest_info: BTreeMap<u16, Dat<QuestInfoTable>>,
+
     // Misc data
     pub data_menu: Dat<MenuTable>,
 }
@@ -114,6 +120,8 @@ impl DatIdMapping {
                 entities,
                 dialog,
                 dialog2,
+                // TODO: The DAT IDs of the zone events still have to be determined from a retail install.
+                events: DatByZone::default(),
 
                 // Global dialog
                 monster_skill_names: 07035.into(),
@@ -169,6 +177,10 @@ impl DatIdMapping {
                 monipulator: 55669.into(),
                 instincts: 55670.into(),
 
+                // Quest logs
+                // TODO: The DAT IDs of the quest logs still have to be determined from a retail install.
+                quest_info: BTreeMap::new(),
+
                 // Misc. data
                 data_menu: 81.into(),
             }
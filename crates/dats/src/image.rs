@@ -1,10 +1,28 @@
-#![allow(dead_code)] // TODO: remove once images are in use
+use std::{
+    cell::RefCell,
+    collections::HashSet,
+    fs,
+    path::{Path, PathBuf},
+};
+
 use anyhow::{anyhow, Result};
-use common::byte_walker::ByteWalker;
+use base64::{engine::general_purpose, Engine as _};
+use common::{
+    byte_walker::{BufferedByteWalker, ByteWalker},
+    vec_byte_walker::VecByteWalker,
+    writing_byte_walker::WritingByteWalker,
+};
 use serde_derive::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize)]
+use crate::serde_hex;
+
+/// Image embedded in DATs, like the icons of items and status effects.
+///
+/// Serializing only includes the header values, since the pixels are meant to be
+/// edited as a PNG (see [`XiImage::to_png`] and [`XiImage::read_png`]).
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct XiImage {
+    image_type: XiImageType,
     id: String,
     category: String,
     width: u32,
@@ -17,8 +35,18 @@ pub struct XiImage {
     vertical_resolution: u32,
     used_colors: u32,
     important_colors: u32,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    dxt: Option<DxtHeader>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty", with = "serde_hex")]
+    trailing_bytes: Vec<u8>,
+
+    #[serde(skip)]
+    data: XiImageData,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum XiImageType {
     DirectX,
     BitmapA,
@@ -34,6 +62,49 @@ impl XiImageType {
             _ => Err(anyhow!("Unknown image type: {:02X}", byte)),
         }
     }
+
+    fn to_byte(self) -> u8 {
+        match self {
+            XiImageType::BitmapA => 0x91,
+            XiImageType::DirectX => 0xA1,
+            XiImageType::BitmapB => 0xB1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DxtHeader {
+    four_cc: String,
+    unknown1: u64,
+}
+
+/// Pixel data of an image. Bitmaps store their rows from the bottom up.
+#[derive(Debug, Clone, Default)]
+enum XiImageData {
+    #[default]
+    Empty,
+    /// BGRA colors, and one index into them per pixel.
+    Palette {
+        palette: Vec<[u8; 4]>,
+        indices: Vec<u8>,
+    },
+    /// BGRA values per pixel.
+    Bgra(Vec<u8>),
+    /// Compressed 4x4 blocks of pixels.
+    Dxt(Vec<u8>),
+}
+
+/// Keyword of the PNG text chunk with the original bytes of the image, which are used
+/// as long as the pixels of the PNG weren't edited.
+const ORIGINAL_IMAGE_KEYWORD: &str = "XI original image";
+
+/// Bitmaps use an alpha of 0x80 for opaque pixels, so it gets scaled for other tools.
+fn bitmap_alpha_to_rgba(alpha: u8) -> u8 {
+    alpha.saturating_mul(2)
+}
+
+fn rgba_alpha_to_bitmap(alpha: u8) -> u8 {
+    (alpha as u16).div_ceil(2) as u8
 }
 
 impl XiImage {
@@ -43,7 +114,7 @@ impl XiImage {
         let category = std::str::from_utf8(walker.take_bytes(8)?)?.to_string();
         let id = std::str::from_utf8(walker.take_bytes(8)?)?.to_string();
 
-        walker.expect_msg(40, "BITMAPINFO structure length")?;
+        walker.expect_msg(40u32, "BITMAPINFO structure length")?;
 
         let width = walker.step::<u32>()?;
         let height = walker.step::<u32>()?;
@@ -60,13 +131,8 @@ impl XiImage {
             return Err(anyhow!("Incompatible width, height, or planes in image."));
         }
 
-        match image_type {
-            XiImageType::DirectX => Self::parse_directx(walker, width, height)?,
-            XiImageType::BitmapA => todo!(),
-            XiImageType::BitmapB => todo!(),
-        }
-
-        Ok(XiImage {
+        let mut image = XiImage {
+            image_type,
             id,
             category,
             width,
@@ -79,30 +145,912 @@ impl XiImage {
             vertical_resolution,
             used_colors,
             important_colors,
-        })
+            dxt: None,
+            trailing_bytes: vec![],
+            data: XiImageData::Empty,
+        };
+
+        match image_type {
+            XiImageType::DirectX => image.parse_directx(walker)?,
+            XiImageType::BitmapA | XiImageType::BitmapB => image.parse_bitmap(walker)?,
+        }
+
+        image.trailing_bytes = walker.take_bytes(walker.remaining())?.to_vec();
+
+        Ok(image)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        XiImage::parse(&mut BufferedByteWalker::on(bytes))
+    }
+
+    fn pixel_count(&self) -> usize {
+        self.width as usize * self.height as usize
+    }
+
+    fn has_palette(&self) -> bool {
+        self.image_type != XiImageType::DirectX && self.bit_count == 8
+    }
+
+    fn palette_size(&self) -> usize {
+        if self.used_colors == 0 {
+            1 << self.bit_count
+        } else {
+            self.used_colors as usize
+        }
     }
 
-    fn parse_directx<T: ByteWalker>(walker: &mut T, width: u32, height: u32) -> Result<()> {
-        let four_cc = std::str::from_utf8(walker.take_bytes(4)?)?;
+    fn parse_bitmap<T: ByteWalker>(&mut self, walker: &mut T) -> Result<()> {
+        self.data = match self.bit_count {
+            8 => {
+                let mut palette = Vec::with_capacity(self.palette_size());
+                for _ in 0..self.palette_size() {
+                    let color = walker.take_bytes(4)?;
+                    palette.push([color[0], color[1], color[2], color[3]]);
+                }
+                let indices = walker.take_bytes(self.pixel_count())?.to_vec();
+
+                XiImageData::Palette { palette, indices }
+            }
+            32 => XiImageData::Bgra(walker.take_bytes(self.pixel_count() * 4)?.to_vec()),
+            bit_count => {
+                return Err(anyhow!("Unsupported bitmap bit count: {}", bit_count));
+            }
+        };
+
+        Ok(())
+    }
 
-        if !four_cc.starts_with("DXT") {
+    fn parse_directx<T: ByteWalker>(&mut self, walker: &mut T) -> Result<()> {
+        let four_cc = std::str::from_utf8(walker.take_bytes(4)?)?.to_string();
+
+        let block_size = dxt_block_size(&four_cc)?;
+        if !self.width.is_multiple_of(4) || !self.height.is_multiple_of(4) {
             return Err(anyhow!(
-                "Expected first three bytes to be \"DXT\", but got \"{}\"",
-                &four_cc[..3]
+                "Expected width and height to be a multiple of 4, but got {} and {}.",
+                self.width,
+                self.height
             ));
         }
 
-        if width % 4 != 0 || height % 4 != 0 {
+        let unknown1 = walker.step::<u64>()?;
+
+        let texel_block_count = self.pixel_count() / 16;
+        self.data = XiImageData::Dxt(walker.take_bytes(texel_block_count * block_size)?.to_vec());
+        self.dxt = Some(DxtHeader { four_cc, unknown1 });
+
+        Ok(())
+    }
+
+    pub fn write<T: WritingByteWalker>(&self, walker: &mut T) -> Result<()> {
+        walker.write(self.image_type.to_byte());
+        write_padded_str(walker, &self.category)?;
+        write_padded_str(walker, &self.id)?;
+
+        walker.write(40u32);
+        walker.write(self.width);
+        walker.write(self.height);
+        walker.write(self.planes);
+        walker.write(self.bit_count);
+        walker.write(self.compression);
+        walker.write(self.image_size);
+        walker.write(self.horizontal_resolution);
+        walker.write(self.vertical_resolution);
+        walker.write(self.used_colors);
+        walker.write(self.important_colors);
+
+        match &self.data {
+            XiImageData::Empty => return Err(anyhow!("Image has no pixel data.")),
+            XiImageData::Palette { palette, indices } => {
+                for color in palette {
+                    walker.write_bytes(color);
+                }
+                walker.write_bytes(indices);
+            }
+            XiImageData::Bgra(pixels) => walker.write_bytes(pixels),
+            XiImageData::Dxt(blocks) => {
+                let dxt = self
+                    .dxt
+                    .as_ref()
+                    .ok_or(anyhow!("DirectX image is missing its DXT header."))?;
+                walker.write_str(&dxt.four_cc);
+                walker.write(dxt.unknown1);
+                walker.write_bytes(blocks);
+            }
+        }
+
+        walker.write_bytes(&self.trailing_bytes);
+
+        Ok(())
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        let mut walker = VecByteWalker::new();
+        self.write(&mut walker)?;
+        Ok(walker.into_vec())
+    }
+
+    /// Decodes the pixels into RGBA values, with rows from top to bottom.
+    pub fn to_rgba(&self) -> Result<Vec<u8>> {
+        let mut rgba = Vec::with_capacity(self.pixel_count() * 4);
+        match &self.data {
+            XiImageData::Empty => return Err(anyhow!("Image has no pixel data.")),
+            XiImageData::Palette { palette, indices } => {
+                for index in indices {
+                    let [b, g, r, a] = palette
+                        .get(*index as usize)
+                        .ok_or(anyhow!("Palette index {} is out of range.", index))?;
+                    rgba.extend([*r, *g, *b, bitmap_alpha_to_rgba(*a)]);
+                }
+            }
+            XiImageData::Bgra(pixels) => {
+                for bgra in pixels.chunks_exact(4) {
+                    rgba.extend([bgra[2], bgra[1], bgra[0], bitmap_alpha_to_rgba(bgra[3])]);
+                }
+            }
+            XiImageData::Dxt(blocks) => {
+                rgba = decode_dxt(self.dxt_four_cc()?, blocks, self.width, self.height)?;
+            }
+        }
+
+        Ok(flip_rows(&rgba, self.width as usize * 4))
+    }
+
+    /// Images are stored in a fixed area of their record, so the size can't change.
+    fn check_size(&self, width: u32, height: u32) -> Result<()> {
+        if (width, height) != (self.width, self.height) {
             return Err(anyhow!(
-                "Expected width and height to be a multiple of 4, but got {} and {}.",
+                "Image is {}x{}, but has to stay {}x{} to fit in its record.",
+                width,
+                height,
+                self.width,
+                self.height
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Replaces the pixels with the given RGBA values (rows from top to bottom),
+    /// encoding them the same way as the current pixels. The size has to stay the same.
+    pub fn set_rgba(&mut self, width: u32, height: u32, rgba: &[u8]) -> Result<()> {
+        self.check_size(width, height)?;
+        if rgba.len() != width as usize * height as usize * 4 {
+            return Err(anyhow!(
+                "RGBA data doesn't match a {}x{} image.",
                 width,
                 height
             ));
         }
 
-        let _texel_block_count = height / 4 * width / 4;
-        let _unknown1 = walker.step::<u64>()?;
+        let rgba = flip_rows(rgba, width as usize * 4);
+        self.data = if self.has_palette() {
+            let mut palette: Vec<[u8; 4]> = vec![];
+            let mut indices = Vec::with_capacity(rgba.len() / 4);
+            for pixel in rgba.chunks_exact(4) {
+                let color = [pixel[2], pixel[1], pixel[0], rgba_alpha_to_bitmap(pixel[3])];
+                let index = match palette.iter().position(|entry| *entry == color) {
+                    Some(index) => index,
+                    None => {
+                        palette.push(color);
+                        palette.len() - 1
+                    }
+                };
+                indices.push(index as u8);
+            }
+
+            let palette_size = self.palette_size();
+            if palette.len() > palette_size {
+                return Err(anyhow!(
+                    "Image has {} colors, but at most {} are supported.",
+                    palette.len(),
+                    palette_size
+                ));
+            }
+            palette.resize(palette_size, [0, 0, 0, 0]);
+
+            XiImageData::Palette { palette, indices }
+        } else if self.image_type == XiImageType::DirectX {
+            XiImageData::Dxt(encode_dxt(self.dxt_four_cc()?, &rgba, width, height)?)
+        } else {
+            XiImageData::Bgra(
+                rgba.chunks_exact(4)
+                    .flat_map(|rgba| [rgba[2], rgba[1], rgba[0], rgba_alpha_to_bitmap(rgba[3])])
+                    .collect(),
+            )
+        };
+
+        Ok(())
+    }
+
+    fn dxt_four_cc(&self) -> Result<&str> {
+        self.dxt
+            .as_ref()
+            .map(|dxt| dxt.four_cc.as_str())
+            .ok_or(anyhow!("DirectX image is missing its DXT header."))
+    }
+
+    /// Encodes the image as a PNG. Palette images are kept as indexed PNGs,
+    /// so their colors and indices stay the same when read back. The original bytes are
+    /// included as well, so that the image is restored exactly unless its pixels change.
+    pub fn to_png(&self) -> Result<Vec<u8>> {
+        let mut png_bytes = vec![];
+        let mut encoder = png::Encoder::new(&mut png_bytes, self.width, self.height);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.add_ztxt_chunk(
+            ORIGINAL_IMAGE_KEYWORD.to_string(),
+            general_purpose::STANDARD_NO_PAD.encode(self.to_bytes()?),
+        )?;
+
+        let image_data = match &self.data {
+            XiImageData::Palette { palette, indices } if palette.len() <= 256 => {
+                encoder.set_color(png::ColorType::Indexed);
+                encoder.set_palette(
+                    palette
+                        .iter()
+                        .flat_map(|[b, g, r, _]| [*r, *g, *b])
+                        .collect::<Vec<_>>(),
+                );
+                encoder.set_trns(
+                    palette
+                        .iter()
+                        .map(|[_, _, _, a]| bitmap_alpha_to_rgba(*a))
+                        .collect::<Vec<_>>(),
+                );
+                flip_rows(indices, self.width as usize)
+            }
+            _ => {
+                encoder.set_color(png::ColorType::Rgba);
+                self.to_rgba()?
+            }
+        };
+
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&image_data)?;
+        writer.finish()?;
+
+        Ok(png_bytes)
+    }
+
+    /// Replaces the pixels with the ones of the given PNG. Pixels which are the same as
+    /// those of the image the PNG was written from are restored exactly, while edited ones
+    /// are encoded again. The size of the PNG has to be the same as the image's.
+    pub fn read_png(&mut self, png_bytes: &[u8]) -> Result<()> {
+        let (width, height, rgba, original) = decode_png(png_bytes)?;
+        self.check_size(width, height)?;
+        if let Some(original) = original {
+            if original.is_encoded_like(self)
+                && (original.width, original.height) == (width, height)
+                && original.to_rgba()? == rgba
+            {
+                self.data = original.data;
+
+                return Ok(());
+            }
+        }
+
+        let mut decoder = png::Decoder::new(png_bytes);
+        decoder.set_transformations(png::Transformations::IDENTITY);
+        let mut reader = decoder.read_info()?;
+
+        let info = reader.info();
+
+        // Indexed PNGs can be used as they are for palette images.
+        if self.has_palette()
+            && info.color_type == png::ColorType::Indexed
+            && info.bit_depth == png::BitDepth::Eight
+        {
+            let palette_size = self.palette_size();
+            let colors = info
+                .palette
+                .as_ref()
+                .map(|p| p.to_vec())
+                .unwrap_or_default();
+            let alphas = info.trns.as_ref().map(|t| t.to_vec()).unwrap_or_default();
+
+            if colors.len() / 3 <= palette_size {
+                let mut palette = colors
+                    .chunks_exact(3)
+                    .enumerate()
+                    .map(|(idx, rgb)| {
+                        let alpha = alphas.get(idx).copied().unwrap_or(0xFF);
+                        [rgb[2], rgb[1], rgb[0], rgba_alpha_to_bitmap(alpha)]
+                    })
+                    .collect::<Vec<_>>();
+                palette.resize(palette_size, [0, 0, 0, 0]);
+
+                let mut indices = vec![0; reader.output_buffer_size()];
+                reader.next_frame(&mut indices)?;
+
+                self.data = XiImageData::Palette {
+                    palette,
+                    indices: flip_rows(&indices, width as usize),
+                };
+
+                return Ok(());
+            }
+        }
+
+        self.set_rgba(width, height, &rgba)
+    }
+
+    /// Whether the pixels of the other image are stored the same way as the ones of this.
+    fn is_encoded_like(&self, other: &XiImage) -> bool {
+        self.image_type == other.image_type
+            && self.bit_count == other.bit_count
+            && self.has_palette() == other.has_palette()
+            && (!self.has_palette() || self.palette_size() == other.palette_size())
+            && self.dxt_four_cc().ok() == other.dxt_four_cc().ok()
+    }
+}
+
+/// Decodes a PNG into its size and RGBA values, along with the original image it was
+/// written from, if it has one.
+fn decode_png(png_bytes: &[u8]) -> Result<(u32, u32, Vec<u8>, Option<XiImage>)> {
+    let mut decoder = png::Decoder::new(png_bytes);
+    decoder.set_transformations(png::Transformations::normalize_to_color8());
+    let mut reader = decoder.read_info()?;
+
+    let original = reader
+        .info()
+        .compressed_latin1_text
+        .iter()
+        .find(|chunk| chunk.keyword == ORIGINAL_IMAGE_KEYWORD)
+        .and_then(|chunk| chunk.get_text().ok())
+        .and_then(|text| general_purpose::STANDARD_NO_PAD.decode(text).ok())
+        .and_then(|bytes| XiImage::from_bytes(&bytes).ok());
+
+    let mut buffer = vec![0; reader.output_buffer_size()];
+    let frame = reader.next_frame(&mut buffer)?;
+    buffer.truncate(frame.buffer_size());
+
+    let rgba = match frame.color_type {
+        png::ColorType::Rgba => buffer,
+        png::ColorType::Rgb => buffer
+            .chunks_exact(3)
+            .flat_map(|rgb| [rgb[0], rgb[1], rgb[2], 0xFF])
+            .collect(),
+        png::ColorType::GrayscaleAlpha => buffer
+            .chunks_exact(2)
+            .flat_map(|ga| [ga[0], ga[0], ga[0], ga[1]])
+            .collect(),
+        png::ColorType::Grayscale => buffer.iter().flat_map(|g| [*g, *g, *g, 0xFF]).collect(),
+        png::ColorType::Indexed => {
+            return Err(anyhow!("Indexed PNG could not be expanded."));
+        }
+    };
+
+    Ok((frame.width, frame.height, rgba, original))
+}
+
+fn write_padded_str<T: WritingByteWalker>(walker: &mut T, str: &str) -> Result<()> {
+    if str.len() != 8 {
+        return Err(anyhow!("Expected \"{}\" to be 8 bytes long.", str));
+    }
+    walker.write_str(str);
+    Ok(())
+}
+
+fn flip_rows<T: Clone>(values: &[T], row_len: usize) -> Vec<T> {
+    if row_len == 0 {
+        return values.to_vec();
+    }
+    values
+        .chunks(row_len)
+        .rev()
+        .flat_map(|row| row.iter().cloned())
+        .collect()
+}
+
+fn dxt_block_size(four_cc: &str) -> Result<usize> {
+    match four_cc {
+        "DXT1" => Ok(8),
+        "DXT2" | "DXT3" | "DXT4" | "DXT5" => Ok(16),
+        _ => Err(anyhow!("Unsupported DXT format: \"{}\"", four_cc)),
+    }
+}
+
+fn rgb565_to_rgb(color: u16) -> [u8; 3] {
+    let r = ((color >> 11) & 0x1F) as u8;
+    let g = ((color >> 5) & 0x3F) as u8;
+    let b = (color & 0x1F) as u8;
+    [
+        (r << 3) | (r >> 2),
+        (g << 2) | (g >> 4),
+        (b << 3) | (b >> 2),
+    ]
+}
+
+fn rgb_to_rgb565(rgb: [u8; 3]) -> u16 {
+    ((rgb[0] as u16 >> 3) << 11) | ((rgb[1] as u16 >> 2) << 5) | (rgb[2] as u16 >> 3)
+}
+
+/// The four colors of a color block, as RGBA.
+fn color_block_palette(color0: u16, color1: u16, allow_transparent: bool) -> [[u8; 4]; 4] {
+    let [r0, g0, b0] = rgb565_to_rgb(color0).map(|c| c as u16);
+    let [r1, g1, b1] = rgb565_to_rgb(color1).map(|c| c as u16);
+    let mix = |c0: u16, c1: u16, w0: u16, w1: u16| ((c0 * w0 + c1 * w1) / (w0 + w1)) as u8;
+
+    let first = [r0 as u8, g0 as u8, b0 as u8, 0xFF];
+    let second = [r1 as u8, g1 as u8, b1 as u8, 0xFF];
+    if color0 > color1 || !allow_transparent {
+        [
+            first,
+            second,
+            [
+                mix(r0, r1, 2, 1),
+                mix(g0, g1, 2, 1),
+                mix(b0, b1, 2, 1),
+                0xFF,
+            ],
+            [
+                mix(r0, r1, 1, 2),
+                mix(g0, g1, 1, 2),
+                mix(b0, b1, 1, 2),
+                0xFF,
+            ],
+        ]
+    } else {
+        [
+            first,
+            second,
+            [
+                mix(r0, r1, 1, 1),
+                mix(g0, g1, 1, 1),
+                mix(b0, b1, 1, 1),
+                0xFF,
+            ],
+            [0, 0, 0, 0],
+        ]
+    }
+}
+
+fn dxt5_alpha_palette(alpha0: u8, alpha1: u8) -> [u8; 8] {
+    let (a0, a1) = (alpha0 as u16, alpha1 as u16);
+    let mut alphas = [alpha0, alpha1, 0, 0, 0, 0, 0, 0xFF];
+    if alpha0 > alpha1 {
+        for idx in 1..7 {
+            alphas[idx + 1] = ((a0 * (7 - idx as u16) + a1 * idx as u16) / 7) as u8;
+        }
+    } else {
+        for idx in 1..5 {
+            alphas[idx + 1] = ((a0 * (5 - idx as u16) + a1 * idx as u16) / 5) as u8;
+        }
+        alphas[6] = 0;
+    }
+    alphas
+}
+
+/// Decodes DXT blocks into RGBA values, in the same row order as the blocks.
+fn decode_dxt(four_cc: &str, blocks: &[u8], width: u32, height: u32) -> Result<Vec<u8>> {
+    let block_size = dxt_block_size(four_cc)?;
+    let (width, height) = (width as usize, height as usize);
+    let mut rgba = vec![0; width * height * 4];
+
+    for (block_idx, block) in blocks.chunks_exact(block_size).enumerate() {
+        let (color_block, alpha_block) = if block_size == 16 {
+            (&block[8..], Some(&block[..8]))
+        } else {
+            (block, None)
+        };
+
+        let color0 = u16::from_le_bytes([color_block[0], color_block[1]]);
+        let color1 = u16::from_le_bytes([color_block[2], color_block[3]]);
+        let colors = color_block_palette(color0, color1, four_cc == "DXT1");
+        let color_indices = u32::from_le_bytes(color_block[4..8].try_into()?);
+
+        let alpha_bits = alpha_block.map(|alpha| u64::from_le_bytes(alpha.try_into().unwrap()));
+
+        let block_x = block_idx % (width / 4) * 4;
+        let block_y = block_idx / (width / 4) * 4;
+        for pixel in 0..16 {
+            let mut color = colors[(color_indices >> (pixel * 2)) as usize & 0x3];
+
+            if let Some(alpha_bits) = alpha_bits {
+                color[3] = match four_cc {
+                    "DXT2" | "DXT3" => ((alpha_bits >> (pixel * 4)) & 0xF) as u8 * 17,
+                    _ => {
+                        let alphas = dxt5_alpha_palette(alpha_bits as u8, (alpha_bits >> 8) as u8);
+                        alphas[((alpha_bits >> (16 + pixel * 3)) & 0x7) as usize]
+                    }
+                };
+            }
+
+            let offset = ((block_y + pixel / 4) * width + block_x + pixel % 4) * 4;
+            rgba[offset..offset + 4].copy_from_slice(&color);
+        }
+    }
+
+    Ok(rgba)
+}
+
+/// Encodes RGBA values into DXT blocks, by using the extremes of each block as its colors.
+fn encode_dxt(four_cc: &str, rgba: &[u8], width: u32, height: u32) -> Result<Vec<u8>> {
+    let block_size = dxt_block_size(four_cc)?;
+    if !width.is_multiple_of(4) || !height.is_multiple_of(4) {
+        return Err(anyhow!(
+            "Expected width and height to be a multiple of 4, but got {} and {}.",
+            width,
+            height
+        ));
+    }
+
+    let (width, height) = (width as usize, height as usize);
+    let mut blocks = Vec::with_capacity(width * height / 16 * block_size);
+
+    for block_y in (0..height).step_by(4) {
+        for block_x in (0..width).step_by(4) {
+            let pixels: [[u8; 4]; 16] = core::array::from_fn(|pixel| {
+                let offset = ((block_y + pixel / 4) * width + block_x + pixel % 4) * 4;
+                rgba[offset..offset + 4].try_into().unwrap()
+            });
+
+            match four_cc {
+                "DXT2" | "DXT3" => {
+                    let alpha_bits = pixels.iter().enumerate().fold(0u64, |bits, (idx, p)| {
+                        bits | (((p[3] as u64 * 15 + 127) / 255) << (idx * 4))
+                    });
+                    blocks.extend(alpha_bits.to_le_bytes());
+                }
+                "DXT4" | "DXT5" => {
+                    let alpha0 = pixels.iter().map(|p| p[3]).max().unwrap_or_default();
+                    let alpha1 = pixels.iter().map(|p| p[3]).min().unwrap_or_default();
+                    let alphas = dxt5_alpha_palette(alpha0, alpha1);
+
+                    let mut alpha_bits = alpha0 as u64 | ((alpha1 as u64) << 8);
+                    for (idx, pixel) in pixels.iter().enumerate() {
+                        alpha_bits |= (nearest(&alphas, |a| a.abs_diff(pixel[3]) as u32) as u64)
+                            << (16 + idx * 3);
+                    }
+                    blocks.extend(alpha_bits.to_le_bytes());
+                }
+                _ => {}
+            }
+
+            let transparent = four_cc == "DXT1" && pixels.iter().any(|p| p[3] < 0x80);
+            let opaque_pixels = pixels.iter().filter(|p| !transparent || p[3] >= 0x80);
+
+            let mut max = [0u8; 3];
+            let mut min = [0xFFu8; 3];
+            for pixel in opaque_pixels {
+                for channel in 0..3 {
+                    max[channel] = max[channel].max(pixel[channel]);
+                    min[channel] = min[channel].min(pixel[channel]);
+                }
+            }
+
+            let (mut color0, mut color1) = (rgb_to_rgb565(max), rgb_to_rgb565(min));
+            if transparent {
+                // Three colors and transparency are used when the first color isn't greater.
+                if color0 > color1 {
+                    std::mem::swap(&mut color0, &mut color1);
+                }
+            } else if color0 < color1 {
+                std::mem::swap(&mut color0, &mut color1);
+            }
+
+            let colors = color_block_palette(color0, color1, four_cc == "DXT1");
+            let mut color_indices = 0u32;
+            for (idx, pixel) in pixels.iter().enumerate() {
+                let color_idx = if transparent && pixel[3] < 0x80 {
+                    3
+                } else {
+                    nearest(&colors[..if transparent { 3 } else { 4 }], |color| {
+                        (0..3)
+                            .map(|c| (color[c] as i32 - pixel[c] as i32).pow(2) as u32)
+                            .sum()
+                    })
+                };
+                color_indices |= (color_idx as u32) << (idx * 2);
+            }
+
+            blocks.extend(color0.to_le_bytes());
+            blocks.extend(color1.to_le_bytes());
+            blocks.extend(color_indices.to_le_bytes());
+        }
+    }
+
+    Ok(blocks)
+}
+
+fn nearest<T>(values: &[T], distance: impl Fn(&T) -> u32) -> usize {
+    values
+        .iter()
+        .enumerate()
+        .min_by_key(|(_, value)| distance(value))
+        .map(|(idx, _)| idx)
+        .unwrap_or_default()
+}
+
+/// Location for the image files of a DAT, which images are written to and read from
+/// while (de)serializing through [`ImageFiles::scoped`].
+#[derive(Debug, Clone)]
+pub struct ImageFiles {
+    base_dir: PathBuf,
+    sub_dir: PathBuf,
+    used_names: RefCell<HashSet<String>>,
+}
+
+thread_local! {
+    static CURRENT_IMAGE_FILES: RefCell<Option<ImageFiles>> = const { RefCell::new(None) };
+}
+
+impl ImageFiles {
+    /// Images of the file at the given path are put in a directory next to it with the
+    /// same name, e.g. `items/weapons.yml` uses `items/weapons/`.
+    pub fn for_file(path: &Path) -> Self {
+        ImageFiles {
+            base_dir: path.parent().map(Path::to_path_buf).unwrap_or_default(),
+            sub_dir: path.file_stem().map(PathBuf::from).unwrap_or_default(),
+            used_names: RefCell::new(HashSet::new()),
+        }
+    }
+
+    /// Runs the given function with these image files being used on this thread.
+    pub fn scoped<R>(self, f: impl FnOnce() -> R) -> R {
+        struct Reset(Option<ImageFiles>);
+        impl Drop for Reset {
+            fn drop(&mut self) {
+                CURRENT_IMAGE_FILES.with(|files| *files.borrow_mut() = self.0.take());
+            }
+        }
+
+        let _reset = Reset(CURRENT_IMAGE_FILES.with(|files| files.replace(Some(self))));
+        f()
+    }
+
+    /// Writes the image in the given bytes to a new PNG file in the current image files,
+    /// and returns its path relative to the base directory. The file is named after the
+    /// given name, e.g. the ID of the record the image belongs to.
+    ///
+    /// Returns `None` if no image files are in use, or if the bytes aren't an image,
+    /// in which case they should be kept as they are.
+    pub(crate) fn write_current(name: &str, bytes: &[u8]) -> Result<Option<(PathBuf, XiImage)>> {
+        CURRENT_IMAGE_FILES.with(|files| {
+            let files = files.borrow();
+            let Some(files) = files.as_ref() else {
+                return Ok(None);
+            };
+
+            let Ok(image) = XiImage::from_bytes(bytes) else {
+                return Ok(None);
+            };
+            let png_bytes = image.to_png()?;
+
+            let mut used_names = files.used_names.borrow_mut();
+            let mut file_name = name.to_string();
+            for count in 2.. {
+                if used_names.insert(file_name.clone()) {
+                    break;
+                }
+                file_name = format!("{}-{}", name, count);
+            }
+
+            let relative_path = files.sub_dir.join(format!("{}.png", file_name));
+            let path = files.base_dir.join(&relative_path);
+            fs::create_dir_all(path.parent().unwrap())?;
+            fs::write(&path, png_bytes)
+                .map_err(|err| anyhow!("Could not write image to {}: {}", path.display(), err))?;
+
+            Ok(Some((relative_path, image)))
+        })
+    }
+
+    /// Reads the PNG at the given path relative to the current image files into the image.
+    pub(crate) fn read_current(relative_path: &Path, image: &mut XiImage) -> Result<()> {
+        let path = CURRENT_IMAGE_FILES
+            .with(|files| {
+                files
+                    .borrow()
+                    .as_ref()
+                    .map(|files| files.base_dir.join(relative_path))
+            })
+            .ok_or(anyhow!(
+                "Image {} can only be read along with the file referencing it.",
+                relative_path.display()
+            ))?;
+
+        let png_bytes = fs::read(&path)
+            .map_err(|err| anyhow!("Could not read image at {}: {}", path.display(), err))?;
+        image.read_png(&png_bytes)
+    }
+}
+
+#[cfg(any(test, feature = "fixtures"))]
+impl XiImage {
+    /// Tiny 4x4 icon with a two-color palette.
+    pub fn fixture() -> Self {
+        let mut palette = vec![[0, 0, 0, 0]; 256];
+        palette[1] = [0x00, 0x00, 0xFF, 0x80];
+        palette[2] = [0xFF, 0x00, 0x00, 0x80];
+
+        XiImage {
+            image_type: XiImageType::BitmapA,
+            id: "fixture ".to_string(),
+            category: "tst_icon".to_string(),
+            width: 4,
+            height: 4,
+            planes: 1,
+            bit_count: 8,
+            compression: 0,
+            image_size: 0,
+            horizontal_resolution: 0,
+            vertical_resolution: 0,
+            used_colors: 0,
+            important_colors: 0,
+            dxt: None,
+            trailing_bytes: vec![],
+            data: XiImageData::Palette {
+                palette,
+                indices: vec![0, 1, 1, 0, 1, 2, 2, 1, 1, 2, 2, 1, 0, 1, 1, 0],
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use base64::{engine::general_purpose, Engine as _};
+    use common::byte_walker::{BufferedByteWalker, ByteWalker};
+
+    use super::{XiImage, XiImageData, XiImageType};
+
+    fn status_icons() -> Vec<Vec<u8>> {
+        let mut dat_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        dat_path.push("resources/test/status_infos.DAT");
+        let bytes = std::fs::read(dat_path).unwrap();
+
+        bytes
+            .chunks_exact(0x1800)
+            .map(|entry| {
+                let mut walker = BufferedByteWalker::on(&entry[0x280..]);
+                let size = walker.step::<u32>().unwrap();
+                walker.take_bytes(size as usize).unwrap().to_vec()
+            })
+            .collect()
+    }
+
+    #[test]
+    pub fn status_icons_through_png() {
+        for icon_bytes in status_icons() {
+            let image = XiImage::from_bytes(&icon_bytes).unwrap();
+            assert_eq!(image.image_type, XiImageType::BitmapA);
+            assert_eq!(image.to_bytes().unwrap(), icon_bytes);
+
+            let mut restored = image.clone();
+            restored.read_png(&image.to_png().unwrap()).unwrap();
+            assert_eq!(restored.to_bytes().unwrap(), icon_bytes);
+        }
+    }
+
+    #[test]
+    pub fn palette_from_rgba() {
+        let icon_bytes = status_icons()
+            .into_iter()
+            .find(|bytes| XiImage::from_bytes(bytes).unwrap().bit_count == 8)
+            .unwrap();
+        let image = XiImage::from_bytes(&icon_bytes).unwrap();
+        let rgba = image.to_rgba().unwrap();
+
+        // Editing tools can save as RGBA, so the palette gets rebuilt from the used colors.
+        let mut restored = image.clone();
+        restored.set_rgba(image.width, image.height, &rgba).unwrap();
+        assert!(matches!(restored.data, XiImageData::Palette { .. }));
+        assert_eq!(restored.to_rgba().unwrap(), rgba);
+    }
+
+    #[test]
+    pub fn dxt_images() {
+        for four_cc in ["DXT1", "DXT3", "DXT5"] {
+            let mut image = XiImage::fixture();
+            image.image_type = XiImageType::DirectX;
+            image.dxt = Some(super::DxtHeader {
+                four_cc: four_cc.to_string(),
+                unknown1: 0,
+            });
+            image.data = XiImageData::Dxt(vec![]);
+
+            // Blocks with two colors (and fully opaque or transparent pixels) are lossless.
+            let rgba = (0..16)
+                .flat_map(|idx| match idx % 3 {
+                    0 => [0xFF, 0xFF, 0xFF, 0xFF],
+                    1 => [0, 0, 0, 0xFF],
+                    _ => [0, 0, 0, 0],
+                })
+                .collect::<Vec<u8>>();
+            image.set_rgba(4, 4, &rgba).unwrap();
+
+            let bytes = image.to_bytes().unwrap();
+            let res = XiImage::from_bytes(&bytes).unwrap();
+            assert_eq!(res.to_rgba().unwrap(), rgba, "{}", four_cc);
+
+            let mut restored = res.clone();
+            restored.read_png(&res.to_png().unwrap()).unwrap();
+            assert_eq!(restored.to_bytes().unwrap(), bytes, "{}", four_cc);
+        }
+    }
+
+    fn rgba_png(width: u32, height: u32, rgba: &[u8], original: Option<&XiImage>) -> Vec<u8> {
+        let mut png_bytes = vec![];
+        let mut encoder = png::Encoder::new(&mut png_bytes, width, height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        if let Some(original) = original {
+            encoder
+                .add_ztxt_chunk(
+                    super::ORIGINAL_IMAGE_KEYWORD.to_string(),
+                    general_purpose::STANDARD_NO_PAD.encode(original.to_bytes().unwrap()),
+                )
+                .unwrap();
+        }
+
+        let mut writer = encoder.write_header().unwrap();
+        writer.write_image_data(rgba).unwrap();
+        writer.finish().unwrap();
+        png_bytes
+    }
+
+    #[test]
+    pub fn unedited_pngs_are_exact() {
+        // Alphas above the opaque 0x80 of bitmaps can't be told apart in a PNG.
+        let mut image = XiImage::fixture();
+        if let XiImageData::Palette { palette, .. } = &mut image.data {
+            palette[1][3] = 0xC0;
+            palette[2][3] = 0xFF;
+        }
+
+        // Blocks with a gradient aren't encoded to the same bytes again.
+        let mut dxt = XiImage::fixture();
+        dxt.image_type = XiImageType::DirectX;
+        dxt.dxt = Some(super::DxtHeader {
+            four_cc: "DXT5".to_string(),
+            unknown1: 0,
+        });
+        dxt.data = XiImageData::Dxt(vec![
+            0xFF, 0x00, 0x49, 0x92, 0x24, 0x49, 0x92, 0x24, 0x1F, 0xF8, 0x00, 0x00, 0xE4, 0x1B,
+            0x4E, 0xB1,
+        ]);
+
+        for image in [image, dxt] {
+            let bytes = image.to_bytes().unwrap();
+
+            let mut reencoded = image.clone();
+            reencoded
+                .set_rgba(image.width, image.height, &image.to_rgba().unwrap())
+                .unwrap();
+            assert_ne!(reencoded.to_bytes().unwrap(), bytes);
+
+            let mut restored = image.clone();
+            restored.read_png(&image.to_png().unwrap()).unwrap();
+            assert_eq!(restored.to_bytes().unwrap(), bytes);
+        }
+    }
+
+    #[test]
+    pub fn edited_pngs_are_encoded_again() {
+        let image = XiImage::fixture();
+        let mut rgba = image.to_rgba().unwrap();
+        rgba[..4].copy_from_slice(&[0x00, 0xFF, 0x00, 0xFF]);
+
+        // Editors may keep the original bytes of the image along with the edited pixels.
+        for original in [None, Some(&image)] {
+            let mut edited = image.clone();
+            edited.read_png(&rgba_png(4, 4, &rgba, original)).unwrap();
+            assert!(matches!(edited.data, XiImageData::Palette { .. }));
+            assert_eq!(edited.to_rgba().unwrap(), rgba);
+        }
+    }
+    #[test]
+    pub fn resized_pngs_are_rejected() {
+        let image = XiImage::fixture();
+        let rgba = vec![0xFF; 8 * 8 * 4];
 
-        todo!();
+        let mut resized = image.clone();
+        let err = resized.read_png(&rgba_png(8, 8, &rgba, None)).unwrap_err();
+        assert!(err.to_string().contains("has to stay 4x4"), "{}", err);
+        assert!(resized.set_rgba(8, 8, &rgba).is_err());
+        assert_eq!(resized.to_bytes().unwrap(), image.to_bytes().unwrap());
     }
 }
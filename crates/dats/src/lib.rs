@@ -1,7 +1,11 @@
 pub mod base;
 pub mod context;
 pub mod dat_format;
+pub mod entries;
 pub mod enums;
+/// Synthetic DATs and installs for tests that can't rely on a retail install.
+#[cfg(any(test, feature = "fixtures"))]
+pub mod fixtures;
 pub mod flags;
 pub mod formats;
 pub mod id_mapping;
@@ -10,4 +14,8 @@ pub mod sanitize_filename;
 mod serde_base64;
 mod serde_flags;
 mod serde_hex;
+mod serde_icon;
+mod serde_pipe_flags;
+pub mod strings;
 mod utils;
+pub mod views;
@@ -0,0 +1,53 @@
+use std::{fmt::Display, path::PathBuf};
+
+use serde::{de, ser, Deserialize, Serialize};
+use serde::{Deserializer, Serializer};
+
+use crate::{
+    image::{ImageFiles, XiImage},
+    serde_base64,
+};
+
+/// Icon which was written to a PNG file, with the values needed to encode it again.
+#[derive(Serialize, Deserialize)]
+struct IconFile {
+    png: PathBuf,
+
+    #[serde(flatten)]
+    image: XiImage,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Icon {
+    Bytes(#[serde(with = "serde_base64")] Vec<u8>),
+    File(IconFile),
+}
+
+/// Icon of a record along with the record's ID, which its PNG file is named after.
+pub(crate) struct RecordIcon<'a, T> {
+    pub id: &'a T,
+    pub bytes: &'a Vec<u8>,
+}
+
+/// Icons are written to PNG files when image files are in use (see [`ImageFiles::scoped`]),
+/// and otherwise kept as base64.
+impl<T: Display> Serialize for RecordIcon<'_, T> {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        match ImageFiles::write_current(&self.id.to_string(), self.bytes)
+            .map_err(ser::Error::custom)?
+        {
+            Some((png, image)) => IconFile { png, image }.serialize(s),
+            None => serde_base64::serialize(self.bytes, s),
+        }
+    }
+}
+
+pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Vec<u8>, D::Error> {
+    match Icon::deserialize(d)? {
+        Icon::Bytes(bytes) => Ok(bytes),
+        Icon::File(IconFile { png, mut image }) => ImageFiles::read_current(&png, &mut image)
+            .and_then(|_| image.to_bytes())
+            .map_err(de::Error::custom),
+    }
+}
@@ -0,0 +1,72 @@
+use bitflags::{
+    parser::{self, ParseHex, WriteHex},
+    Flags,
+};
+use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+/// Flags as a single value like `WAR|PLD`, for formats without sequences such as CSV.
+/// Bits without a name are kept as hex, and no flags at all are written as `0x0`.
+pub fn serialize<B: Flags, S: Serializer>(flags: &B, s: S) -> Result<S::Ok, S::Error>
+where
+    B::Bits: WriteHex,
+{
+    s.serialize_str(&to_string(flags))
+}
+
+pub fn deserialize<'de, D: Deserializer<'de>, B: Flags>(d: D) -> Result<B, D::Error>
+where
+    B::Bits: ParseHex,
+{
+    from_str(&String::deserialize(d)?).map_err(D::Error::custom)
+}
+
+fn to_string<B: Flags>(flags: &B) -> String
+where
+    B::Bits: WriteHex,
+{
+    if flags.is_empty() {
+        return "0x0".to_string();
+    }
+
+    let mut text = String::new();
+    let _ = parser::to_writer(flags, &mut text);
+    text.replace(" | ", "|")
+}
+
+fn from_str<B: Flags>(text: &str) -> Result<B, parser::ParseError>
+where
+    B::Bits: ParseHex,
+{
+    parser::from_str(text.trim())
+}
+
+/// Optional flags, which are left empty if missing.
+pub mod option {
+    use bitflags::{
+        parser::{ParseHex, WriteHex},
+        Flags,
+    };
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<B: Flags, S: Serializer>(flags: &Option<B>, s: S) -> Result<S::Ok, S::Error>
+    where
+        B::Bits: WriteHex,
+    {
+        match flags {
+            Some(flags) => s.serialize_some(&super::to_string(flags)),
+            None => s.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>, B: Flags>(d: D) -> Result<Option<B>, D::Error>
+    where
+        B::Bits: ParseHex,
+    {
+        match Option::<String>::deserialize(d)? {
+            Some(text) if !text.trim().is_empty() => {
+                super::from_str(&text).map(Some).map_err(D::Error::custom)
+            }
+            _ => Ok(None),
+        }
+    }
+}
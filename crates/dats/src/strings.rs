@@ -0,0 +1,12 @@
+/// Formats with decoded text, which can be searched across DATs.
+pub trait DatStrings {
+    /// Every string of the DAT together with the ID of its entry. Entries with more than one
+    /// string, like lists or items, have one pair for each of them.
+    fn strings(&self) -> Vec<(u32, &str)>;
+}
+
+/// Formats whose strings can be replaced in place, e.g. by their translations.
+pub trait DatStringsMut: DatStrings {
+    /// The same strings as [`DatStrings::strings`], in the same order.
+    fn strings_mut(&mut self) -> Vec<(u32, &mut String)>;
+}
@@ -0,0 +1,130 @@
+use std::collections::BTreeMap;
+
+use anyhow::{anyhow, Result};
+use serde_derive::{Deserialize, Serialize};
+
+use crate::{
+    context::DatContext,
+    formats::{
+        dmsg3_string_table::Dmsg3StringTable,
+        menu_table::{AbilityInfo, MenuTable},
+    },
+    id_mapping::DatIdMapping,
+};
+
+use super::{retain_edited_records, DatView};
+
+/// Abilities of the menu DAT by their ID, joined with their names and descriptions.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct AbilityInfoView {
+    pub abilities: BTreeMap<u16, AbilityEntry>,
+}
+
+/// The name and description are only for reference, and are edited through their own DATs.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AbilityEntry {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub info: AbilityInfo,
+}
+
+impl AbilityInfoView {
+    pub fn join(
+        menu: &MenuTable,
+        names: &Dmsg3StringTable,
+        descriptions: &Dmsg3StringTable,
+    ) -> Result<Self> {
+        let mut abilities = BTreeMap::new();
+        for info in menu.abilities() {
+            let id = info.id;
+            let entry = AbilityEntry {
+                name: names.string(id as u32).map(str::to_string),
+                description: descriptions.string(id as u32).map(str::to_string),
+                info: info.clone(),
+            };
+
+            if abilities.insert(id, entry).is_some() {
+                return Err(anyhow!("Found more than one ability with ID {id}."));
+            }
+        }
+
+        Ok(AbilityInfoView { abilities })
+    }
+}
+
+impl DatView for AbilityInfoView {
+    type Base = MenuTable;
+
+    fn from_base(base: &MenuTable, dat_context: &DatContext) -> Result<Self> {
+        let mapping = DatIdMapping::get();
+        let names = dat_context.get_data_from_dat(&mapping.ability_names)?.dat;
+        let descriptions = dat_context
+            .get_data_from_dat(&mapping.ability_descriptions)?
+            .dat;
+
+        Self::join(base, &names, &descriptions)
+    }
+
+    fn apply_to(mut self, base: &mut MenuTable) -> Result<()> {
+        for info in base.abilities_mut() {
+            if let Some(entry) = self.abilities.remove(&info.id) {
+                if entry.info.id != info.id {
+                    return Err(anyhow!(
+                        "Ability {} has a different ID in its info: {}",
+                        info.id,
+                        entry.info.id
+                    ));
+                }
+                *info = entry.info;
+            }
+        }
+
+        if !self.abilities.is_empty() {
+            return Err(anyhow!(
+                "Abilities can't be added to the menu DAT yet: {:?}",
+                self.abilities.keys().collect::<Vec<_>>()
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn retain_edits(&mut self, original: &Self, edited_base: &Self) -> Result<()> {
+        retain_edited_records(
+            &mut self.abilities,
+            &original.abilities,
+            &edited_base.abilities,
+            |entry| &entry.info,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::{
+        dat_format::DatFormat,
+        formats::{dmsg3_string_table::Dmsg3StringTable, menu_table::MenuTable},
+    };
+
+    use super::{AbilityInfoView, DatView};
+
+    #[test]
+    pub fn abilities_with_names() {
+        let resources = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("resources/test");
+        let menu = MenuTable::from_path(&resources.join("menu.DAT")).unwrap();
+        let names = Dmsg3StringTable::from_path(&resources.join("ability_names.DAT")).unwrap();
+
+        let view = AbilityInfoView::join(&menu, &names, &Dmsg3StringTable::default()).unwrap();
+        assert_eq!(view.abilities[&2].name.as_deref(), Some("Shoulder Tackle"));
+        assert_eq!(view.abilities[&2].description, None);
+
+        let original_bytes = menu.to_bytes().unwrap();
+        let mut edited = MenuTable::from_bytes(&original_bytes).unwrap();
+        view.apply_to(&mut edited).unwrap();
+        assert_eq!(edited.to_bytes().unwrap(), original_bytes);
+    }
+}
@@ -0,0 +1,38 @@
+use anyhow::Result;
+
+use crate::{context::DatContext, formats::event::EventFile, id_mapping::DatIdMapping};
+
+use super::DatView;
+
+/// Events of a zone, with the names of their actors and the text of their messages.
+impl DatView for EventFile {
+    type Base = EventFile;
+
+    fn from_base(base: &EventFile, dat_context: &DatContext) -> Result<Self> {
+        let mut events = base.clone();
+        let Some(zone_id) = events.zone_id() else {
+            return Ok(events);
+        };
+
+        // Zones without dialog or entity names just don't get the reference text.
+        let mapping = DatIdMapping::get();
+        let dialog = mapping
+            .dialog
+            .get(&zone_id)
+            .and_then(|dat| dat_context.get_data_from_dat(dat).ok())
+            .map(|extracted| extracted.dat);
+        let entities = mapping
+            .entities
+            .get(&zone_id)
+            .and_then(|dat| dat_context.get_data_from_dat(dat).ok())
+            .map(|extracted| extracted.dat);
+
+        events.resolve_names(dialog.as_ref(), entities.as_ref());
+        Ok(events)
+    }
+
+    fn apply_to(self, base: &mut EventFile) -> Result<()> {
+        *base = self;
+        Ok(())
+    }
+}
@@ -0,0 +1,266 @@
+use std::collections::BTreeMap;
+
+use anyhow::{anyhow, Result};
+use serde_derive::{Deserialize, Serialize};
+
+use crate::{
+    context::DatContext,
+    enums::{ItemType, SkillType},
+    flags::{EquipmentSlot, ItemFlag, JobFlag, Race, ValidTargets},
+    formats::item_info::{EquipmentData, ItemInfo, ItemInfoTable, WeaponData},
+    serde_pipe_flags,
+};
+
+use super::DatView;
+
+/// Items flattened into one row each, so that they can be edited as a spreadsheet.
+/// Only the common, equipment and weapon fields are included, everything else of an item
+/// is left as is when the rows are applied.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ItemTableView {
+    pub rows: Vec<ItemRow>,
+}
+
+/// The equipment and weapon columns are empty for items without that data.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct ItemRow {
+    pub id: u32,
+    /// Only for reference, the strings of items aren't edited through their rows.
+    pub name: Option<String>,
+
+    #[serde(with = "serde_pipe_flags")]
+    pub flags: ItemFlag,
+    pub stack_size: u16,
+    pub item_type: ItemType,
+    pub resource_id: u16,
+    #[serde(with = "serde_pipe_flags")]
+    pub valid_targets: ValidTargets,
+
+    pub level: Option<u16>,
+    #[serde(with = "serde_pipe_flags::option")]
+    pub slots: Option<EquipmentSlot>,
+    #[serde(with = "serde_pipe_flags::option")]
+    pub races: Option<Race>,
+    #[serde(with = "serde_pipe_flags::option")]
+    pub jobs: Option<JobFlag>,
+    pub superior_level: Option<u16>,
+    pub shield_size: Option<u16>,
+    pub max_charges: Option<u8>,
+    pub casting_time: Option<u8>,
+    pub use_delay: Option<u16>,
+    pub reuse_delay: Option<u32>,
+    pub equipment_unknown1: Option<u16>,
+    pub ilevel: Option<u8>,
+    pub equipment_unknown2: Option<u8>,
+    pub equipment_unknown3: Option<u32>,
+
+    pub damage: Option<u16>,
+    pub delay: Option<u16>,
+    pub dps: Option<u16>,
+    pub skill_type: Option<SkillType>,
+    pub jug_size: Option<u8>,
+    pub weapon_unknown1: Option<u32>,
+}
+
+/// Builds the data of a group of columns, which have to be either all filled in or all empty.
+macro_rules! column_group {
+    ($row:expr, $group:literal, $data:ident { $($field:ident: $column:ident),* $(,)? }) => {{
+        let filled = [$($row.$column.is_some()),*];
+        if filled.iter().all(|filled| *filled) {
+            Some($data {
+                $($field: $row.$column.unwrap(),)*
+            })
+        } else if filled.iter().any(|filled| *filled) {
+            return Err(anyhow!(
+                "Item {} has only some of its {} columns filled in.",
+                $row.id,
+                $group
+            ));
+        } else {
+            None
+        }
+    }};
+}
+
+impl ItemRow {
+    pub fn from_item(item: &ItemInfo) -> Self {
+        let equipment = item.equipment.as_ref();
+        let weapon = item.weapon.as_ref();
+
+        ItemRow {
+            id: item.id,
+            name: item
+                .strings
+                .as_ref()
+                .map(|strings| strings.name().to_string()),
+
+            flags: item.flags,
+            stack_size: item.stack_size,
+            item_type: item.item_type,
+            resource_id: item.resource_id,
+            valid_targets: item.valid_targets,
+
+            level: equipment.map(|equipment| equipment.level),
+            slots: equipment.map(|equipment| equipment.slots),
+            races: equipment.map(|equipment| equipment.races),
+            jobs: equipment.map(|equipment| equipment.jobs),
+            superior_level: equipment.map(|equipment| equipment.superior_level),
+            shield_size: equipment.map(|equipment| equipment.shield_size),
+            max_charges: equipment.map(|equipment| equipment.max_charges),
+            casting_time: equipment.map(|equipment| equipment.casting_time),
+            use_delay: equipment.map(|equipment| equipment.use_delay),
+            reuse_delay: equipment.map(|equipment| equipment.reuse_delay),
+            equipment_unknown1: equipment.map(|equipment| equipment.unknown1),
+            ilevel: equipment.map(|equipment| equipment.ilevel),
+            equipment_unknown2: equipment.map(|equipment| equipment.unknown2),
+            equipment_unknown3: equipment.map(|equipment| equipment.unknown3),
+
+            damage: weapon.map(|weapon| weapon.damage),
+            delay: weapon.map(|weapon| weapon.delay),
+            dps: weapon.map(|weapon| weapon.dps),
+            skill_type: weapon.map(|weapon| weapon.skill_type),
+            jug_size: weapon.map(|weapon| weapon.jug_size),
+            weapon_unknown1: weapon.map(|weapon| weapon.unknown1),
+        }
+    }
+
+    /// Replaces the fields of the item which the row covers.
+    pub fn apply_to(self, item: &mut ItemInfo) -> Result<()> {
+        let equipment = column_group!(
+            self,
+            "equipment",
+            EquipmentData {
+                level: level,
+                slots: slots,
+                races: races,
+                jobs: jobs,
+                superior_level: superior_level,
+                shield_size: shield_size,
+                max_charges: max_charges,
+                casting_time: casting_time,
+                use_delay: use_delay,
+                reuse_delay: reuse_delay,
+                unknown1: equipment_unknown1,
+                ilevel: ilevel,
+                unknown2: equipment_unknown2,
+                unknown3: equipment_unknown3,
+            }
+        );
+        let weapon = column_group!(
+            self,
+            "weapon",
+            WeaponData {
+                damage: damage,
+                delay: delay,
+                dps: dps,
+                skill_type: skill_type,
+                jug_size: jug_size,
+                unknown1: weapon_unknown1,
+            }
+        );
+        if weapon.is_some() && equipment.is_none() {
+            return Err(anyhow!(
+                "Item {} has weapon columns, but no equipment columns.",
+                self.id
+            ));
+        }
+
+        item.flags = self.flags;
+        item.stack_size = self.stack_size;
+        item.item_type = self.item_type;
+        item.resource_id = self.resource_id;
+        item.valid_targets = self.valid_targets;
+        item.equipment = equipment;
+        item.weapon = weapon;
+
+        Ok(())
+    }
+}
+
+impl ItemTableView {
+    pub fn from_table(table: &ItemInfoTable) -> Self {
+        ItemTableView {
+            rows: table.items.iter().map(ItemRow::from_item).collect(),
+        }
+    }
+}
+
+impl DatView for ItemTableView {
+    type Base = ItemInfoTable;
+
+    fn from_base(base: &ItemInfoTable, _dat_context: &DatContext) -> Result<Self> {
+        Ok(Self::from_table(base))
+    }
+
+    fn apply_to(self, base: &mut ItemInfoTable) -> Result<()> {
+        let mut items = base
+            .items
+            .iter_mut()
+            .map(|item| (item.id, item))
+            .collect::<BTreeMap<_, _>>();
+
+        for row in self.rows {
+            let item = items
+                .get_mut(&row.id)
+                .ok_or_else(|| anyhow!("There's no item with ID {} in the table.", row.id))?;
+            row.apply_to(item)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        dat_format::DatFormat, flags::JobFlag, formats::item_info::ItemInfoTable, views::DatView,
+    };
+
+    use super::ItemTableView;
+
+    #[test]
+    fn rows_of_fixture() {
+        let table = ItemInfoTable::fixture();
+        let view = ItemTableView::from_table(&table);
+
+        assert_eq!(view.rows.len(), 2);
+        assert_eq!(view.rows[0].name.as_deref(), Some("Chocobo Bedding"));
+        assert_eq!(view.rows[0].level, None);
+        assert_eq!(view.rows[1].delay, Some(240));
+        assert_eq!(view.rows[1].jobs, Some(JobFlag::WAR | JobFlag::PLD));
+    }
+
+    #[test]
+    fn apply_keeps_other_fields() {
+        let mut table = ItemInfoTable::fixture();
+        let mut view = ItemTableView::from_table(&table);
+        view.rows[1].delay = Some(200);
+        view.rows[1].name = Some("Not applied".to_string());
+        view.rows.remove(0);
+
+        let original = serde_yaml::to_value(&table).unwrap();
+        view.apply_to(&mut table).unwrap();
+        let applied = serde_yaml::to_value(&table).unwrap();
+
+        assert_eq!(table.items[1].weapon.as_ref().unwrap().delay, 200);
+        assert_eq!(
+            applied["items"][1]["icon_bytes"],
+            original["items"][1]["icon_bytes"]
+        );
+        assert_eq!(
+            table.items[1].strings.as_ref().unwrap().name(),
+            "Excalipoor"
+        );
+        assert_eq!(applied["items"][0], original["items"][0]);
+        assert!(table.to_bytes().is_ok());
+    }
+
+    #[test]
+    fn partial_groups_are_rejected() {
+        let mut table = ItemInfoTable::fixture();
+        let mut view = ItemTableView::from_table(&table);
+        view.rows[1].damage = None;
+
+        assert!(view.apply_to(&mut table).is_err());
+    }
+}
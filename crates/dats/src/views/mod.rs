@@ -0,0 +1,66 @@
+use std::{collections::BTreeMap, fmt::Debug};
+
+use anyhow::{anyhow, Result};
+
+use crate::{context::DatContext, dat_format::DatFormat};
+
+pub mod ability_info;
+pub mod events;
+pub mod item_table;
+pub mod spell_info;
+
+/// Editable part of a DAT, which can be joined with data from other DATs for context.
+pub trait DatView: Sized {
+    type Base: DatFormat;
+
+    fn from_base(base: &Self::Base, dat_context: &DatContext) -> Result<Self>;
+
+    /// Replaces the records of the base DAT with the ones of this view,
+    /// while leaving everything else untouched.
+    fn apply_to(self, base: &mut Self::Base) -> Result<()>;
+
+    /// Keeps only the records which differ from the `original` view, so that applying this
+    /// view doesn't undo edits made in the base DAT's own file, as seen in `edited_base`.
+    /// Records which were edited differently in both are an error. Views which are the only
+    /// file of their DAT have no other edits to keep.
+    fn retain_edits(&mut self, _original: &Self, _edited_base: &Self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Implements [`DatView::retain_edits`] for records by their ID, compared by the part of
+/// them which gets applied.
+pub(crate) fn retain_edited_records<K: Ord + Debug, R, T: PartialEq>(
+    records: &mut BTreeMap<K, R>,
+    original: &BTreeMap<K, R>,
+    edited_base: &BTreeMap<K, R>,
+    applied: impl Fn(&R) -> &T,
+) -> Result<()> {
+    records.retain(|id, record| {
+        original
+            .get(id)
+            .is_none_or(|original| applied(original) != applied(record))
+    });
+
+    let conflicts: Vec<&K> = records
+        .iter()
+        .filter(
+            |(id, record)| match (original.get(*id), edited_base.get(*id)) {
+                (Some(original), Some(edited)) => {
+                    applied(edited) != applied(original) && applied(edited) != applied(record)
+                }
+                _ => false,
+            },
+        )
+        .map(|(id, _)| id)
+        .collect();
+
+    if !conflicts.is_empty() {
+        return Err(anyhow!(
+            "Records {:?} were edited differently in both the view and its DAT's own file.",
+            conflicts
+        ));
+    }
+
+    Ok(())
+}
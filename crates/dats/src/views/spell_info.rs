@@ -0,0 +1,172 @@
+use std::collections::BTreeMap;
+
+use anyhow::{anyhow, Result};
+use serde_derive::{Deserialize, Serialize};
+
+use crate::{
+    context::DatContext,
+    formats::{
+        dmsg3_string_table::Dmsg3StringTable,
+        menu_table::{MagicInfo, MenuTable},
+    },
+    id_mapping::DatIdMapping,
+};
+
+use super::{retain_edited_records, DatView};
+
+/// Spells of the menu DAT by their ID, joined with their names and descriptions.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SpellInfoView {
+    pub spells: BTreeMap<u16, SpellEntry>,
+}
+
+/// The name and description are only for reference, and are edited through their own DATs.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SpellEntry {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub info: MagicInfo,
+}
+
+impl SpellInfoView {
+    pub fn join(
+        menu: &MenuTable,
+        names: &Dmsg3StringTable,
+        descriptions: &Dmsg3StringTable,
+    ) -> Result<Self> {
+        let mut spells = BTreeMap::new();
+        // Unused spell slots all have an ID of 0, and are left as they are.
+        for info in menu.spells().filter(|info| info.id != 0) {
+            let id = info.id;
+            let entry = SpellEntry {
+                name: names.string(id as u32).map(str::to_string),
+                description: descriptions.string(id as u32).map(str::to_string),
+                info: info.clone(),
+            };
+
+            if spells.insert(id, entry).is_some() {
+                return Err(anyhow!("Found more than one spell with ID {id}."));
+            }
+        }
+
+        Ok(SpellInfoView { spells })
+    }
+}
+
+impl DatView for SpellInfoView {
+    type Base = MenuTable;
+
+    fn from_base(base: &MenuTable, dat_context: &DatContext) -> Result<Self> {
+        let mapping = DatIdMapping::get();
+        let names = dat_context.get_data_from_dat(&mapping.spell_names)?.dat;
+        let descriptions = dat_context
+            .get_data_from_dat(&mapping.spell_descriptions)?
+            .dat;
+
+        Self::join(base, &names, &descriptions)
+    }
+
+    fn apply_to(mut self, base: &mut MenuTable) -> Result<()> {
+        for info in base.spells_mut().filter(|info| info.id != 0) {
+            if let Some(entry) = self.spells.remove(&info.id) {
+                if entry.info.id != info.id {
+                    return Err(anyhow!(
+                        "Spell {} has a different ID in its info: {}",
+                        info.id,
+                        entry.info.id
+                    ));
+                }
+                *info = entry.info;
+            }
+        }
+
+        if !self.spells.is_empty() {
+            return Err(anyhow!(
+                "Spells can't be added to the menu DAT yet: {:?}",
+                self.spells.keys().collect::<Vec<_>>()
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn retain_edits(&mut self, original: &Self, edited_base: &Self) -> Result<()> {
+        retain_edited_records(
+            &mut self.spells,
+            &original.spells,
+            &edited_base.spells,
+            |entry| &entry.info,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::{
+        dat_format::DatFormat,
+        formats::{dmsg3_string_table::Dmsg3StringTable, menu_table::MenuTable},
+    };
+
+    use super::{DatView, SpellInfoView};
+
+    #[test]
+    pub fn menu_spells() {
+        let mut dat_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        dat_path.push("resources/test/menu.DAT");
+        let menu = MenuTable::from_path(&dat_path).unwrap();
+
+        let view = SpellInfoView::join(
+            &menu,
+            &Dmsg3StringTable::default(),
+            &Dmsg3StringTable::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            view.spells.len(),
+            menu.spells().filter(|info| info.id != 0).count()
+        );
+
+        let original_bytes = menu.to_bytes().unwrap();
+        let mut edited = MenuTable::from_bytes(&original_bytes).unwrap();
+        view.apply_to(&mut edited).unwrap();
+        assert_eq!(edited.to_bytes().unwrap(), original_bytes);
+    }
+
+    #[test]
+    pub fn edit_spell() {
+        let menu = MenuTable::fixture();
+        let names = Dmsg3StringTable::fixture();
+
+        let mut view = SpellInfoView::join(&menu, &names, &Dmsg3StringTable::default()).unwrap();
+        assert_eq!(view.spells.len(), 4);
+        assert_eq!(view.spells[&1].name.as_deref(), Some("Test pass"));
+
+        view.spells.retain(|id, _| *id == 2);
+        view.spells.get_mut(&2).unwrap().info.mp_cost = 99;
+
+        let mut edited = MenuTable::fixture();
+        view.apply_to(&mut edited).unwrap();
+
+        let mp_costs: Vec<_> = edited.spells().map(|info| info.mp_cost).collect();
+        assert_eq!(mp_costs, vec![8, 99, 24, 32]);
+    }
+
+    #[test]
+    pub fn unknown_spell() {
+        let mut view = SpellInfoView::join(
+            &MenuTable::fixture(),
+            &Dmsg3StringTable::default(),
+            &Dmsg3StringTable::default(),
+        )
+        .unwrap();
+        let mut entry = view.spells.remove(&4).unwrap();
+        entry.info = MenuTable::fixture().spells().next().unwrap().clone();
+        view.spells.insert(5, entry);
+
+        assert!(view.apply_to(&mut MenuTable::fixture()).is_err());
+    }
+}
@@ -3,9 +3,12 @@ use std::{
     sync::OnceLock,
 };
 
+use crate::language::Language;
+
 pub struct ConversionTable;
 
 static REVERSE_TABLE: OnceLock<HashMap<u16, u16>> = OnceLock::new();
+static JAPANESE_REVERSE_TABLE: OnceLock<HashMap<u16, u16>> = OnceLock::new();
 
 const EMPTY_TABLE: [u8; 512] = [0xFF; 512];
 
@@ -20,71 +23,75 @@ impl ConversionTable {
         )
     }
 
-    pub fn rev_lookup(input: u16) -> u16 {
-        REVERSE_TABLE
-            .get_or_init(|| {
-                let mut map = HashMap::new();
+    pub fn rev_lookup_for(input: u16, language: Language) -> u16 {
+        let table = match language {
+            Language::English => REVERSE_TABLE.get_or_init(|| Self::build_reverse_table(|_, _| 0)),
+            Language::Japanese => {
+                JAPANESE_REVERSE_TABLE.get_or_init(|| Self::build_reverse_table(japanese_rank))
+            }
+        };
+
+        table.get(&input).copied().unwrap_or_default()
+    }
 
-                let index_table = Self::get_table(0x00);
+    // Several characters are found in more than one place in the tables. The byte(s) with the
+    // lowest rank are used for those, and the later one if the ranks are equal.
+    fn build_reverse_table(rank: impl Fn(u8, Option<u8>) -> u8) -> HashMap<u16, u16> {
+        let mut map: HashMap<u16, (u16, u8)> = HashMap::new();
+        let mut insert = |short: u16, value: u16, rank: u8| match map.entry(short) {
+            Entry::Occupied(mut occupied) => {
+                // Duplicate character conversion
+                if rank <= occupied.get().1 {
+                    occupied.insert((value, rank));
+                }
+            }
+            Entry::Vacant(vacant) => {
+                vacant.insert((value, rank));
+            }
+        };
+
+        let index_table = Self::get_table(0x00);
+
+        for first_byte in 0x00u8..=0xFF {
+            let first_idx = first_byte as usize * 2;
+            let first_short =
+                u16::from_le_bytes(index_table[first_idx..first_idx + 2].try_into().unwrap());
+
+            // Check if it needs a seondary lookup
+            if first_short == 0xFFFE {
+                let second_table = Self::get_table(first_byte);
+
+                if second_table == &EMPTY_TABLE {
+                    // It's the empty table.
+                    continue;
+                }
 
-                for first_byte in 0x00u8..=0xFF {
-                    let first_idx = first_byte as usize * 2;
-                    let first_short = u16::from_le_bytes(
-                        index_table[first_idx..first_idx + 2].try_into().unwrap(),
+                for second_byte in 0x00u8..=0xFF {
+                    let second_idx = second_byte as usize * 2;
+                    let second_short = u16::from_le_bytes(
+                        second_table[second_idx..second_idx + 2].try_into().unwrap(),
                     );
 
-                    // Check if it needs a seondary lookup
-                    if first_short == 0xFFFE {
-                        let second_table = Self::get_table(first_byte);
-
-                        if second_table == &EMPTY_TABLE {
-                            // It's the empty table.
-                            continue;
-                        }
-
-                        for second_byte in 0x00u8..=0xFF {
-                            let second_idx = second_byte as usize * 2;
-                            let second_short = u16::from_le_bytes(
-                                second_table[second_idx..second_idx + 2].try_into().unwrap(),
-                            );
-
-                            if second_short == 0xFFFF {
-                                // No conversion
-                                continue;
-                            }
-
-                            let value = u16::from_le_bytes([first_byte, second_byte]);
-                            match map.entry(second_short) {
-                                Entry::Occupied(mut occupied) => {
-                                    // Duplicate character conversion
-                                    occupied.insert(value);
-                                }
-                                Entry::Vacant(vacant) => {
-                                    vacant.insert(value);
-                                }
-                            }
-                        }
-                    } else if first_short == 0xFFFF {
+                    if second_short == 0xFFFF {
                         // No conversion
                         continue;
-                    } else {
-                        let value = u16::from_le_bytes([0, first_byte]);
-                        match map.entry(first_short) {
-                            Entry::Occupied(mut occupied) => {
-                                // Duplicate character conversion
-                                occupied.insert(value);
-                            }
-                            Entry::Vacant(vacant) => {
-                                vacant.insert(value);
-                            }
-                        }
                     }
+
+                    let value = u16::from_le_bytes([first_byte, second_byte]);
+                    insert(second_short, value, rank(first_byte, Some(second_byte)));
                 }
-                map
-            })
-            .get(&input)
-            .copied()
-            .unwrap_or_default()
+            } else if first_short == 0xFFFF {
+                // No conversion
+                continue;
+            } else {
+                let value = u16::from_le_bytes([0, first_byte]);
+                insert(first_short, value, rank(first_byte, None));
+            }
+        }
+
+        map.into_iter()
+            .map(|(short, (value, _))| (short, value))
+            .collect()
     }
 
     #[inline]
@@ -160,9 +167,25 @@ impl ConversionTable {
     }
 }
 
+// Prefers the standard Shift-JIS (CP932) bytes of a character over FFXI's own additions,
+// since those are what the Japanese text is written with.
+fn japanese_rank(first_byte: u8, second_byte: Option<u8>) -> u8 {
+    match (first_byte, second_byte) {
+        // FFXI specific characters, like the European letters
+        (0x85 | 0x86, Some(_)) | (0x87, Some(0x9D..)) | (0x88, Some(..=0x9E)) => 4,
+        // NEC-selected IBM extensions
+        (0xED | 0xEE, Some(_)) => 3,
+        // IBM extensions
+        (0xFA..=0xFC, Some(_)) => 2,
+        // NEC special characters
+        (0x87, Some(_)) => 1,
+        _ => 0,
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::conversion_tables::ConversionTable;
+    use crate::{conversion_tables::ConversionTable, language::Language};
 
     fn get_misc_conversions() -> Vec<((u8, u8), u16)> {
         let mut u16_buffer = [0u16; 2];
@@ -188,10 +211,40 @@ mod tests {
                     "failed lookup"
                 );
                 assert_eq!(
-                    ConversionTable::rev_lookup(short),
+                    ConversionTable::rev_lookup_for(short, Language::English),
                     u16::from_le_bytes([first_byte, second_byte]),
                     "failed reverse lookup"
                 );
             });
     }
+
+    #[test]
+    fn japanese_duplicate_conversions() {
+        // (character, English bytes, Japanese bytes)
+        let duplicates = [
+            ('‘', [0x87, 0xB0], [0x81, 0x65]),
+            ('§', [0x85, 0x67], [0x81, 0x98]),
+            ('√', [0x87, 0x95], [0x81, 0xE3]),
+            ('Ⅰ', [0xFA, 0x4A], [0x87, 0x54]),
+            ('∵', [0xFA, 0x5B], [0x81, 0xE6]),
+            ('ｱ', [0x00, 0xB1], [0x00, 0xB1]),
+            ('é', [0x88, 0x69], [0x88, 0x69]),
+        ];
+
+        for (char, english_bytes, japanese_bytes) in duplicates {
+            let short = char as u16;
+            assert_eq!(
+                ConversionTable::rev_lookup_for(short, Language::English),
+                u16::from_le_bytes(english_bytes),
+                "{}",
+                char
+            );
+            assert_eq!(
+                ConversionTable::rev_lookup_for(short, Language::Japanese),
+                u16::from_le_bytes(japanese_bytes),
+                "{}",
+                char
+            );
+        }
+    }
 }
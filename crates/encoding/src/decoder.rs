@@ -3,6 +3,7 @@ use std::char::decode_utf16;
 use crate::{
     conversion_tables::ConversionTable,
     encoder::Encoder,
+    language::Language,
     named_bytes::{base_len_1, icon, prefix_01, prefix_7f_len_1},
     SPACE_U16, TAG_END_U16, TAG_PARAM_START_U16, TAG_PREFIX_U16, TAG_START_U16,
 };
@@ -28,14 +29,24 @@ impl<'a> Decoder<'a> {
 
 impl<'a> Decoder<'a> {
     pub fn decode_simple(bytes: &[u8]) -> Result<String> {
-        Self::decode(bytes, true)
+        Self::decode_simple_for(bytes, Language::current())
     }
 
     pub fn decode_dialog(bytes: &[u8]) -> Result<String> {
-        Self::decode(bytes, false)
+        Self::decode_dialog_for(bytes, Language::current())
     }
 
-    pub(crate) fn decode(bytes: &[u8], is_simple: bool) -> Result<String> {
+    pub fn decode_simple_for(bytes: &[u8], language: Language) -> Result<String> {
+        Self::decode(bytes, true, language)
+    }
+
+    pub fn decode_dialog_for(bytes: &[u8], language: Language) -> Result<String> {
+        Self::decode(bytes, false, language)
+    }
+
+    // The conversion tables contain both the Shift-JIS characters and FFXI's additions,
+    // so decoding is the same for all languages. Only re-encoding depends on the language.
+    pub(crate) fn decode(bytes: &[u8], is_simple: bool, language: Language) -> Result<String> {
         if bytes.is_empty() {
             return Ok("".to_string());
         }
@@ -83,7 +94,7 @@ impl<'a> Decoder<'a> {
 
             let bytes = &bytes[..last_idx];
 
-            let encoded_bytes = Encoder::encode(&string, is_simple)?;
+            let encoded_bytes = Encoder::encode(&string, is_simple, language)?;
 
             if encoded_bytes != bytes {
                 eprintln!(
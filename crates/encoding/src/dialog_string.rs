@@ -0,0 +1,385 @@
+use std::{fmt::Display, str::FromStr};
+
+use anyhow::{anyhow, Result};
+
+use crate::{
+    decoder::Decoder,
+    encoder::Encoder,
+    language::Language,
+    named_bytes::icon,
+    validation::{check_tag, split_tag, ParameterKind, ValidationError, ValidationErrorKind},
+    TAG_END, TAG_PREFIX, TAG_START,
+};
+
+/// A dialog string as its text runs and typed control tags, instead of the text syntax
+/// with inline `${tag}` markup. Converts to and from both the bytes and the text syntax.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DialogString {
+    pub nodes: Vec<DialogNode>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DialogNode {
+    /// Text without any tags, with line breaks as `\n`.
+    Text(String),
+    /// Waits for the player before continuing, which ends most dialog.
+    Prompt,
+    /// Lines the player picks one of, which run until the prompt or the end of the string.
+    Choices(Vec<Vec<DialogNode>>),
+    /// Any other control tag.
+    Tag(Tag),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tag {
+    pub name: String,
+    pub parameters: TagParameters,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TagParameters {
+    None,
+    /// A single byte, e.g. the index of a number or color.
+    Byte(u8),
+    /// A named icon, e.g. `fire`.
+    Icon(String),
+    /// Raw bytes of unknown tags and unnamed icons.
+    Bytes(Vec<u8>),
+    /// Values together with their byte length, e.g. the index of an item.
+    Values(Vec<SizedValue>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SizedValue {
+    pub value: u32,
+    /// Byte length of the value, which is 1, 2 or 4.
+    pub len: u8,
+}
+
+impl DialogString {
+    pub fn decode(bytes: &[u8]) -> Result<Self> {
+        Self::decode_for(bytes, Language::current())
+    }
+
+    pub fn decode_for(bytes: &[u8], language: Language) -> Result<Self> {
+        Decoder::decode_dialog_for(bytes, language)?.parse()
+    }
+
+    pub fn encode(&self) -> Result<Vec<u8>> {
+        self.encode_for(Language::current())
+    }
+
+    pub fn encode_for(&self, language: Language) -> Result<Vec<u8>> {
+        Encoder::encode_dialog_for(&self.to_string(), language)
+    }
+
+    /// The text of the string without any tags, with one line for each choice.
+    pub fn plain_text(&self) -> String {
+        let mut text = String::new();
+        Self::push_plain_text(&mut text, &self.nodes);
+        text
+    }
+
+    fn push_plain_text(text: &mut String, nodes: &[DialogNode]) {
+        for node in nodes {
+            match node {
+                DialogNode::Text(run) => text.push_str(run),
+                DialogNode::Choices(lines) => {
+                    for (idx, line) in lines.iter().enumerate() {
+                        if idx > 0 {
+                            text.push('\n');
+                        }
+                        Self::push_plain_text(text, line);
+                    }
+                }
+                DialogNode::Prompt | DialogNode::Tag(_) => {}
+            }
+        }
+    }
+
+    /// The nodes new ones are added to, which are those of the last choice if there are any.
+    fn current_nodes<'a>(
+        nodes: &'a mut Vec<DialogNode>,
+        choices: &'a mut Option<Vec<Vec<DialogNode>>>,
+    ) -> &'a mut Vec<DialogNode> {
+        match choices.as_mut().and_then(|lines| lines.last_mut()) {
+            Some(line) => line,
+            None => nodes,
+        }
+    }
+
+    fn push_text(
+        nodes: &mut Vec<DialogNode>,
+        choices: &mut Option<Vec<Vec<DialogNode>>>,
+        text: &mut String,
+    ) {
+        if !text.is_empty() {
+            Self::current_nodes(nodes, choices).push(DialogNode::Text(std::mem::take(text)));
+        }
+    }
+}
+
+impl FromStr for DialogString {
+    type Err = anyhow::Error;
+
+    /// Parses the text syntax, failing on the first tag the encoder couldn't encode.
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        let mut nodes = vec![];
+        let mut choices: Option<Vec<Vec<DialogNode>>> = None;
+        let mut text = String::new();
+        let mut chars = string.char_indices().peekable();
+
+        while let Some((idx, char)) = chars.next() {
+            if char == TAG_PREFIX && chars.peek().is_some_and(|(_, ch)| *ch == TAG_START) {
+                let Some(tag_end) = string[idx..].find(TAG_END).map(|end| idx + end) else {
+                    return Err(anyhow!(
+                        "{}",
+                        ValidationError::at(string, idx, ValidationErrorKind::UnclosedTag)
+                    ));
+                };
+                while chars
+                    .next_if(|(char_idx, _)| *char_idx <= tag_end)
+                    .is_some()
+                {}
+
+                let (name, parameters) = split_tag(&string[idx + 2..tag_end]);
+                if let Some(kind) = check_tag(name, parameters) {
+                    return Err(anyhow!("{}", ValidationError::at(string, idx, kind)));
+                }
+
+                Self::push_text(&mut nodes, &mut choices, &mut text);
+                match name {
+                    "prompt" => {
+                        if let Some(lines) = choices.take() {
+                            nodes.push(DialogNode::Choices(lines));
+                        }
+                        nodes.push(DialogNode::Prompt);
+                    }
+
+                    "selection-lines" => {
+                        if let Some(lines) = choices.take() {
+                            nodes.push(DialogNode::Choices(lines));
+                        }
+                        choices = Some(vec![vec![]]);
+
+                        // The encoder skips one newline following it, like here.
+                        chars.next_if(|(_, ch)| *ch == '\n');
+                    }
+
+                    _ => Self::current_nodes(&mut nodes, &mut choices)
+                        .push(DialogNode::Tag(Tag::parse(name, parameters)?)),
+                }
+            } else if char == '\n' && choices.is_some() {
+                Self::push_text(&mut nodes, &mut choices, &mut text);
+                choices.as_mut().unwrap().push(vec![]);
+            } else {
+                text.push(char);
+            }
+        }
+
+        Self::push_text(&mut nodes, &mut choices, &mut text);
+        if let Some(lines) = choices {
+            nodes.push(DialogNode::Choices(lines));
+        }
+
+        Ok(Self { nodes })
+    }
+}
+
+impl Display for DialogString {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.nodes.iter().try_for_each(|node| write!(f, "{}", node))
+    }
+}
+
+impl Display for DialogNode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DialogNode::Text(text) => write!(f, "{}", text),
+            DialogNode::Prompt => write!(f, "${{prompt}}"),
+            DialogNode::Choices(lines) => {
+                write!(f, "${{selection-lines}}")?;
+                for line in lines {
+                    writeln!(f)?;
+                    line.iter().try_for_each(|node| write!(f, "{}", node))?;
+                }
+                Ok(())
+            }
+            DialogNode::Tag(tag) => write!(f, "{}", tag),
+        }
+    }
+}
+
+impl Tag {
+    /// Parses the parameters of a tag that has already been checked.
+    fn parse(name: &str, parameters: &str) -> Result<Self> {
+        let kind = ParameterKind::of(name).ok_or_else(|| anyhow!("Unknown tag '{}'", name))?;
+
+        let parameters = match kind {
+            ParameterKind::Nothing => TagParameters::None,
+            ParameterKind::Byte => TagParameters::Byte(parameters.parse()?),
+            ParameterKind::Icon if icon::encode(parameters).is_some() => {
+                TagParameters::Icon(parameters.to_string())
+            }
+            ParameterKind::Icon | ParameterKind::HexBytes => {
+                TagParameters::Bytes(Self::parse_hex_bytes(parameters)?)
+            }
+            ParameterKind::SizedValues if parameters.is_empty() => TagParameters::Values(vec![]),
+            ParameterKind::SizedValues => TagParameters::Values(
+                parameters
+                    .split(',')
+                    .map(|parameter| {
+                        Encoder::parse_param_with_length(parameter)
+                            .map(|(value, len)| SizedValue {
+                                value,
+                                len: len as u8,
+                            })
+                            .ok_or_else(|| anyhow!("Invalid parameter '{}'", parameter))
+                    })
+                    .collect::<Result<_>>()?,
+            ),
+        };
+
+        Ok(Self {
+            name: name.to_string(),
+            parameters,
+        })
+    }
+
+    fn parse_hex_bytes(parameters: &str) -> Result<Vec<u8>> {
+        let hex = parameters
+            .strip_prefix("0x")
+            .ok_or_else(|| anyhow!("Expected hex bytes, got '{}'", parameters))?;
+
+        (0..hex.len())
+            .step_by(2)
+            .map(|idx| Ok(u8::from_str_radix(&hex[idx..idx + 2], 16)?))
+            .collect()
+    }
+}
+
+impl Display for Tag {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "${{{}", self.name)?;
+        match &self.parameters {
+            TagParameters::None => {}
+            TagParameters::Byte(byte) => write!(f, ": {}", byte)?,
+            TagParameters::Icon(name) => write!(f, ": {}", name)?,
+            TagParameters::Bytes(bytes) => {
+                write!(f, ": 0x")?;
+                bytes
+                    .iter()
+                    .try_for_each(|byte| write!(f, "{:02X}", byte))?;
+            }
+            TagParameters::Values(values) if values.is_empty() => {}
+            TagParameters::Values(values) => {
+                write!(f, ": ")?;
+                for (idx, value) in values.iter().enumerate() {
+                    if idx > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}[{}]", value.value, value.len)?;
+                }
+            }
+        }
+        write!(f, "}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{language::Language, tests::example_strings_for_encoding};
+
+    use super::{DialogNode, DialogString, SizedValue, Tag, TagParameters};
+
+    #[test]
+    fn example_strings() {
+        for (bytes, string) in example_strings_for_encoding() {
+            let language = if string.is_ascii() {
+                Language::English
+            } else {
+                Language::Japanese
+            };
+
+            let dialog_string = DialogString::decode_for(bytes, language).unwrap();
+            assert_eq!(dialog_string.to_string(), string);
+            assert_eq!(string.parse::<DialogString>().unwrap(), dialog_string);
+            assert_eq!(dialog_string.encode_for(language).unwrap(), bytes);
+        }
+    }
+
+    #[test]
+    fn nodes() {
+        let dialog_string: DialogString =
+            "Can ya imagine it, ${name-player}?\n${number: 1}${item-plural: 0[2]}...${prompt}"
+                .parse()
+                .unwrap();
+        assert_eq!(
+            dialog_string.nodes,
+            vec![
+                DialogNode::Text("Can ya imagine it, ".to_string()),
+                DialogNode::Tag(Tag {
+                    name: "name-player".to_string(),
+                    parameters: TagParameters::None,
+                }),
+                DialogNode::Text("?\n".to_string()),
+                DialogNode::Tag(Tag {
+                    name: "number".to_string(),
+                    parameters: TagParameters::Byte(1),
+                }),
+                DialogNode::Tag(Tag {
+                    name: "item-plural".to_string(),
+                    parameters: TagParameters::Values(vec![SizedValue { value: 0, len: 2 }]),
+                }),
+                DialogNode::Text("...".to_string()),
+                DialogNode::Prompt,
+            ]
+        );
+    }
+
+    #[test]
+    fn choices() {
+        let string = "What do you speak of?\n${selection-lines}\nRazfahd's message.\n${icon: fire} ${unknown: 0x1A2B}.${prompt}";
+        let dialog_string: DialogString = string.parse().unwrap();
+        assert_eq!(
+            dialog_string.nodes,
+            vec![
+                DialogNode::Text("What do you speak of?\n".to_string()),
+                DialogNode::Choices(vec![
+                    vec![DialogNode::Text("Razfahd's message.".to_string())],
+                    vec![
+                        DialogNode::Tag(Tag {
+                            name: "icon".to_string(),
+                            parameters: TagParameters::Icon("fire".to_string()),
+                        }),
+                        DialogNode::Text(" ".to_string()),
+                        DialogNode::Tag(Tag {
+                            name: "unknown".to_string(),
+                            parameters: TagParameters::Bytes(vec![0x1A, 0x2B]),
+                        }),
+                        DialogNode::Text(".".to_string()),
+                    ],
+                ]),
+                DialogNode::Prompt,
+            ]
+        );
+        assert_eq!(dialog_string.to_string(), string);
+        assert_eq!(
+            dialog_string.plain_text(),
+            "What do you speak of?\nRazfahd's message.\n ."
+        );
+    }
+
+    #[test]
+    fn invalid_tags() {
+        assert_eq!(
+            "Hello, ${playr}!"
+                .parse::<DialogString>()
+                .unwrap_err()
+                .to_string(),
+            "line 1, column 8: Unknown tag 'playr'"
+        );
+        assert!("${number: 1".parse::<DialogString>().is_err());
+        assert!("${number: one}".parse::<DialogString>().is_err());
+    }
+}
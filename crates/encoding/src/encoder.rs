@@ -2,6 +2,7 @@ use std::{iter::Peekable, str::CharIndices};
 
 use crate::{
     conversion_tables::ConversionTable,
+    language::Language,
     named_bytes::{base_len_1, icon, prefix_01, prefix_7f_len_1},
     TAG_END, TAG_PARAM_START, TAG_PREFIX, TAG_START,
 };
@@ -12,6 +13,7 @@ pub struct Encoder<'a> {
     source_str: &'a str,
     source_chars: Peekable<CharIndices<'a>>,
     had_prompt: bool,
+    language: Language,
 }
 
 impl<'a> Encoder<'a> {
@@ -21,21 +23,31 @@ impl<'a> Encoder<'a> {
             source_str: str,
             source_chars: str.char_indices().peekable(),
             had_prompt: false,
+            language: Language::current(),
         }
     }
 }
 
 impl<'a> Encoder<'a> {
     pub fn encode_simple(string: &'a str) -> Result<Vec<u8>> {
-        Self::encode(string, true)
+        Self::encode_simple_for(string, Language::current())
     }
 
     pub fn encode_dialog(string: &'a str) -> Result<Vec<u8>> {
-        Self::encode(string, false)
+        Self::encode_dialog_for(string, Language::current())
     }
 
-    pub(crate) fn encode(string: &'a str, is_simple: bool) -> Result<Vec<u8>> {
+    pub fn encode_simple_for(string: &'a str, language: Language) -> Result<Vec<u8>> {
+        Self::encode(string, true, language)
+    }
+
+    pub fn encode_dialog_for(string: &'a str, language: Language) -> Result<Vec<u8>> {
+        Self::encode(string, false, language)
+    }
+
+    pub(crate) fn encode(string: &'a str, is_simple: bool, language: Language) -> Result<Vec<u8>> {
         let mut encoder = Encoder::new(string);
+        encoder.language = language;
         if is_simple {
             encoder.encode_all::<true>()?;
         } else {
@@ -101,7 +113,7 @@ impl<'a> Encoder<'a> {
             let original_utf16_shorts = char.encode_utf16(&mut u16_buffer);
             let converted_utf16_shorts = original_utf16_shorts
                 .iter()
-                .map(|short| ConversionTable::rev_lookup(*short))
+                .map(|short| ConversionTable::rev_lookup_for(*short, self.language))
                 .collect::<Vec<_>>();
 
             let final_utf16_shorts = if converted_utf16_shorts.iter().all(|short| *short > 0) {
@@ -316,7 +328,7 @@ impl<'a> Encoder<'a> {
         Ok(())
     }
 
-    fn parse_param_with_length(param: &str) -> Option<(u32, u32)> {
+    pub(crate) fn parse_param_with_length(param: &str) -> Option<(u32, u32)> {
         let mut param_iter = param.char_indices().peekable();
 
         // Skip whitespaces
@@ -386,7 +398,9 @@ impl<'a> Encoder<'a> {
 #[cfg(test)]
 mod tests {
 
-    use crate::{decoder::Decoder, encoder::Encoder, tests::example_strings_for_encoding};
+    use crate::{
+        decoder::Decoder, encoder::Encoder, language::Language, tests::example_strings_for_encoding,
+    };
     use pretty_assertions::assert_eq;
 
     #[test]
@@ -458,4 +472,33 @@ mod tests {
             "item singular roundtrip",
         );
     }
+
+    #[test]
+    fn japanese_roundtrips() {
+        let bytes: &[u8] = &[
+            0x81, 0x65, 0x87, 0x54, 0x81, 0x66, 0x81, 0xE3, 0xB1, 0xB2, 0x81, 0x40, 0x93, 0x50,
+            0x00, 0x07,
+        ];
+        let string = "‘Ⅰ’√ｱｲ\u{3000}撤";
+
+        assert_eq!(
+            Decoder::decode_dialog_for(bytes, Language::Japanese).unwrap(),
+            string
+        );
+        assert_eq!(
+            Encoder::encode_dialog_for(string, Language::Japanese).unwrap(),
+            bytes
+        );
+        assert_ne!(
+            Encoder::encode_dialog_for(string, Language::English).unwrap(),
+            bytes
+        );
+
+        assert_eq!(
+            Language::Japanese
+                .scoped(|| Encoder::encode_dialog(string))
+                .unwrap(),
+            bytes
+        );
+    }
 }
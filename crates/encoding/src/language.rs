@@ -0,0 +1,55 @@
+use std::cell::Cell;
+
+/// Language of the text in a DAT, which determines how characters are encoded.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Language {
+    #[default]
+    English,
+    Japanese,
+}
+
+thread_local! {
+    static CURRENT_LANGUAGE: Cell<Language> = const { Cell::new(Language::English) };
+}
+
+impl Language {
+    /// The language used by the decoding and encoding functions without an explicit language.
+    /// Defaults to English, and can be changed for the current thread with [`Language::scoped`].
+    pub fn current() -> Self {
+        CURRENT_LANGUAGE.with(|language| language.get())
+    }
+
+    /// Runs the given function with this language as the current language of this thread.
+    pub fn scoped<R>(self, f: impl FnOnce() -> R) -> R {
+        struct Reset(Language);
+        impl Drop for Reset {
+            fn drop(&mut self) {
+                CURRENT_LANGUAGE.with(|language| language.set(self.0));
+            }
+        }
+
+        let _reset = Reset(CURRENT_LANGUAGE.with(|language| language.replace(self)));
+        f()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Language;
+
+    #[test]
+    fn scoped_language() {
+        assert_eq!(Language::current(), Language::English);
+
+        let inner = Language::Japanese.scoped(|| {
+            assert_eq!(
+                Language::English.scoped(Language::current),
+                Language::English
+            );
+            Language::current()
+        });
+
+        assert_eq!(inner, Language::Japanese);
+        assert_eq!(Language::current(), Language::English);
+    }
+}
@@ -1,7 +1,10 @@
 mod conversion_tables;
 pub mod decoder;
+pub mod dialog_string;
 pub mod encoder;
+pub mod language;
 mod named_bytes;
+pub mod validation;
 
 const TAG_PREFIX: char = '$';
 const TAG_START: char = '{';
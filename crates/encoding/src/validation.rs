@@ -0,0 +1,369 @@
+use std::fmt::Display;
+
+use crate::{
+    conversion_tables::ConversionTable,
+    encoder::Encoder,
+    language::Language,
+    named_bytes::{base_len_1, icon, prefix_01, prefix_7f_len_1},
+    TAG_END, TAG_PARAM_START, TAG_PREFIX, TAG_START,
+};
+
+/// A problem which keeps a string from being encoded as written.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationError {
+    /// Offset of the problem from the start of the string, in characters.
+    pub offset: usize,
+    /// Line of the problem, starting at 1.
+    pub line: usize,
+    /// Column of the problem within its line in characters, starting at 1.
+    pub column: usize,
+    pub kind: ValidationErrorKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationErrorKind {
+    UnclosedTag,
+    UnknownTag(String),
+    UnexpectedParameters {
+        tag: String,
+    },
+    MissingParameter {
+        tag: String,
+        expected: &'static str,
+    },
+    InvalidParameter {
+        tag: String,
+        parameter: String,
+        expected: &'static str,
+    },
+    UnmappedCharacter(char),
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.kind
+        )
+    }
+}
+
+impl Display for ValidationErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValidationErrorKind::UnclosedTag => write!(f, "Tag is missing its closing '}}'"),
+            ValidationErrorKind::UnknownTag(tag) => write!(f, "Unknown tag '{}'", tag),
+            ValidationErrorKind::UnexpectedParameters { tag } => {
+                write!(f, "Tag '{}' takes no parameters", tag)
+            }
+            ValidationErrorKind::MissingParameter { tag, expected } => {
+                write!(
+                    f,
+                    "Tag '{}' is missing its parameter, expected {}",
+                    tag, expected
+                )
+            }
+            ValidationErrorKind::InvalidParameter {
+                tag,
+                parameter,
+                expected,
+            } => write!(
+                f,
+                "Invalid parameter '{}' of tag '{}', expected {}",
+                parameter, tag, expected
+            ),
+            ValidationErrorKind::UnmappedCharacter(char) => write!(
+                f,
+                "Character '{}' (U+{:04X}) can't be encoded",
+                char, *char as u32
+            ),
+        }
+    }
+}
+
+impl ValidationError {
+    pub(crate) fn at(string: &str, byte_idx: usize, kind: ValidationErrorKind) -> Self {
+        let preceding = &string[..byte_idx];
+        let line_start = preceding.rfind('\n').map(|idx| idx + 1).unwrap_or_default();
+
+        Self {
+            offset: preceding.chars().count(),
+            line: preceding.matches('\n').count() + 1,
+            column: preceding[line_start..].chars().count() + 1,
+            kind,
+        }
+    }
+}
+
+/// Parameters of a tag as the encoder expects them.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum ParameterKind {
+    Nothing,
+    Byte,
+    Icon,
+    HexBytes,
+    SizedValues,
+}
+
+impl ParameterKind {
+    pub(crate) fn of(tag: &str) -> Option<Self> {
+        match tag {
+            "prompt"
+            | "selection-lines"
+            | "name-player"
+            | "name-npc"
+            | "choice-player-gender"
+            | "choice-source-gender"
+            | "choice-target-gender"
+            | "entity-wrap-end"
+            | "entity-wrap-start"
+            | "related-entity" => Some(ParameterKind::Nothing),
+
+            "icon" => Some(ParameterKind::Icon),
+
+            "unknown" | "unknown-table" | "unknown-table-index" | "unknown-table-value" => {
+                Some(ParameterKind::HexBytes)
+            }
+
+            _ if base_len_1::encode(tag).is_some() || prefix_7f_len_1::encode(tag).is_some() => {
+                Some(ParameterKind::Byte)
+            }
+
+            _ if prefix_01::encode(tag).is_some() => Some(ParameterKind::SizedValues),
+
+            _ => None,
+        }
+    }
+
+    fn expected(self) -> &'static str {
+        match self {
+            ParameterKind::Nothing => "nothing",
+            ParameterKind::Byte => "a number from 0 to 255",
+            ParameterKind::Icon => "an icon name or hex bytes like 0x1F",
+            ParameterKind::HexBytes => "hex bytes like 0x0A1B",
+            ParameterKind::SizedValues => {
+                "values with their byte length of 1, 2 or 4, like `0[2], 1[1]`"
+            }
+        }
+    }
+
+    fn accepts(self, parameters: &str) -> bool {
+        match self {
+            ParameterKind::Nothing => parameters.is_empty(),
+            ParameterKind::Byte => parameters.parse::<u8>().is_ok(),
+            ParameterKind::Icon => icon::encode(parameters).is_some() || is_hex_bytes(parameters),
+            ParameterKind::HexBytes => is_hex_bytes(parameters),
+            ParameterKind::SizedValues => parameters.split(',').all(|parameter| {
+                match Encoder::parse_param_with_length(parameter) {
+                    Some((_, 4)) => true,
+                    Some((value, len @ (1 | 2))) => value < 1 << (8 * len),
+                    _ => false,
+                }
+            }),
+        }
+    }
+}
+
+pub(crate) fn is_hex_bytes(parameters: &str) -> bool {
+    parameters.strip_prefix("0x").is_some_and(|hex| {
+        !hex.is_empty() && hex.len() % 2 == 0 && hex.chars().all(|ch| ch.is_ascii_hexdigit())
+    })
+}
+
+/// Splits the content between a tag's braces into its name and parameters.
+pub(crate) fn split_tag(content: &str) -> (&str, &str) {
+    match content.split_once(TAG_PARAM_START) {
+        Some((tag, parameters)) => (tag, parameters.trim_start_matches(' ')),
+        None => (content, ""),
+    }
+}
+
+pub(crate) fn check_tag(tag: &str, parameters: &str) -> Option<ValidationErrorKind> {
+    let Some(expected) = ParameterKind::of(tag) else {
+        return Some(ValidationErrorKind::UnknownTag(tag.to_string()));
+    };
+
+    match expected {
+        ParameterKind::Nothing if !parameters.is_empty() => {
+            Some(ValidationErrorKind::UnexpectedParameters {
+                tag: tag.to_string(),
+            })
+        }
+        ParameterKind::Byte | ParameterKind::Icon | ParameterKind::HexBytes
+            if parameters.is_empty() =>
+        {
+            Some(ValidationErrorKind::MissingParameter {
+                tag: tag.to_string(),
+                expected: expected.expected(),
+            })
+        }
+        // Tags with sized values may have none of them.
+        ParameterKind::SizedValues if parameters.is_empty() => None,
+        _ if !expected.accepts(parameters) => Some(ValidationErrorKind::InvalidParameter {
+            tag: tag.to_string(),
+            parameter: parameters.to_string(),
+            expected: expected.expected(),
+        }),
+        _ => None,
+    }
+}
+
+/// Checks a string for everything the encoder can't encode as written: unclosed or unknown
+/// tags, tags with missing or invalid parameters, and characters without a byte conversion.
+/// Unlike encoding, every problem of the string is reported.
+pub fn validate(string: &str) -> Vec<ValidationError> {
+    validate_for(string, Language::current())
+}
+
+pub fn validate_for(string: &str, language: Language) -> Vec<ValidationError> {
+    let mut errors = vec![];
+    let mut chars = string.char_indices().peekable();
+    let mut u16_buffer = [0u16; 2];
+
+    while let Some((idx, char)) = chars.next() {
+        match char {
+            TAG_PREFIX if chars.peek().is_some_and(|(_, ch)| *ch == TAG_START) => {
+                let Some(tag_end) = string[idx..].find(TAG_END).map(|end| idx + end) else {
+                    errors.push(ValidationError::at(
+                        string,
+                        idx,
+                        ValidationErrorKind::UnclosedTag,
+                    ));
+                    break;
+                };
+
+                // Skip past the tag, which is parsed the same way as in the encoder.
+                while chars
+                    .next_if(|(char_idx, _)| *char_idx <= tag_end)
+                    .is_some()
+                {}
+
+                let (tag, parameters) = split_tag(&string[idx + 2..tag_end]);
+
+                if let Some(kind) = check_tag(tag, parameters) {
+                    errors.push(ValidationError::at(string, idx, kind));
+                }
+            }
+
+            '\n' | '\0' => {}
+
+            _ => {
+                let is_mapped = char
+                    .encode_utf16(&mut u16_buffer)
+                    .iter()
+                    .all(|short| ConversionTable::rev_lookup_for(*short, language) > 0);
+
+                if !is_mapped {
+                    errors.push(ValidationError::at(
+                        string,
+                        idx,
+                        ValidationErrorKind::UnmappedCharacter(char),
+                    ));
+                }
+            }
+        }
+    }
+
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{language::Language, tests::example_strings_for_encoding};
+
+    use super::{validate_for, ValidationError, ValidationErrorKind};
+
+    #[test]
+    fn example_strings_are_valid() {
+        for (_, string) in example_strings_for_encoding() {
+            let language = if string.is_ascii() {
+                Language::English
+            } else {
+                Language::Japanese
+            };
+            assert_eq!(validate_for(string, language), vec![], "{}", string);
+        }
+    }
+
+    #[test]
+    fn every_problem_is_reported() {
+        let string = "Hello, ${playr}!\nYou have ${number} gil and ${name-npc: 1}\n${item-plural: 0[3]} ${icon: fire}${prompt}";
+        let errors = validate_for(string, Language::English);
+
+        assert_eq!(
+            errors
+                .iter()
+                .map(|error| (error.offset, error.line, error.column))
+                .collect::<Vec<_>>(),
+            vec![(7, 1, 8), (26, 2, 10), (44, 2, 28), (59, 3, 1)]
+        );
+        assert_eq!(
+            errors[0].kind,
+            ValidationErrorKind::UnknownTag("playr".to_string())
+        );
+        assert!(matches!(
+            errors[1].kind,
+            ValidationErrorKind::MissingParameter { .. }
+        ));
+        assert!(matches!(
+            errors[2].kind,
+            ValidationErrorKind::UnexpectedParameters { .. }
+        ));
+        assert!(matches!(
+            &errors[3].kind,
+            ValidationErrorKind::InvalidParameter { parameter, .. } if parameter == "0[3]"
+        ));
+    }
+
+    #[test]
+    fn parameters() {
+        for valid in [
+            "${number: 255}",
+            "${lettercase: 1}",
+            "${icon: 0x1F}",
+            "${unknown: 0x0A1B}",
+            "${item-count}",
+            "${item-article: 65535[2], 1[1]}",
+        ] {
+            assert_eq!(validate_for(valid, Language::English), vec![], "{}", valid);
+        }
+
+        for invalid in [
+            "${number: 256}",
+            "${icon: flame}",
+            "${unknown: 0xA}",
+            "${unknown: 1B}",
+            "${item-article: 256[1]}",
+            "${item-article: 1}",
+        ] {
+            assert_eq!(
+                validate_for(invalid, Language::English).len(),
+                1,
+                "{}",
+                invalid
+            );
+        }
+    }
+
+    #[test]
+    fn unclosed_tags_and_unmapped_characters() {
+        assert_eq!(
+            validate_for("A 🙂 and $ sign\n${prompt", Language::English),
+            vec![
+                ValidationError {
+                    offset: 2,
+                    line: 1,
+                    column: 3,
+                    kind: ValidationErrorKind::UnmappedCharacter('🙂'),
+                },
+                ValidationError {
+                    offset: 15,
+                    line: 2,
+                    column: 1,
+                    kind: ValidationErrorKind::UnclosedTag,
+                },
+            ]
+        );
+    }
+}
@@ -1,15 +1,72 @@
 use std::{
+    any::Any,
     fs::{self, File},
     io::{BufReader, BufWriter, Write},
-    path::PathBuf,
+    path::{Path, PathBuf},
     sync::Arc,
 };
 
 use anyhow::{anyhow, Result};
-use dats::{base::Dat, context::DatContext, dat_format::DatFormat};
-use serde::Serialize;
+use dats::{
+    base::{Dat, DatId},
+    context::DatContext,
+    dat_format::{DatFormat, RoundTripMismatch},
+    entries::DatEntries,
+    formats::{
+        dialog::Dialog, dmsg2_string_table::Dmsg2StringTable, dmsg3_string_table::Dmsg3StringTable,
+        entity_names::EntityNames, item_info::ItemInfoTable, quest_info::QuestInfoTable,
+        status_info::StatusInfoTable, xistring_table::XiStringTable,
+    },
+    image::ImageFiles,
+    strings::{DatStrings, DatStringsMut},
+    views::{item_table::ItemTableView, DatView},
+};
+use encoding::validation::validate;
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{
+    dat_descriptor::{DatDescriptor, DatUsage},
+    diff::DatDiff,
+    item_table::{read_item_table, write_item_table},
+    merge::DatMerge,
+    raw_data_format::RawDataFormat,
+    translation::{apply_translations, TranslationUnit},
+    validation::StringProblem,
+};
+
+fn write_raw_data<T: Serialize>(raw_data_path: &PathBuf, data: &T) -> Result<()> {
+    let format = raw_data_format(raw_data_path)?;
+    fs::create_dir_all(&raw_data_path.parent().unwrap())?;
+    let file = File::create(raw_data_path).map_err(|err| {
+        anyhow!(
+            "Could not create at file {}: {}",
+            raw_data_path.display(),
+            err
+        )
+    })?;
+
+    // Images are written next to the raw data file, so they can be edited with other tools.
+    ImageFiles::for_file(raw_data_path).scoped(|| format.write(BufWriter::new(file), data))
+}
+
+fn read_raw_data<T: DeserializeOwned>(raw_data_path: &PathBuf) -> Result<T> {
+    let format = raw_data_format(raw_data_path)?;
+    let raw_data_file = File::open(raw_data_path)
+        .map_err(|err| anyhow!("Could open file at {}: {}", raw_data_path.display(), err))?;
 
-use crate::dat_descriptor::DatUsage;
+    ImageFiles::for_file(raw_data_path)
+        .scoped(|| format.read(BufReader::new(raw_data_file)))
+        .map_err(|err| anyhow!("Could not read {}: {}", raw_data_path.display(), err))
+}
+
+fn raw_data_format(raw_data_path: &Path) -> Result<RawDataFormat> {
+    RawDataFormat::from_path(raw_data_path).ok_or_else(|| {
+        anyhow!(
+            "Unsupported raw data format for {}, expected .yml, .json or .ron.",
+            raw_data_path.display()
+        )
+    })
+}
 
 pub(crate) struct DatToYamlConverter {
     pub dat_context: Arc<DatContext>,
@@ -17,22 +74,26 @@ pub(crate) struct DatToYamlConverter {
 }
 
 impl DatUsage for DatToYamlConverter {
-    fn use_dat<T: DatFormat + Serialize + for<'b> serde::Deserialize<'b>>(
+    fn use_dat<T: DatFormat + Serialize + for<'b> serde::Deserialize<'b> + 'static>(
         self,
         dat: Dat<T>,
     ) -> Result<PathBuf> {
         let data = self.dat_context.get_data_from_dat(&dat)?;
+        write_raw_data(&self.raw_data_path, &data.dat)?;
 
-        fs::create_dir_all(&self.raw_data_path.parent().unwrap())?;
-        let file = File::create(&self.raw_data_path).map_err(|err| {
-            anyhow!(
-                "Could not create at file {}: {}",
-                self.raw_data_path.display(),
-                err
-            )
-        })?;
+        Ok(data.path)
+    }
 
-        serde_yaml::to_writer(BufWriter::new(file), &data.dat)?;
+    fn use_view<V: DatView + Serialize + for<'b> serde::Deserialize<'b>>(
+        self,
+        dat: Dat<V::Base>,
+    ) -> Result<PathBuf>
+    where
+        V::Base: Serialize + for<'b> serde::Deserialize<'b> + 'static,
+    {
+        let data = self.dat_context.get_data_from_dat(&dat)?;
+        let view = V::from_base(&data.dat, &self.dat_context)?;
+        write_raw_data(&self.raw_data_path, &view)?;
 
         Ok(data.path)
     }
@@ -42,13 +103,48 @@ pub(crate) struct YamlToDatConverter {
     pub dat_context: Arc<DatContext>,
     pub raw_data_path: PathBuf,
     pub dat_root_path: PathBuf,
+    /// Files of views which are applied on top of the DAT's own file.
+    pub view_paths: Vec<(DatDescriptor, PathBuf)>,
 }
 
 impl DatUsage for YamlToDatConverter {
-    fn use_dat<T: DatFormat + Serialize + for<'a> serde::Deserialize<'a>>(
+    fn use_dat<T: DatFormat + Serialize + for<'a> serde::Deserialize<'a> + 'static>(
         self,
         dat: Dat<T>,
     ) -> Result<PathBuf> {
+        // With only views of the DAT being edited, they're applied onto the original DAT.
+        let mut data: T = if self.view_paths.is_empty() || self.raw_data_path.exists() {
+            read_raw_data(&self.raw_data_path)?
+        } else {
+            self.dat_context.get_data_from_dat(&dat)?.dat
+        };
+
+        for (view, view_path) in &self.view_paths {
+            view.apply_view(&self.dat_context, view_path.clone(), &mut data)?;
+        }
+
+        self.write_dat(&dat, &data)
+    }
+
+    fn use_view<V: DatView + Serialize + for<'a> serde::Deserialize<'a>>(
+        self,
+        dat: Dat<V::Base>,
+    ) -> Result<PathBuf>
+    where
+        V::Base: Serialize + for<'a> serde::Deserialize<'a> + 'static,
+    {
+        // Views with a file of their own base DAT are redirected to it, so this view is
+        // the only file of its DAT, and gets applied onto the original one.
+        let mut data = self.dat_context.get_data_from_dat(&dat)?.dat;
+        let view: V = read_raw_data(&self.raw_data_path)?;
+        view.apply_to(&mut data)?;
+
+        self.write_dat(&dat, &data)
+    }
+}
+
+impl YamlToDatConverter {
+    fn write_dat<T: DatFormat>(&self, dat: &Dat<T>, data: &T) -> Result<PathBuf> {
         let relative_dat_path = dat.get_relative_dat_path(&self.dat_context)?;
         let dat_path = self.dat_root_path.join(relative_dat_path);
 
@@ -56,17 +152,582 @@ impl DatUsage for YamlToDatConverter {
         let mut dat_file = File::create(&dat_path)
             .map_err(|err| anyhow!("Could not create file at {}: {}", dat_path.display(), err))?;
 
-        let raw_data_file = File::open(&self.raw_data_path).map_err(|err| {
-            anyhow!(
-                "Could open file at {}: {}",
-                self.raw_data_path.display(),
-                err
-            )
-        })?;
-        let data: T = serde_yaml::from_reader(BufReader::new(raw_data_file))?;
-
         dat_file.write_all(&data.to_bytes()?)?;
 
         Ok(dat_path)
     }
 }
+
+/// Applies a view file onto the already loaded data of the DAT it's part of.
+pub(crate) struct ViewApplier<'a> {
+    pub dat_context: &'a DatContext,
+    pub raw_data_path: PathBuf,
+    pub base: &'a mut dyn Any,
+}
+
+impl DatUsage for ViewApplier<'_> {
+    fn use_dat<T: DatFormat + Serialize + for<'a> serde::Deserialize<'a> + 'static>(
+        self,
+        _dat: Dat<T>,
+    ) -> Result<PathBuf> {
+        Err(anyhow!("Only views can be applied onto a DAT."))
+    }
+
+    fn use_view<V: DatView + Serialize + for<'a> serde::Deserialize<'a>>(
+        self,
+        dat: Dat<V::Base>,
+    ) -> Result<PathBuf>
+    where
+        V::Base: Serialize + for<'a> serde::Deserialize<'a> + 'static,
+    {
+        let base = self
+            .base
+            .downcast_mut::<V::Base>()
+            .ok_or_else(|| anyhow!("View doesn't match the DAT it's applied to."))?;
+
+        // Only the records edited in the view are applied, to keep the edits of the base.
+        let original = self.dat_context.get_data_from_dat(&dat)?.dat;
+        let original = V::from_base(&original, self.dat_context)?;
+        let edited_base = V::from_base(base, self.dat_context)?;
+
+        let mut view: V = read_raw_data(&self.raw_data_path)?;
+        view.retain_edits(&original, &edited_base)
+            .map_err(|err| anyhow!("{}: {}", self.raw_data_path.display(), err))?;
+        view.apply_to(base)?;
+
+        Ok(self.raw_data_path)
+    }
+}
+
+pub(crate) struct RoundTripChecker {
+    pub dat_context: Arc<DatContext>,
+}
+
+impl DatUsage for RoundTripChecker {
+    fn use_dat<T: DatFormat + Serialize + for<'a> serde::Deserialize<'a> + 'static>(
+        self,
+        dat: Dat<T>,
+    ) -> Result<PathBuf> {
+        self.dat_context.get_data_from_dat_checked(&dat)?;
+
+        Ok(self.dat_context.get_dat_path(&dat)?)
+    }
+
+    fn use_view<V: DatView + Serialize + for<'a> serde::Deserialize<'a>>(
+        self,
+        dat: Dat<V::Base>,
+    ) -> Result<PathBuf>
+    where
+        V::Base: Serialize + for<'a> serde::Deserialize<'a> + 'static,
+    {
+        let dat_path = self.dat_context.get_dat_path(&dat)?;
+        let mut data = self.dat_context.get_data_from_dat_checked(&dat)?;
+        let original_bytes = data.to_bytes()?;
+
+        // The view has to survive its own file format, and leave the DAT unchanged.
+        let view = V::from_base(&data, &self.dat_context)?;
+        let view: V = serde_yaml::from_str(&serde_yaml::to_string(&view)?)?;
+        view.apply_to(&mut data)?;
+
+        let re_encoded_bytes = data.to_bytes()?;
+        if re_encoded_bytes != original_bytes {
+            return Err(
+                RoundTripMismatch::new(dat_path, &original_bytes, &re_encoded_bytes).into(),
+            );
+        }
+
+        Ok(dat_path)
+    }
+}
+
+/// Checks that the strings of a raw data file can be encoded, collecting every problem.
+pub(crate) struct StringValidator<'a> {
+    pub raw_data_path: PathBuf,
+    pub problems: &'a mut Vec<StringProblem>,
+}
+
+impl DatUsage for StringValidator<'_> {
+    fn use_dat<T: DatFormat + Serialize + for<'a> serde::Deserialize<'a> + 'static>(
+        self,
+        _dat: Dat<T>,
+    ) -> Result<PathBuf> {
+        let data: T = read_raw_data(&self.raw_data_path)?;
+        for (entry_id, string) in dat_strings(&data).unwrap_or_default() {
+            self.problems
+                .extend(validate(string).into_iter().map(|error| StringProblem {
+                    raw_data_path: self.raw_data_path.clone(),
+                    entry_id,
+                    error,
+                }));
+        }
+
+        Ok(self.raw_data_path)
+    }
+
+    fn use_view<V: DatView + Serialize + for<'a> serde::Deserialize<'a>>(
+        self,
+        _dat: Dat<V::Base>,
+    ) -> Result<PathBuf>
+    where
+        V::Base: Serialize + for<'a> serde::Deserialize<'a> + 'static,
+    {
+        // The names and descriptions in views are only for reference, and are never written back,
+        // so they're validated with the DATs they come from.
+        Ok(self.raw_data_path)
+    }
+}
+
+/// Collects the decoded strings of a DAT.
+pub(crate) struct StringCollector<'a> {
+    pub dat_context: &'a DatContext,
+    pub strings: &'a mut Vec<(u32, String)>,
+}
+
+/// Runs `$strings_of` with the type of `$data` if it's one of the formats with strings, so that
+/// validation, search and translation all cover the same formats.
+macro_rules! with_string_format {
+    ($data:expr, $strings_of:ident) => {
+        if $data.is::<Dialog>() {
+            $strings_of::<Dialog>($data)
+        } else if $data.is::<Dmsg2StringTable>() {
+            $strings_of::<Dmsg2StringTable>($data)
+        } else if $data.is::<Dmsg3StringTable>() {
+            $strings_of::<Dmsg3StringTable>($data)
+        } else if $data.is::<XiStringTable>() {
+            $strings_of::<XiStringTable>($data)
+        } else if $data.is::<EntityNames>() {
+            $strings_of::<EntityNames>($data)
+        } else if $data.is::<StatusInfoTable>() {
+            $strings_of::<StatusInfoTable>($data)
+        } else if $data.is::<ItemInfoTable>() {
+            $strings_of::<ItemInfoTable>($data)
+        } else if $data.is::<QuestInfoTable>() {
+            $strings_of::<QuestInfoTable>($data)
+        } else {
+            None
+        }
+    };
+}
+
+/// The strings of the given data, if its format has any.
+fn dat_strings(data: &dyn Any) -> Option<Vec<(u32, &str)>> {
+    fn strings_of<T: DatStrings + 'static>(data: &dyn Any) -> Option<Vec<(u32, &str)>> {
+        data.downcast_ref::<T>().map(DatStrings::strings)
+    }
+
+    with_string_format!(data, strings_of)
+}
+
+/// The same strings as [`dat_strings`], to replace them in place.
+fn dat_strings_mut(data: &mut dyn Any) -> Option<Vec<(u32, &mut String)>> {
+    fn strings_mut_of<T: DatStringsMut + 'static>(
+        data: &mut dyn Any,
+    ) -> Option<Vec<(u32, &mut String)>> {
+        data.downcast_mut::<T>().map(DatStringsMut::strings_mut)
+    }
+
+    with_string_format!(data, strings_mut_of)
+}
+
+impl DatUsage for StringCollector<'_> {
+    fn use_dat<T: DatFormat + Serialize + for<'a> serde::Deserialize<'a> + 'static>(
+        self,
+        dat: Dat<T>,
+    ) -> Result<PathBuf> {
+        let data = self.dat_context.get_data_from_dat(&dat)?;
+
+        let strings = dat_strings(&data.dat).unwrap_or_default();
+        self.strings.extend(
+            strings
+                .into_iter()
+                .map(|(entry_id, string)| (entry_id, string.to_string())),
+        );
+
+        Ok(data.path)
+    }
+
+    fn use_view<V: DatView + Serialize + for<'a> serde::Deserialize<'a>>(
+        self,
+        dat: Dat<V::Base>,
+    ) -> Result<PathBuf>
+    where
+        V::Base: Serialize + for<'a> serde::Deserialize<'a> + 'static,
+    {
+        // The text of views is joined from other DATs, which are searched on their own.
+        Ok(self.dat_context.get_dat_path(&dat)?)
+    }
+}
+
+/// Finds the path of a DAT in the install, without loading it.
+pub(crate) struct DatPathResolver<'a> {
+    pub dat_context: &'a DatContext,
+}
+
+impl DatUsage for DatPathResolver<'_> {
+    fn use_dat<T: DatFormat + Serialize + for<'a> serde::Deserialize<'a> + 'static>(
+        self,
+        dat: Dat<T>,
+    ) -> Result<PathBuf> {
+        Ok(self.dat_context.get_dat_path(&dat)?)
+    }
+
+    fn use_view<V: DatView + Serialize + for<'a> serde::Deserialize<'a>>(
+        self,
+        dat: Dat<V::Base>,
+    ) -> Result<PathBuf>
+    where
+        V::Base: Serialize + for<'a> serde::Deserialize<'a> + 'static,
+    {
+        Ok(self.dat_context.get_dat_path(&dat)?)
+    }
+}
+
+/// Looks up the ID of a DAT, whether or not it's part of the install.
+pub(crate) struct DatIdResolver<'a> {
+    pub dat_id: &'a mut Option<DatId>,
+}
+
+impl DatUsage for DatIdResolver<'_> {
+    fn use_dat<T: DatFormat + Serialize + for<'a> serde::Deserialize<'a> + 'static>(
+        self,
+        dat: Dat<T>,
+    ) -> Result<PathBuf> {
+        *self.dat_id = Some(dat.into());
+
+        // Only the ID is looked up, as the DAT doesn't have a path without being installed.
+        Ok(PathBuf::new())
+    }
+
+    fn use_view<V: DatView + Serialize + for<'a> serde::Deserialize<'a>>(
+        self,
+        dat: Dat<V::Base>,
+    ) -> Result<PathBuf>
+    where
+        V::Base: Serialize + for<'a> serde::Deserialize<'a> + 'static,
+    {
+        *self.dat_id = Some(dat.into());
+
+        Ok(PathBuf::new())
+    }
+}
+
+/// Compares two files of a DAT's format entry by entry.
+pub(crate) struct DatDiffer<'a> {
+    pub old_path: PathBuf,
+    pub new_path: PathBuf,
+    pub diff: &'a mut DatDiff,
+}
+
+fn diff_of<T: DatEntries + 'static>(old: &dyn Any, new: &dyn Any) -> Option<Result<DatDiff>> {
+    Some(DatDiff::between(
+        old.downcast_ref::<T>()?,
+        new.downcast_ref::<T>()?,
+    ))
+}
+
+/// The differences between the given data, if its format is made up of entries.
+fn entry_diff(old: &dyn Any, new: &dyn Any) -> Option<Result<DatDiff>> {
+    diff_of::<Dialog>(old, new)
+        .or_else(|| diff_of::<Dmsg2StringTable>(old, new))
+        .or_else(|| diff_of::<Dmsg3StringTable>(old, new))
+        .or_else(|| diff_of::<XiStringTable>(old, new))
+        .or_else(|| diff_of::<EntityNames>(old, new))
+        .or_else(|| diff_of::<StatusInfoTable>(old, new))
+        .or_else(|| diff_of::<ItemInfoTable>(old, new))
+        .or_else(|| diff_of::<QuestInfoTable>(old, new))
+}
+
+impl DatUsage for DatDiffer<'_> {
+    fn use_dat<T: DatFormat + Serialize + for<'a> serde::Deserialize<'a> + 'static>(
+        self,
+        _dat: Dat<T>,
+    ) -> Result<PathBuf> {
+        let load = |path: &PathBuf| {
+            T::from_path(path).map_err(|err| anyhow!("Could not load {}: {}", path.display(), err))
+        };
+        let old = load(&self.old_path)?;
+        let new = load(&self.new_path)?;
+
+        *self.diff = entry_diff(&old, &new)
+            .ok_or_else(|| anyhow!("The entries of this DAT can't be compared yet."))??;
+
+        Ok(self.new_path)
+    }
+
+    fn use_view<V: DatView + Serialize + for<'a> serde::Deserialize<'a>>(
+        self,
+        _dat: Dat<V::Base>,
+    ) -> Result<PathBuf>
+    where
+        V::Base: Serialize + for<'a> serde::Deserialize<'a> + 'static,
+    {
+        Err(anyhow!(
+            "Views are compared through the DAT they're part of."
+        ))
+    }
+}
+
+/// Merges our edits of a DAT with the changes between two versions of the DAT.
+pub(crate) struct DatMerger<'a> {
+    pub base_path: PathBuf,
+    pub ours_path: PathBuf,
+    pub theirs_path: PathBuf,
+    pub out_path: PathBuf,
+    pub merge: &'a mut Option<DatMerge>,
+}
+
+fn merge_of<T: DatEntries + Serialize + 'static>(
+    base: &dyn Any,
+    ours: &dyn Any,
+    theirs: &dyn Any,
+) -> Option<Result<DatMerge>> {
+    Some(DatMerge::between(
+        base.downcast_ref::<T>()?,
+        ours.downcast_ref::<T>()?,
+        theirs.downcast_ref::<T>()?,
+    ))
+}
+
+/// The three-way merge of the given data, if its format is made up of entries.
+fn entry_merge(base: &dyn Any, ours: &dyn Any, theirs: &dyn Any) -> Option<Result<DatMerge>> {
+    merge_of::<Dialog>(base, ours, theirs)
+        .or_else(|| merge_of::<Dmsg2StringTable>(base, ours, theirs))
+        .or_else(|| merge_of::<Dmsg3StringTable>(base, ours, theirs))
+        .or_else(|| merge_of::<XiStringTable>(base, ours, theirs))
+        .or_else(|| merge_of::<EntityNames>(base, ours, theirs))
+        .or_else(|| merge_of::<StatusInfoTable>(base, ours, theirs))
+        .or_else(|| merge_of::<ItemInfoTable>(base, ours, theirs))
+        .or_else(|| merge_of::<QuestInfoTable>(base, ours, theirs))
+}
+
+impl DatUsage for DatMerger<'_> {
+    fn use_dat<T: DatFormat + Serialize + for<'a> serde::Deserialize<'a> + 'static>(
+        self,
+        _dat: Dat<T>,
+    ) -> Result<PathBuf> {
+        let load = |path: &PathBuf| {
+            T::from_path(path).map_err(|err| anyhow!("Could not load {}: {}", path.display(), err))
+        };
+        let base = load(&self.base_path)?;
+        let theirs = load(&self.theirs_path)?;
+        let ours: T = read_raw_data(&self.ours_path)?;
+
+        let merge = entry_merge(&base, &ours, &theirs)
+            .ok_or_else(|| anyhow!("The entries of this DAT can't be merged yet."))??;
+
+        // Without conflicts the merged data is written like any other DAT, e.g. with its
+        // images as separate files. Conflicting icons are kept inline.
+        if merge.has_conflicts() {
+            write_raw_data(&self.out_path, &merge.merged)?;
+        } else {
+            let merged: T = serde_yaml::from_value(merge.merged.clone())?;
+            write_raw_data(&self.out_path, &merged)?;
+        }
+        *self.merge = Some(merge);
+
+        Ok(self.out_path)
+    }
+
+    fn use_view<V: DatView + Serialize + for<'a> serde::Deserialize<'a>>(
+        self,
+        _dat: Dat<V::Base>,
+    ) -> Result<PathBuf>
+    where
+        V::Base: Serialize + for<'a> serde::Deserialize<'a> + 'static,
+    {
+        Err(anyhow!("Views are merged through the DAT they're part of."))
+    }
+}
+
+/// Loads the edited raw data file of a DAT if there is one, and the DAT itself otherwise.
+fn load_edited<T: DatFormat + DeserializeOwned>(
+    dat_context: &DatContext,
+    dat: &Dat<T>,
+    raw_data_path: Option<&PathBuf>,
+) -> Result<T> {
+    match raw_data_path {
+        Some(raw_data_path) if raw_data_path.exists() => read_raw_data(raw_data_path),
+        _ => Ok(dat_context.get_data_from_dat(dat)?.dat),
+    }
+}
+
+fn item_table(data: &mut dyn Any) -> Result<&mut ItemInfoTable> {
+    data.downcast_mut::<ItemInfoTable>()
+        .ok_or_else(|| anyhow!("Only item DATs can be edited as a table."))
+}
+
+/// Writes the items of a DAT as rows of a CSV or TSV file.
+pub(crate) struct ItemTableExporter<'a> {
+    pub dat_context: &'a DatContext,
+    pub raw_data_path: Option<PathBuf>,
+    pub table_path: PathBuf,
+}
+
+impl DatUsage for ItemTableExporter<'_> {
+    fn use_dat<T: DatFormat + Serialize + for<'a> serde::Deserialize<'a> + 'static>(
+        self,
+        dat: Dat<T>,
+    ) -> Result<PathBuf> {
+        let mut data = load_edited(self.dat_context, &dat, self.raw_data_path.as_ref())?;
+        let view = ItemTableView::from_table(item_table(&mut data)?);
+        write_item_table(&self.table_path, &view)?;
+
+        Ok(self.table_path)
+    }
+
+    fn use_view<V: DatView + Serialize + for<'a> serde::Deserialize<'a>>(
+        self,
+        _dat: Dat<V::Base>,
+    ) -> Result<PathBuf>
+    where
+        V::Base: Serialize + for<'a> serde::Deserialize<'a> + 'static,
+    {
+        Err(anyhow!("Only item DATs can be edited as a table."))
+    }
+}
+
+/// Applies the rows of a CSV or TSV file to the items of a DAT, and writes them as raw data.
+pub(crate) struct ItemTableImporter<'a> {
+    pub dat_context: &'a DatContext,
+    pub raw_data_path: PathBuf,
+    pub table_path: PathBuf,
+}
+
+impl DatUsage for ItemTableImporter<'_> {
+    fn use_dat<T: DatFormat + Serialize + for<'a> serde::Deserialize<'a> + 'static>(
+        self,
+        dat: Dat<T>,
+    ) -> Result<PathBuf> {
+        let mut data = load_edited(self.dat_context, &dat, Some(&self.raw_data_path))?;
+        read_item_table(&self.table_path)?.apply_to(item_table(&mut data)?)?;
+        write_raw_data(&self.raw_data_path, &data)?;
+
+        Ok(self.raw_data_path)
+    }
+
+    fn use_view<V: DatView + Serialize + for<'a> serde::Deserialize<'a>>(
+        self,
+        _dat: Dat<V::Base>,
+    ) -> Result<PathBuf>
+    where
+        V::Base: Serialize + for<'a> serde::Deserialize<'a> + 'static,
+    {
+        Err(anyhow!("Only item DATs can be edited as a table."))
+    }
+}
+
+/// Collects the translatable strings of a DAT as translation units.
+pub(crate) struct TranslationExporter<'a> {
+    pub dat_context: &'a DatContext,
+    pub dat_descriptor: DatDescriptor,
+    pub raw_data_path: Option<PathBuf>,
+    pub units: &'a mut Vec<TranslationUnit>,
+}
+
+impl DatUsage for TranslationExporter<'_> {
+    fn use_dat<T: DatFormat + Serialize + for<'a> serde::Deserialize<'a> + 'static>(
+        self,
+        dat: Dat<T>,
+    ) -> Result<PathBuf> {
+        let data = load_edited(self.dat_context, &dat, self.raw_data_path.as_ref())?;
+        if let Some(strings) = dat_strings(&data) {
+            self.units
+                .extend(TranslationUnit::from_strings(self.dat_descriptor, &strings));
+        }
+
+        Ok(self.dat_context.get_dat_path(&dat)?)
+    }
+
+    fn use_view<V: DatView + Serialize + for<'a> serde::Deserialize<'a>>(
+        self,
+        dat: Dat<V::Base>,
+    ) -> Result<PathBuf>
+    where
+        V::Base: Serialize + for<'a> serde::Deserialize<'a> + 'static,
+    {
+        // The text of views is joined from other DATs, which are translated on their own.
+        Ok(self.dat_context.get_dat_path(&dat)?)
+    }
+}
+
+/// Replaces the strings of a DAT with their translations, and writes them as raw data.
+pub(crate) struct TranslationImporter<'a> {
+    pub dat_context: &'a DatContext,
+    pub dat_descriptor: DatDescriptor,
+    pub raw_data_path: PathBuf,
+    pub units: &'a [TranslationUnit],
+    pub count: &'a mut usize,
+}
+
+impl DatUsage for TranslationImporter<'_> {
+    fn use_dat<T: DatFormat + Serialize + for<'a> serde::Deserialize<'a> + 'static>(
+        self,
+        dat: Dat<T>,
+    ) -> Result<PathBuf> {
+        let mut data = load_edited(self.dat_context, &dat, Some(&self.raw_data_path))?;
+        let strings = dat_strings_mut(&mut data)
+            .ok_or_else(|| anyhow!("{:?} has no translatable strings.", self.dat_descriptor))?;
+
+        *self.count = apply_translations(self.dat_descriptor, strings, self.units)?;
+        write_raw_data(&self.raw_data_path, &data)?;
+
+        Ok(self.raw_data_path)
+    }
+
+    fn use_view<V: DatView + Serialize + for<'a> serde::Deserialize<'a>>(
+        self,
+        _dat: Dat<V::Base>,
+    ) -> Result<PathBuf>
+    where
+        V::Base: Serialize + for<'a> serde::Deserialize<'a> + 'static,
+    {
+        Err(anyhow!(
+            "Views are translated through the DATs they're made of."
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::any::Any;
+
+    use dats::formats::{
+        dialog::Dialog, dmsg2_string_table::Dmsg2StringTable, dmsg3_string_table::Dmsg3StringTable,
+        entity_names::EntityNames, item_info::ItemInfoTable, quest_info::QuestInfoTable,
+        status_info::StatusInfoTable, xistring_table::XiStringTable,
+    };
+
+    use super::{dat_strings, dat_strings_mut};
+
+    #[test]
+    fn every_string_format_is_covered() {
+        let mut fixtures: Vec<Box<dyn Any>> = vec![
+            Box::new(Dialog::fixture()),
+            Box::new(Dmsg2StringTable::fixture()),
+            Box::new(Dmsg3StringTable::fixture()),
+            Box::new(XiStringTable::fixture()),
+            Box::new(EntityNames::fixture()),
+            Box::new(StatusInfoTable::fixture()),
+            Box::new(ItemInfoTable::fixture()),
+            Box::new(QuestInfoTable::fixture()),
+        ];
+
+        for fixture in fixtures.iter_mut() {
+            let strings: Vec<(u32, String)> = dat_strings(fixture.as_ref())
+                .unwrap()
+                .into_iter()
+                .map(|(id, string)| (id, string.to_string()))
+                .collect();
+            assert!(!strings.is_empty());
+
+            let strings_mut: Vec<(u32, String)> = dat_strings_mut(fixture.as_mut())
+                .unwrap()
+                .into_iter()
+                .map(|(id, string)| (id, string.clone()))
+                .collect();
+            assert_eq!(strings, strings_mut);
+        }
+
+        assert!(dat_strings(&0u32).is_none());
+    }
+}
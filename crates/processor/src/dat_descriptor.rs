@@ -1,15 +1,34 @@
 use anyhow::{anyhow, Result};
-use std::{path::PathBuf, sync::Arc};
+use std::{
+    any::Any,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 use dats::{
-    base::{Dat, ZoneId},
+    base::{Dat, DatByZone, DatId, ZoneId},
     context::DatContext,
     dat_format::DatFormat,
+    formats::event::EventFile,
     id_mapping::DatIdMapping,
+    views::{ability_info::AbilityInfoView, spell_info::SpellInfoView, DatView},
 };
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 
-use crate::converters::{DatToYamlConverter, YamlToDatConverter};
+use crate::{
+    converters::{
+        DatDiffer, DatIdResolver, DatMerger, DatPathResolver, DatToYamlConverter,
+        ItemTableExporter, ItemTableImporter, RoundTripChecker, StringCollector, StringValidator,
+        TranslationExporter, TranslationImporter, ViewApplier, YamlToDatConverter,
+    },
+    diff::DatDiff,
+    merge::DatMerge,
+    raw_data_format::RawDataFormat,
+    search::SearchMatch,
+    translation::TranslationUnit,
+    validation::StringProblem,
+};
 
 #[derive(
     Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, specta::Type, Serialize, Deserialize,
@@ -18,6 +37,10 @@ use crate::converters::{DatToYamlConverter, YamlToDatConverter};
 pub enum DatDescriptor {
     DataMenu,
 
+    // Views on the menu DAT
+    SpellInfo,
+    AbilityInfo,
+
     // String tables
     AbilityNames,
     AbilityDescriptions,
@@ -72,46 +95,471 @@ pub enum DatDescriptor {
     SystemMessages4,
     UnityDialogs,
 
+    // Quest logs by index
+    QuestInfo(u16),
+
     // Dats by zone
     EntityNames(ZoneId),
     Dialog(ZoneId),
     Dialog2(ZoneId),
+    Events(ZoneId),
 }
 
 pub trait DatUsage {
-    fn use_dat<T: DatFormat + Serialize + for<'a> serde::Deserialize<'a>>(
+    fn use_dat<T: DatFormat + Serialize + for<'a> serde::Deserialize<'a> + 'static>(
         self,
         dat: Dat<T>,
     ) -> Result<PathBuf>;
+
+    /// Uses only the part of the given DAT which the view covers.
+    fn use_view<V: DatView + Serialize + for<'a> serde::Deserialize<'a>>(
+        self,
+        dat: Dat<V::Base>,
+    ) -> Result<PathBuf>
+    where
+        V::Base: Serialize + for<'a> serde::Deserialize<'a> + 'static;
 }
 
 impl DatDescriptor {
+    /// Converts the DAT of this descriptor into a raw data file in the given format.
     pub fn dat_to_yaml(
         &self,
         dat_context: Arc<DatContext>,
         raw_data_root_path: PathBuf,
+        format: RawDataFormat,
     ) -> Result<PathBuf> {
-        let data_path = raw_data_root_path.join(self.get_relative_path(&dat_context)? + ".yml");
+        let data_path = format.path_in(&raw_data_root_path, &self.get_relative_path(&dat_context)?);
         self.convert_with(DatToYamlConverter {
             dat_context,
             raw_data_path: data_path,
         })
     }
 
+    /// Generates the DAT of this descriptor. Views are applied together with the file of the
+    /// DAT they're part of, so that editing any of them leads to the same DAT.
+    /// The raw data files may be in any of the supported formats.
     pub fn yaml_to_dat(
         &self,
         dat_context: Arc<DatContext>,
         raw_data_root_path: PathBuf,
         dat_root_path: PathBuf,
     ) -> Result<PathBuf> {
-        let raw_data_path = raw_data_root_path.join(self.get_relative_path(&dat_context)? + ".yml");
-        self.convert_with(YamlToDatConverter {
+        let dat_descriptor = self.view_base().unwrap_or(*self);
+        let raw_data_path =
+            match dat_descriptor.find_raw_data_path(&dat_context, &raw_data_root_path)? {
+                Some(raw_data_path) => raw_data_path,
+                None => RawDataFormat::default().path_in(
+                    &raw_data_root_path,
+                    &dat_descriptor.get_relative_path(&dat_context)?,
+                ),
+            };
+
+        let mut view_paths = vec![];
+        for view in dat_descriptor.views() {
+            if let Some(view_path) = view.find_raw_data_path(&dat_context, &raw_data_root_path)? {
+                view_paths.push((*view, view_path));
+            }
+        }
+
+        dat_descriptor.convert_with(YamlToDatConverter {
             dat_context,
             raw_data_path,
             dat_root_path,
+            view_paths,
         })
     }
 
+    /// Applies the view file at the given path onto the data of the DAT it's part of.
+    pub(crate) fn apply_view(
+        &self,
+        dat_context: &DatContext,
+        raw_data_path: PathBuf,
+        base: &mut dyn Any,
+    ) -> Result<PathBuf> {
+        self.convert_with(ViewApplier {
+            dat_context,
+            raw_data_path,
+            base,
+        })
+    }
+
+    /// The descriptor of the DAT which this view is a part of.
+    pub fn view_base(&self) -> Option<DatDescriptor> {
+        match self {
+            DatDescriptor::SpellInfo | DatDescriptor::AbilityInfo => Some(DatDescriptor::DataMenu),
+            _ => None,
+        }
+    }
+
+    /// The views which are a part of the DAT of this descriptor.
+    pub fn views(&self) -> &'static [DatDescriptor] {
+        match self {
+            DatDescriptor::DataMenu => &[DatDescriptor::SpellInfo, DatDescriptor::AbilityInfo],
+            _ => &[],
+        }
+    }
+
+    pub fn check_roundtrip(&self, dat_context: Arc<DatContext>) -> Result<PathBuf> {
+        self.convert_with(RoundTripChecker { dat_context })
+    }
+
+    /// The decoded strings of this descriptor's DAT, by the ID of their entry.
+    pub fn collect_strings(&self, dat_context: &DatContext) -> Result<Vec<(u32, String)>> {
+        let mut strings = vec![];
+        self.convert_with(StringCollector {
+            dat_context,
+            strings: &mut strings,
+        })?;
+
+        Ok(strings)
+    }
+
+    /// Checks that every string of this descriptor's raw data file can be encoded, and
+    /// returns all of the problems found. Without a raw data file, there are none.
+    pub fn validate_strings(
+        &self,
+        dat_context: &DatContext,
+        raw_data_root_path: &Path,
+    ) -> Result<Vec<StringProblem>> {
+        let Some(raw_data_path) = self.find_raw_data_path(dat_context, raw_data_root_path)? else {
+            return Ok(vec![]);
+        };
+
+        let mut problems = vec![];
+        self.convert_with(StringValidator {
+            raw_data_path,
+            problems: &mut problems,
+        })?;
+
+        Ok(problems)
+    }
+
+    /// The strings of this descriptor's DAT which match the given pattern.
+    pub fn search_strings(
+        &self,
+        dat_context: &DatContext,
+        pattern: &Regex,
+    ) -> Result<Vec<SearchMatch>> {
+        let strings = self.collect_strings(dat_context)?;
+        Ok(SearchMatch::find_all(*self, &strings, pattern))
+    }
+
+    /// Compares two files in the format of this descriptor's DAT, e.g. from before and after
+    /// a game update.
+    pub fn diff(&self, old_path: PathBuf, new_path: PathBuf) -> Result<DatDiff> {
+        let mut diff = DatDiff::default();
+        self.convert_with(DatDiffer {
+            old_path,
+            new_path,
+            diff: &mut diff,
+        })?;
+
+        Ok(diff)
+    }
+
+    /// Merges our edits with the changes from the base DAT to their DAT, e.g. the
+    /// retail DATs from before and after a game update. The merged data is written to the
+    /// given output path in the format of its extension, with any conflicts marked in it.
+    pub fn merge(
+        &self,
+        base_path: PathBuf,
+        ours_path: PathBuf,
+        theirs_path: PathBuf,
+        out_path: PathBuf,
+    ) -> Result<DatMerge> {
+        let mut merge = None;
+        self.convert_with(DatMerger {
+            base_path,
+            ours_path,
+            theirs_path,
+            out_path,
+            merge: &mut merge,
+        })?;
+
+        merge.ok_or_else(|| anyhow!("Nothing was merged for {:?}.", self))
+    }
+
+    /// Writes the items of this descriptor's DAT to a CSV or TSV file, taking the raw data file
+    /// of the DAT in the given raw data directory if it was converted before.
+    pub fn export_item_table(
+        &self,
+        dat_context: &DatContext,
+        raw_data_root_path: Option<PathBuf>,
+        table_path: PathBuf,
+    ) -> Result<PathBuf> {
+        let raw_data_path = match raw_data_root_path {
+            Some(root_path) => self.find_raw_data_path(dat_context, &root_path)?,
+            None => None,
+        };
+
+        self.convert_with(ItemTableExporter {
+            dat_context,
+            raw_data_path,
+            table_path,
+        })
+    }
+
+    /// Applies the rows of a CSV or TSV file to the items of this descriptor's DAT, and writes
+    /// the result as its raw data file in the given raw data directory. An existing file is
+    /// updated in its own format, otherwise the file is written in the given format.
+    pub fn import_item_table(
+        &self,
+        dat_context: &DatContext,
+        raw_data_root_path: PathBuf,
+        table_path: PathBuf,
+        format: RawDataFormat,
+    ) -> Result<PathBuf> {
+        let raw_data_path = match self.find_raw_data_path(dat_context, &raw_data_root_path)? {
+            Some(raw_data_path) => raw_data_path,
+            None => format.path_in(&raw_data_root_path, &self.get_relative_path(dat_context)?),
+        };
+
+        self.convert_with(ItemTableImporter {
+            dat_context,
+            raw_data_path,
+            table_path,
+        })
+    }
+
+    /// The translatable strings of this descriptor's DAT, taking its raw data file in the given
+    /// directory if there is one. Formats without translatable strings have no units.
+    pub fn translation_units(
+        &self,
+        dat_context: &DatContext,
+        raw_data_root_path: Option<&Path>,
+    ) -> Result<Vec<TranslationUnit>> {
+        let raw_data_path = match raw_data_root_path {
+            Some(root_path) => self.find_raw_data_path(dat_context, root_path)?,
+            None => None,
+        };
+
+        let mut units = vec![];
+        self.convert_with(TranslationExporter {
+            dat_context,
+            dat_descriptor: *self,
+            raw_data_path,
+            units: &mut units,
+        })?;
+
+        Ok(units)
+    }
+
+    /// Replaces the strings of this descriptor's DAT with the translations of the given units,
+    /// and writes them to its raw data file like [`DatDescriptor::import_item_table`].
+    /// Returns how many strings were translated.
+    pub fn apply_translations(
+        &self,
+        dat_context: &DatContext,
+        raw_data_root_path: PathBuf,
+        units: &[TranslationUnit],
+        format: RawDataFormat,
+    ) -> Result<usize> {
+        let raw_data_path = match self.find_raw_data_path(dat_context, &raw_data_root_path)? {
+            Some(raw_data_path) => raw_data_path,
+            None => format.path_in(&raw_data_root_path, &self.get_relative_path(dat_context)?),
+        };
+
+        let mut count = 0;
+        self.convert_with(TranslationImporter {
+            dat_context,
+            dat_descriptor: *self,
+            raw_data_path,
+            units,
+            count: &mut count,
+        })?;
+
+        Ok(count)
+    }
+
+    /// The existing raw data file of this descriptor in the given directory, in any format.
+    pub fn find_raw_data_path(
+        &self,
+        dat_context: &DatContext,
+        raw_data_root_path: &Path,
+    ) -> Result<Option<PathBuf>> {
+        RawDataFormat::find_existing(raw_data_root_path, &self.get_relative_path(dat_context)?)
+    }
+
+    /// The path of this descriptor's DAT in the install.
+    pub fn dat_path(&self, dat_context: &DatContext) -> Result<PathBuf> {
+        self.convert_with(DatPathResolver { dat_context })
+    }
+
+    /// The ID of this descriptor's DAT. Views share the ID of the DAT they're part of.
+    pub fn dat_id(&self) -> Result<DatId> {
+        let mut dat_id = None;
+        self.convert_with(DatIdResolver {
+            dat_id: &mut dat_id,
+        })?;
+
+        dat_id.ok_or_else(|| anyhow!("No DAT ID found for {:?}.", self))
+    }
+
+    /// The zone this descriptor's DAT belongs to, for zoned descriptors.
+    pub fn zone_id(&self) -> Option<ZoneId> {
+        match self {
+            DatDescriptor::EntityNames(zone_id)
+            | DatDescriptor::Dialog(zone_id)
+            | DatDescriptor::Dialog2(zone_id)
+            | DatDescriptor::Events(zone_id) => Some(*zone_id),
+            _ => None,
+        }
+    }
+
+    /// The directory of this descriptor's raw data file, for files which aren't in the root
+    /// of the raw data directory.
+    pub fn sub_dir(&self) -> Option<&'static str> {
+        match self {
+            DatDescriptor::Armor
+            | DatDescriptor::Armor2
+            | DatDescriptor::Currency
+            | DatDescriptor::GeneralItems
+            | DatDescriptor::GeneralItems2
+            | DatDescriptor::PuppetItems
+            | DatDescriptor::UsableItems
+            | DatDescriptor::Weapons
+            | DatDescriptor::VouchersAndSlips
+            | DatDescriptor::Monipulator
+            | DatDescriptor::Instincts => Some("items"),
+
+            DatDescriptor::MonsterSkillNames
+            | DatDescriptor::StatusNamesDialog
+            | DatDescriptor::EmoteMessages
+            | DatDescriptor::SystemMessages1
+            | DatDescriptor::SystemMessages2
+            | DatDescriptor::SystemMessages3
+            | DatDescriptor::SystemMessages4
+            | DatDescriptor::UnityDialogs => Some("global_dialog"),
+
+            DatDescriptor::QuestInfo(_) => Some("quests"),
+
+            DatDescriptor::EntityNames(_) => Some("entity_names"),
+            DatDescriptor::Dialog(_) => Some("dialog"),
+            DatDescriptor::Dialog2(_) => Some("dialog2"),
+            DatDescriptor::Events(_) => Some("events"),
+
+            _ => None,
+        }
+    }
+
+    /// All descriptors which aren't tied to a zone, and which can currently be converted.
+    pub fn all_standalone() -> Vec<DatDescriptor> {
+        vec![
+            DatDescriptor::DataMenu,
+            DatDescriptor::SpellInfo,
+            DatDescriptor::AbilityInfo,
+            DatDescriptor::AbilityNames,
+            DatDescriptor::AbilityDescriptions,
+            DatDescriptor::AreaNames,
+            DatDescriptor::AreaNamesAlt,
+            DatDescriptor::CharacterSelect,
+            DatDescriptor::ChatFilterTypes,
+            DatDescriptor::DayNames,
+            DatDescriptor::Directions,
+            DatDescriptor::EquipmentLocations,
+            DatDescriptor::ErrorMessages,
+            DatDescriptor::IngameMessages1,
+            DatDescriptor::IngameMessages2,
+            DatDescriptor::JobNames,
+            DatDescriptor::KeyItems,
+            DatDescriptor::MenuItemsDescription,
+            DatDescriptor::MenuItemsText,
+            DatDescriptor::MoonPhases,
+            DatDescriptor::PolMessages,
+            DatDescriptor::RaceNames,
+            DatDescriptor::RegionNames,
+            DatDescriptor::SpellNames,
+            DatDescriptor::SpellDescriptions,
+            DatDescriptor::StatusInfo,
+            DatDescriptor::StatusNames,
+            DatDescriptor::TimeAndPronouns,
+            DatDescriptor::Titles,
+            DatDescriptor::Misc1,
+            DatDescriptor::Misc2,
+            DatDescriptor::WeatherTypes,
+            DatDescriptor::Armor,
+            DatDescriptor::Armor2,
+            DatDescriptor::Currency,
+            DatDescriptor::GeneralItems,
+            DatDescriptor::GeneralItems2,
+            DatDescriptor::PuppetItems,
+            DatDescriptor::UsableItems,
+            DatDescriptor::Weapons,
+            DatDescriptor::VouchersAndSlips,
+            DatDescriptor::Monipulator,
+            DatDescriptor::Instincts,
+            DatDescriptor::MonsterSkillNames,
+            DatDescriptor::StatusNamesDialog,
+            DatDescriptor::EmoteMessages,
+            DatDescriptor::SystemMessages1,
+            DatDescriptor::SystemMessages2,
+            DatDescriptor::SystemMessages3,
+            DatDescriptor::SystemMessages4,
+            DatDescriptor::UnityDialogs,
+        ]
+    }
+
+    /// All zoned descriptors, for every named zone whose DAT is in the install. DATs which fail
+    /// the format check are included, so that processing them reports the failure.
+    pub fn all_zoned(dat_context: &DatContext) -> Vec<DatDescriptor> {
+        let mapping = DatIdMapping::get();
+        let mut descriptors = Vec::new();
+
+        Self::push_zones(
+            &mut descriptors,
+            &mapping.entities,
+            dat_context,
+            DatDescriptor::EntityNames,
+        );
+        Self::push_zones(
+            &mut descriptors,
+            &mapping.dialog,
+            dat_context,
+            DatDescriptor::Dialog,
+        );
+        Self::push_zones(
+            &mut descriptors,
+            &mapping.dialog2,
+            dat_context,
+            DatDescriptor::Dialog2,
+        );
+        Self::push_zones(
+            &mut descriptors,
+            &mapping.events,
+            dat_context,
+            DatDescriptor::Events,
+        );
+
+        descriptors
+    }
+
+    /// All quest log descriptors, for every quest log whose DAT is known and in the install.
+    pub fn all_quests(dat_context: &DatContext) -> Vec<DatDescriptor> {
+        DatIdMapping::get()
+            .quest_info
+            .iter()
+            .filter(|(_, dat)| dat_context.get_dat_path(*dat).is_ok())
+            .map(|(index, _)| DatDescriptor::QuestInfo(*index))
+            .collect()
+    }
+
+    fn push_zones<T: DatFormat>(
+        descriptors: &mut Vec<DatDescriptor>,
+        dat_by_zone: &DatByZone<T>,
+        dat_context: &DatContext,
+        to_descriptor: fn(ZoneId) -> DatDescriptor,
+    ) {
+        descriptors.extend(dat_by_zone.map.iter().filter_map(|(zone_id, dat)| {
+            if dat_context.zone_id_to_name.contains_key(zone_id)
+                && dat_context.get_dat_path(dat).is_ok()
+            {
+                Some(to_descriptor(*zone_id))
+            } else {
+                None
+            }
+        }));
+    }
+
     fn get_zoned_file_name(
         dat_context: &DatContext,
         dir_name: &'static str,
@@ -128,9 +576,11 @@ impl DatDescriptor {
         ))
     }
 
-    fn get_relative_path(&self, dat_context: &DatContext) -> Result<String> {
+    pub fn get_relative_path(&self, dat_context: &DatContext) -> Result<String> {
         match self {
             DatDescriptor::DataMenu => Ok("data_menu".to_string()),
+            DatDescriptor::SpellInfo => Ok("spell_info".to_string()),
+            DatDescriptor::AbilityInfo => Ok("ability_info".to_string()),
 
             DatDescriptor::AbilityNames => Ok("ability_names".to_string()),
             DatDescriptor::AbilityDescriptions => Ok("ability_descriptions".to_string()),
@@ -183,6 +633,8 @@ impl DatDescriptor {
             DatDescriptor::SystemMessages4 => Ok("global_dialog/system_messages4".to_string()),
             DatDescriptor::UnityDialogs => Ok("global_dialog/unity_dialogs".to_string()),
 
+            DatDescriptor::QuestInfo(index) => Ok(format!("quests/{}", index)),
+
             DatDescriptor::EntityNames(zone_id) => {
                 Self::get_zoned_file_name(dat_context, "entity_names", zone_id)
             }
@@ -192,6 +644,9 @@ impl DatDescriptor {
             DatDescriptor::Dialog2(zone_id) => {
                 Self::get_zoned_file_name(dat_context, "dialog2", zone_id)
             }
+            DatDescriptor::Events(zone_id) => {
+                Self::get_zoned_file_name(dat_context, "events", zone_id)
+            }
         }
     }
 
@@ -206,10 +661,8 @@ impl DatDescriptor {
     ) -> Option<Self> {
         let path = path.strip_prefix(raw_data_dir).unwrap_or(path);
 
-        let file_name = path
-            .file_name()
-            .and_then(|osstr| osstr.to_str())
-            .map(|s| s.trim_end_matches(".yml"))?;
+        RawDataFormat::from_path(path)?;
+        let file_name = path.file_stem().and_then(|osstr| osstr.to_str())?;
 
         if let Some(parent) = path
             .parent()
@@ -223,6 +676,7 @@ impl DatDescriptor {
                 }
                 "dialog" => Self::get_zone_id(file_name, dat_context).map(DatDescriptor::Dialog),
                 "dialog2" => Self::get_zone_id(file_name, dat_context).map(DatDescriptor::Dialog2),
+                "events" => Self::get_zone_id(file_name, dat_context).map(DatDescriptor::Events),
 
                 "items" => match file_name {
                     "armor" => Some(DatDescriptor::Armor),
@@ -238,6 +692,7 @@ impl DatDescriptor {
                     "instincts" => Some(DatDescriptor::Instincts),
                     _ => None,
                 },
+                "quests" => file_name.parse().ok().map(DatDescriptor::QuestInfo),
                 "global_dialog" => match file_name {
                     "monster_skill_names" => Some(DatDescriptor::MonsterSkillNames),
                     "status_names_dialog" => Some(DatDescriptor::StatusNamesDialog),
@@ -249,16 +704,15 @@ impl DatDescriptor {
                     "unity_dialogs" => Some(DatDescriptor::UnityDialogs),
                     _ => None,
                 },
-                _ => {
-                    println!("Parent is: {}", parent);
-                    None
-                }
+                _ => None,
             };
         }
 
         // Files in root directory
         match file_name {
             "data_menu" => Some(DatDescriptor::DataMenu),
+            "spell_info" => Some(DatDescriptor::SpellInfo),
+            "ability_info" => Some(DatDescriptor::AbilityInfo),
 
             "ability_names" => Some(DatDescriptor::AbilityNames),
             "ability_descriptions" => Some(DatDescriptor::AbilityDescriptions),
@@ -296,132 +750,104 @@ impl DatDescriptor {
     }
 
     fn convert_with<T: DatUsage>(self, converter: T) -> Result<PathBuf> {
-        match self {
-            DatDescriptor::DataMenu => converter.use_dat(DatIdMapping::get().data_menu.clone()),
+        let mapping = DatIdMapping::get();
 
-            DatDescriptor::AbilityNames => {
-                converter.use_dat(DatIdMapping::get().ability_names.clone())
-            }
-            DatDescriptor::AbilityDescriptions => {
-                converter.use_dat(DatIdMapping::get().ability_descriptions.clone())
-            }
-            DatDescriptor::AreaNames => converter.use_dat(DatIdMapping::get().area_names.clone()),
-            DatDescriptor::AreaNamesAlt => {
-                converter.use_dat(DatIdMapping::get().area_names_alt.clone())
-            }
-            DatDescriptor::CharacterSelect => {
-                converter.use_dat(DatIdMapping::get().character_select.clone())
+        match self {
+            DatDescriptor::DataMenu => converter.use_dat(mapping.data_menu.clone()),
+            DatDescriptor::SpellInfo => {
+                converter.use_view::<SpellInfoView>(mapping.data_menu.clone())
             }
-            DatDescriptor::ChatFilterTypes => {
-                converter.use_dat(DatIdMapping::get().chat_filter_types.clone())
+            DatDescriptor::AbilityInfo => {
+                converter.use_view::<AbilityInfoView>(mapping.data_menu.clone())
             }
-            DatDescriptor::DayNames => converter.use_dat(DatIdMapping::get().day_names.clone()),
-            DatDescriptor::Directions => converter.use_dat(DatIdMapping::get().directions.clone()),
+
+            DatDescriptor::AbilityNames => converter.use_dat(mapping.ability_names.clone()),
+            DatDescriptor::AbilityDescriptions => {
+                converter.use_dat(mapping.ability_descriptions.clone())
+            }
+            DatDescriptor::AreaNames => converter.use_dat(mapping.area_names.clone()),
+            DatDescriptor::AreaNamesAlt => converter.use_dat(mapping.area_names_alt.clone()),
+            DatDescriptor::CharacterSelect => converter.use_dat(mapping.character_select.clone()),
+            DatDescriptor::ChatFilterTypes => converter.use_dat(mapping.chat_filter_types.clone()),
+            DatDescriptor::DayNames => converter.use_dat(mapping.day_names.clone()),
+            DatDescriptor::Directions => converter.use_dat(mapping.directions.clone()),
             DatDescriptor::EquipmentLocations => {
-                converter.use_dat(DatIdMapping::get().equipment_locations.clone())
-            }
-            DatDescriptor::ErrorMessages => {
-                converter.use_dat(DatIdMapping::get().error_messages.clone())
-            }
-            DatDescriptor::IngameMessages1 => {
-                converter.use_dat(DatIdMapping::get().ingame_messages_1.clone())
+                converter.use_dat(mapping.equipment_locations.clone())
             }
-            DatDescriptor::IngameMessages2 => {
-                converter.use_dat(DatIdMapping::get().ingame_messages_2.clone())
-            }
-            DatDescriptor::JobNames => converter.use_dat(DatIdMapping::get().job_names.clone()),
-            DatDescriptor::KeyItems => converter.use_dat(DatIdMapping::get().key_items.clone()),
+            DatDescriptor::ErrorMessages => converter.use_dat(mapping.error_messages.clone()),
+            DatDescriptor::IngameMessages1 => converter.use_dat(mapping.ingame_messages_1.clone()),
+            DatDescriptor::IngameMessages2 => converter.use_dat(mapping.ingame_messages_2.clone()),
+            DatDescriptor::JobNames => converter.use_dat(mapping.job_names.clone()),
+            DatDescriptor::KeyItems => converter.use_dat(mapping.key_items.clone()),
             DatDescriptor::MenuItemsDescription => {
-                converter.use_dat(DatIdMapping::get().menu_items_description.clone())
-            }
-            DatDescriptor::MenuItemsText => {
-                converter.use_dat(DatIdMapping::get().menu_items_text.clone())
-            }
-            DatDescriptor::MoonPhases => converter.use_dat(DatIdMapping::get().moon_phases.clone()),
-            DatDescriptor::PolMessages => {
-                converter.use_dat(DatIdMapping::get().pol_messages.clone())
-            }
-            DatDescriptor::RaceNames => converter.use_dat(DatIdMapping::get().race_names.clone()),
-            DatDescriptor::RegionNames => {
-                converter.use_dat(DatIdMapping::get().region_names.clone())
-            }
-            DatDescriptor::SpellNames => converter.use_dat(DatIdMapping::get().spell_names.clone()),
+                converter.use_dat(mapping.menu_items_description.clone())
+            }
+            DatDescriptor::MenuItemsText => converter.use_dat(mapping.menu_items_text.clone()),
+            DatDescriptor::MoonPhases => converter.use_dat(mapping.moon_phases.clone()),
+            DatDescriptor::PolMessages => converter.use_dat(mapping.pol_messages.clone()),
+            DatDescriptor::RaceNames => converter.use_dat(mapping.race_names.clone()),
+            DatDescriptor::RegionNames => converter.use_dat(mapping.region_names.clone()),
+            DatDescriptor::SpellNames => converter.use_dat(mapping.spell_names.clone()),
             DatDescriptor::SpellDescriptions => {
-                converter.use_dat(DatIdMapping::get().spell_descriptions.clone())
-            }
-            DatDescriptor::StatusInfo => converter.use_dat(DatIdMapping::get().status_info.clone()),
-            DatDescriptor::StatusNames => {
-                converter.use_dat(DatIdMapping::get().status_names.clone())
-            }
-            DatDescriptor::TimeAndPronouns => {
-                converter.use_dat(DatIdMapping::get().time_and_pronouns.clone())
-            }
-            DatDescriptor::Titles => converter.use_dat(DatIdMapping::get().titles.clone()),
-            DatDescriptor::Misc1 => converter.use_dat(DatIdMapping::get().misc1.clone()),
-            DatDescriptor::Misc2 => converter.use_dat(DatIdMapping::get().misc2.clone()),
-            DatDescriptor::WeatherTypes => {
-                converter.use_dat(DatIdMapping::get().weather_types.clone())
-            }
+                converter.use_dat(mapping.spell_descriptions.clone())
+            }
+            DatDescriptor::StatusInfo => converter.use_dat(mapping.status_info.clone()),
+            DatDescriptor::StatusNames => converter.use_dat(mapping.status_names.clone()),
+            DatDescriptor::TimeAndPronouns => converter.use_dat(mapping.time_and_pronouns.clone()),
+            DatDescriptor::Titles => converter.use_dat(mapping.titles.clone()),
+            DatDescriptor::Misc1 => converter.use_dat(mapping.misc1.clone()),
+            DatDescriptor::Misc2 => converter.use_dat(mapping.misc2.clone()),
+            DatDescriptor::WeatherTypes => converter.use_dat(mapping.weather_types.clone()),
 
-            DatDescriptor::Armor => converter.use_dat(DatIdMapping::get().armor.clone()),
-            DatDescriptor::Armor2 => converter.use_dat(DatIdMapping::get().armor2.clone()),
-            DatDescriptor::Currency => converter.use_dat(DatIdMapping::get().currency.clone()),
-            DatDescriptor::GeneralItems => {
-                converter.use_dat(DatIdMapping::get().general_items.clone())
-            }
-            DatDescriptor::GeneralItems2 => {
-                converter.use_dat(DatIdMapping::get().general_items2.clone())
-            }
-            DatDescriptor::PuppetItems => {
-                converter.use_dat(DatIdMapping::get().puppet_items.clone())
-            }
-            DatDescriptor::UsableItems => {
-                converter.use_dat(DatIdMapping::get().usable_items.clone())
-            }
-            DatDescriptor::Weapons => converter.use_dat(DatIdMapping::get().weapons.clone()),
+            DatDescriptor::Armor => converter.use_dat(mapping.armor.clone()),
+            DatDescriptor::Armor2 => converter.use_dat(mapping.armor2.clone()),
+            DatDescriptor::Currency => converter.use_dat(mapping.currency.clone()),
+            DatDescriptor::GeneralItems => converter.use_dat(mapping.general_items.clone()),
+            DatDescriptor::GeneralItems2 => converter.use_dat(mapping.general_items2.clone()),
+            DatDescriptor::PuppetItems => converter.use_dat(mapping.puppet_items.clone()),
+            DatDescriptor::UsableItems => converter.use_dat(mapping.usable_items.clone()),
+            DatDescriptor::Weapons => converter.use_dat(mapping.weapons.clone()),
             DatDescriptor::VouchersAndSlips => {
-                converter.use_dat(DatIdMapping::get().vouchers_and_slips.clone())
-            }
-            DatDescriptor::Monipulator => {
-                converter.use_dat(DatIdMapping::get().monipulator.clone())
+                converter.use_dat(mapping.vouchers_and_slips.clone())
             }
-            DatDescriptor::Instincts => converter.use_dat(DatIdMapping::get().instincts.clone()),
+            DatDescriptor::Monipulator => converter.use_dat(mapping.monipulator.clone()),
+            DatDescriptor::Instincts => converter.use_dat(mapping.instincts.clone()),
 
             // Global dialog
             DatDescriptor::MonsterSkillNames => {
-                converter.use_dat(DatIdMapping::get().monster_skill_names.clone())
+                converter.use_dat(mapping.monster_skill_names.clone())
             }
             DatDescriptor::StatusNamesDialog => {
-                converter.use_dat(DatIdMapping::get().status_names_dialog.clone())
-            }
-            DatDescriptor::EmoteMessages => {
-                converter.use_dat(DatIdMapping::get().emote_messages.clone())
-            }
-            DatDescriptor::SystemMessages1 => {
-                converter.use_dat(DatIdMapping::get().system_messages_1.clone())
-            }
-            DatDescriptor::SystemMessages2 => {
-                converter.use_dat(DatIdMapping::get().system_messages_2.clone())
-            }
-            DatDescriptor::SystemMessages3 => {
-                converter.use_dat(DatIdMapping::get().system_messages_3.clone())
-            }
-            DatDescriptor::SystemMessages4 => {
-                converter.use_dat(DatIdMapping::get().system_messages_4.clone())
-            }
-            DatDescriptor::UnityDialogs => {
-                converter.use_dat(DatIdMapping::get().unity_dialogs.clone())
+                converter.use_dat(mapping.status_names_dialog.clone())
+            }
+            DatDescriptor::EmoteMessages => converter.use_dat(mapping.emote_messages.clone()),
+            DatDescriptor::SystemMessages1 => converter.use_dat(mapping.system_messages_1.clone()),
+            DatDescriptor::SystemMessages2 => converter.use_dat(mapping.system_messages_2.clone()),
+            DatDescriptor::SystemMessages3 => converter.use_dat(mapping.system_messages_3.clone()),
+            DatDescriptor::SystemMessages4 => converter.use_dat(mapping.system_messages_4.clone()),
+            DatDescriptor::UnityDialogs => converter.use_dat(mapping.unity_dialogs.clone()),
+
+            // Quest logs
+            DatDescriptor::QuestInfo(index) => {
+                let dat = mapping
+                    .quest_info
+                    .get(&index)
+                    .ok_or(anyhow!("The DAT of quest log {} isn't known yet.", index))?;
+                converter.use_dat(dat.clone())
             }
 
             // By zone
             DatDescriptor::EntityNames(zone_id) => {
-                converter.use_dat(DatIdMapping::get().entities.get_result(&zone_id)?.clone())
+                converter.use_dat(mapping.entities.get_result(&zone_id)?.clone())
             }
             DatDescriptor::Dialog(zone_id) => {
-                converter.use_dat(DatIdMapping::get().dialog.get_result(&zone_id)?.clone())
+                converter.use_dat(mapping.dialog.get_result(&zone_id)?.clone())
             }
             DatDescriptor::Dialog2(zone_id) => {
-                converter.use_dat(DatIdMapping::get().dialog.get_result(&zone_id)?.clone())
+                converter.use_dat(mapping.dialog2.get_result(&zone_id)?.clone())
+            }
+            DatDescriptor::Events(zone_id) => {
+                converter.use_view::<EventFile>(mapping.events.get_result(&zone_id)?.clone())
             }
         }
     }
@@ -0,0 +1,253 @@
+use std::{collections::BTreeSet, fmt::Write};
+
+use anyhow::Result;
+use dats::entries::DatEntries;
+use serde::Serialize;
+use serde_yaml::Value;
+
+/// Values longer than this are cut off in the text output, e.g. for icons.
+const MAX_TEXT_VALUE_LENGTH: usize = 80;
+
+/// The entries which differ between two versions of a DAT, ordered by their key.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct DatDiff {
+    pub changes: Vec<EntryChange>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "change", rename_all = "snake_case")]
+pub enum EntryChange {
+    Added { key: u32, entry: Value },
+    Removed { key: u32, entry: Value },
+    Changed { key: u32, old: Value, new: Value },
+}
+
+impl EntryChange {
+    pub fn key(&self) -> u32 {
+        match self {
+            EntryChange::Added { key, .. }
+            | EntryChange::Removed { key, .. }
+            | EntryChange::Changed { key, .. } => *key,
+        }
+    }
+}
+
+impl DatDiff {
+    pub fn between<T: DatEntries>(old: &T, new: &T) -> Result<Self> {
+        let old_entries = old.entries();
+        let new_entries = new.entries();
+        let keys = old_entries
+            .keys()
+            .chain(new_entries.keys())
+            .copied()
+            .collect::<BTreeSet<_>>();
+
+        let mut changes = vec![];
+        for key in keys {
+            let change = match (old_entries.get(&key), new_entries.get(&key)) {
+                (Some(old), Some(new)) => {
+                    let old = serde_yaml::to_value(old)?;
+                    let new = serde_yaml::to_value(new)?;
+                    if old == new {
+                        continue;
+                    }
+                    EntryChange::Changed { key, old, new }
+                }
+                (None, Some(new)) => EntryChange::Added {
+                    key,
+                    entry: serde_yaml::to_value(new)?,
+                },
+                (Some(old), None) => EntryChange::Removed {
+                    key,
+                    entry: serde_yaml::to_value(old)?,
+                },
+                (None, None) => continue,
+            };
+            changes.push(change);
+        }
+
+        Ok(DatDiff { changes })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    pub fn to_yaml(&self) -> Result<String> {
+        Ok(serde_yaml::to_string(self)?)
+    }
+
+    /// Renders one line for each added or removed entry, and one for each changed field of
+    /// the changed entries.
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        let (mut added, mut removed, mut changed) = (0, 0, 0);
+
+        for change in &self.changes {
+            match change {
+                EntryChange::Added { key, entry } => {
+                    added += 1;
+                    let _ = writeln!(text, "+ {}: {}", key, inline_value(entry));
+                }
+                EntryChange::Removed { key, entry } => {
+                    removed += 1;
+                    let _ = writeln!(text, "- {}: {}", key, inline_value(entry));
+                }
+                EntryChange::Changed { key, old, new } => {
+                    changed += 1;
+                    match (old, new) {
+                        (Value::Mapping(old_fields), Value::Mapping(new_fields)) => {
+                            let _ = writeln!(text, "~ {}:", key);
+                            let fields = old_fields.keys().chain(
+                                new_fields
+                                    .keys()
+                                    .filter(|key| !old_fields.contains_key(key)),
+                            );
+                            for field in fields {
+                                let old_value = old_fields.get(field).unwrap_or(&Value::Null);
+                                let new_value = new_fields.get(field).unwrap_or(&Value::Null);
+                                if old_value != new_value {
+                                    let _ = writeln!(
+                                        text,
+                                        "    {}: {} -> {}",
+                                        inline_value(field),
+                                        inline_value(old_value),
+                                        inline_value(new_value)
+                                    );
+                                }
+                            }
+                        }
+                        _ => {
+                            let _ = writeln!(
+                                text,
+                                "~ {}: {} -> {}",
+                                key,
+                                inline_value(old),
+                                inline_value(new)
+                            );
+                        }
+                    }
+                }
+            }
+        }
+
+        let _ = writeln!(
+            text,
+            "{} added, {} removed, {} changed",
+            added, removed, changed
+        );
+        text
+    }
+}
+
+fn inline_value(value: &Value) -> String {
+    let text = match value {
+        Value::String(string) => format!("{:?}", string),
+        _ => serde_json::to_string(value).unwrap_or_else(|_| format!("{:?}", value)),
+    };
+
+    match text.char_indices().nth(MAX_TEXT_VALUE_LENGTH) {
+        Some((idx, _)) => format!("{}...", &text[..idx]),
+        None => text,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use anyhow::Result;
+    use dats::{
+        dat_format::DatFormat,
+        fixtures::FixtureInstall,
+        formats::{dialog::Dialog, entity_names::EntityNames},
+    };
+    use serde_yaml::Value;
+
+    use crate::dat_descriptor::DatDescriptor;
+
+    use super::{DatDiff, EntryChange};
+
+    #[test]
+    fn dialog_changes() -> Result<()> {
+        let old = Dialog::fixture();
+        let mut new = Dialog::fixture();
+        new.entries.remove(&0);
+        new.entries.insert(1, "Changed.".to_string());
+        new.entries.insert(5, "Added.".to_string());
+
+        let diff = DatDiff::between(&old, &new)?;
+        assert_eq!(
+            diff.changes
+                .iter()
+                .map(EntryChange::key)
+                .collect::<Vec<_>>(),
+            vec![0, 1, 5]
+        );
+        assert!(matches!(diff.changes[0], EntryChange::Removed { .. }));
+        assert_eq!(
+            diff.changes[2],
+            EntryChange::Added {
+                key: 5,
+                entry: Value::String("Added.".to_string())
+            }
+        );
+
+        let text = diff.to_text();
+        assert!(text.contains("+ 5: \"Added.\""));
+        assert!(text.contains("~ 1: "));
+        assert!(text.ends_with("1 added, 1 removed, 1 changed\n"));
+        assert!(diff.to_yaml()?.contains("change: removed"));
+
+        assert!(DatDiff::between(&old, &Dialog::fixture())?.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn changed_fields() -> Result<()> {
+        let old = EntityNames::fixture();
+        let new: EntityNames =
+            serde_yaml::from_str(&serde_yaml::to_string(&old)?.replace("Moogle", "Mog"))?;
+
+        let diff = DatDiff::between(&old, &new)?;
+        assert_eq!(diff.changes.len(), 1);
+        assert_eq!(
+            diff.to_text(),
+            "~ 16781313:\n    \"name\": \"Moogle\" -> \"Mog\"\n0 added, 0 removed, 1 changed\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn diff_files_of_descriptor() -> Result<()> {
+        let dat_dir = tempfile::tempdir()?;
+        let fixture = FixtureInstall::new()?;
+        let dat_context = fixture.dat_context.clone();
+
+        let mut dialog = Dialog::fixture();
+        dialog
+            .entries
+            .insert(0, "Welcome to the patched zone.".to_string());
+        let new_path = dat_dir.path().join("new.DAT");
+        fs::write(&new_path, dialog.to_bytes()?)?;
+
+        let old_path = DatDescriptor::Dialog(1).dat_path(&dat_context)?;
+        let diff = DatDescriptor::Dialog(1).diff(old_path, new_path)?;
+        assert_eq!(
+            diff.to_text(),
+            "~ 0: \"Welcome to the test zone.\" -> \"Welcome to the patched zone.\"\n\
+             0 added, 0 removed, 1 changed\n"
+        );
+
+        assert!(DatDescriptor::DataMenu
+            .diff(
+                DatDescriptor::DataMenu.dat_path(&dat_context)?,
+                DatDescriptor::DataMenu.dat_path(&dat_context)?,
+            )
+            .is_err());
+
+        Ok(())
+    }
+}
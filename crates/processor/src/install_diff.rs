@@ -0,0 +1,349 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    fs::{self, File},
+    hash::{DefaultHasher, Hasher},
+    io::{BufReader, BufWriter, Read},
+    path::{Path, PathBuf},
+    sync::mpsc,
+};
+
+use anyhow::{anyhow, Result};
+use dats::{
+    base::{DatId, DatPath},
+    context::DatContext,
+    id_mapping::DatIdMapping,
+};
+use serde::Serialize;
+
+use crate::dat_descriptor::DatDescriptor;
+
+/// Which DATs were added, removed or changed between two installs, e.g. by a game update.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct InstallDiff {
+    pub added: usize,
+    pub removed: usize,
+    pub changed: usize,
+    pub moved: usize,
+    /// The changed DATs, ordered by their ID.
+    pub changes: Vec<DatChange>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DatChange {
+    pub dat_id: u32,
+    #[serde(flatten)]
+    pub kind: DatChangeKind,
+    /// The descriptor the DAT is converted with, if it's a supported DAT.
+    pub dat_descriptor: Option<DatDescriptor>,
+    pub zone_name: Option<String>,
+}
+
+/// How a DAT changed, with the paths of the DAT relative to each install.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "change", rename_all = "snake_case")]
+pub enum DatChangeKind {
+    Added {
+        path: PathBuf,
+    },
+    Removed {
+        path: PathBuf,
+    },
+    Changed {
+        old_path: PathBuf,
+        new_path: PathBuf,
+    },
+    /// The DAT's contents are the same, but it's stored in another file.
+    Moved {
+        old_path: PathBuf,
+        new_path: PathBuf,
+    },
+}
+
+/// A DAT whose file exists in its install.
+struct InstalledDat {
+    dat_path: DatPath,
+    full_path: PathBuf,
+    size: u64,
+}
+
+impl InstallDiff {
+    /// Compares the DAT IDs of both installs, and the contents of the DATs present in both.
+    /// DATs with the same size are compared by their hash, which is done in parallel.
+    pub fn between(old_context: &DatContext, new_context: &DatContext) -> Result<Self> {
+        let old_dats = installed_dats(old_context);
+        let new_dats = installed_dats(new_context);
+        let dat_ids = old_dats
+            .keys()
+            .chain(new_dats.keys())
+            .copied()
+            .collect::<BTreeSet<_>>();
+
+        let mut kinds = vec![];
+        let mut same_size = vec![];
+        for dat_id in dat_ids {
+            match (old_dats.get(&dat_id), new_dats.get(&dat_id)) {
+                (Some(old), Some(new)) if old.size == new.size => {
+                    same_size.push((dat_id, old.full_path.clone(), new.full_path.clone()))
+                }
+                (Some(old), Some(new)) => kinds.push((
+                    dat_id,
+                    DatChangeKind::Changed {
+                        old_path: old.dat_path.to_path(),
+                        new_path: new.dat_path.to_path(),
+                    },
+                )),
+                (None, Some(new)) => kinds.push((
+                    dat_id,
+                    DatChangeKind::Added {
+                        path: new.dat_path.to_path(),
+                    },
+                )),
+                (Some(old), None) => kinds.push((
+                    dat_id,
+                    DatChangeKind::Removed {
+                        path: old.dat_path.to_path(),
+                    },
+                )),
+                (None, None) => {}
+            }
+        }
+
+        for (dat_id, same_contents) in compare_contents(same_size)? {
+            let old_path = old_dats[&dat_id].dat_path.to_path();
+            let new_path = new_dats[&dat_id].dat_path.to_path();
+            if !same_contents {
+                kinds.push((dat_id, DatChangeKind::Changed { old_path, new_path }));
+            } else if old_path != new_path {
+                kinds.push((dat_id, DatChangeKind::Moved { old_path, new_path }));
+            }
+        }
+        kinds.sort_by_key(|(dat_id, _)| *dat_id);
+
+        let dat_descriptors = descriptors_by_dat_id();
+
+        let mut diff = InstallDiff::default();
+        for (dat_id, kind) in kinds {
+            match kind {
+                DatChangeKind::Added { .. } => diff.added += 1,
+                DatChangeKind::Removed { .. } => diff.removed += 1,
+                DatChangeKind::Changed { .. } => diff.changed += 1,
+                DatChangeKind::Moved { .. } => diff.moved += 1,
+            }
+
+            // Zone names are taken from the install the DAT is part of, preferring the new one.
+            let dat_context = match kind {
+                DatChangeKind::Removed { .. } => old_context,
+                _ => new_context,
+            };
+            let dat_descriptor = dat_descriptors.get(&dat_id).copied();
+            let zone_name = dat_descriptor
+                .and_then(|dat_descriptor| dat_descriptor.zone_id())
+                .and_then(|zone_id| dat_context.zone_id_to_name.get(&zone_id))
+                .map(|zone_name| zone_name.display_name.clone());
+
+            diff.changes.push(DatChange {
+                dat_id: dat_id.get_inner(),
+                kind,
+                dat_descriptor,
+                zone_name,
+            });
+        }
+
+        Ok(diff)
+    }
+
+    /// Writes the diff as JSON or YAML, depending on the extension of the given path.
+    pub fn write_to_path(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or_default();
+        if !matches!(extension, "json" | "yml" | "yaml") {
+            return Err(anyhow!(
+                "Unsupported report format '{}', expected .json, .yml or .yaml.",
+                extension
+            ));
+        }
+
+        let writer = BufWriter::new(
+            File::create(path)
+                .map_err(|err| anyhow!("Could not create file at {}: {}", path.display(), err))?,
+        );
+        if extension == "json" {
+            serde_json::to_writer_pretty(writer, self)?;
+        } else {
+            serde_yaml::to_writer(writer, self)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The DATs of the install's lookup tables whose files exist.
+fn installed_dats(dat_context: &DatContext) -> HashMap<DatId, InstalledDat> {
+    dat_context
+        .id_map
+        .iter()
+        .filter_map(|(dat_id, dat_path)| {
+            let full_path = dat_context.ffxi_path.join(dat_path.to_path());
+            let size = fs::metadata(&full_path).ok()?.len();
+
+            Some((
+                *dat_id,
+                InstalledDat {
+                    dat_path: *dat_path,
+                    full_path,
+                    size,
+                },
+            ))
+        })
+        .collect()
+}
+
+/// Whether the old and new file of each DAT have the same contents.
+fn compare_contents(files: Vec<(DatId, PathBuf, PathBuf)>) -> Result<Vec<(DatId, bool)>> {
+    let pool = threadpool::Builder::new()
+        .thread_name("dat-hasher".to_string())
+        .build();
+    let (tx, rx) = mpsc::channel();
+
+    let total_count = files.len();
+    for (dat_id, old_path, new_path) in files {
+        let tx = tx.clone();
+
+        pool.execute(move || {
+            let same_contents = hash_file(&old_path)
+                .and_then(|old_hash| Ok(old_hash == hash_file(&new_path)?))
+                .map(|same_contents| (dat_id, same_contents));
+            let _ = tx.send(same_contents);
+        });
+    }
+    drop(tx);
+
+    rx.iter().take(total_count).collect()
+}
+
+fn hash_file(path: &Path) -> Result<u64> {
+    let file = File::open(path)
+        .map_err(|err| anyhow!("Could not open file at {}: {}", path.display(), err))?;
+
+    let mut reader = BufReader::new(file);
+    let mut hasher = DefaultHasher::new();
+    let mut buffer = [0u8; 0x10000];
+    loop {
+        let read = reader.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.write(&buffer[..read]);
+    }
+
+    Ok(hasher.finish())
+}
+
+/// The descriptors of the supported DATs by their ID, including zones without a DAT in the
+/// install. Views aren't included, as they share the ID of the DAT they're part of.
+fn descriptors_by_dat_id() -> HashMap<DatId, DatDescriptor> {
+    let mapping = DatIdMapping::get();
+    let mut dat_descriptors = DatDescriptor::all_standalone();
+    dat_descriptors.extend(
+        mapping
+            .quest_info
+            .keys()
+            .map(|index| DatDescriptor::QuestInfo(*index)),
+    );
+    for (zoned_dats, to_descriptor) in [
+        (
+            mapping.entities.map.keys().collect::<Vec<_>>(),
+            DatDescriptor::EntityNames as fn(_) -> _,
+        ),
+        (mapping.dialog.map.keys().collect(), DatDescriptor::Dialog),
+        (mapping.dialog2.map.keys().collect(), DatDescriptor::Dialog2),
+        (mapping.events.map.keys().collect(), DatDescriptor::Events),
+    ] {
+        dat_descriptors.extend(
+            zoned_dats
+                .into_iter()
+                .map(|zone_id| to_descriptor(*zone_id)),
+        );
+    }
+
+    dat_descriptors
+        .into_iter()
+        .filter(|dat_descriptor| dat_descriptor.view_base().is_none())
+        .filter_map(|dat_descriptor| Some((dat_descriptor.dat_id().ok()?, dat_descriptor)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use dats::{
+        fixtures::{FakeInstall, FixtureInstall},
+        formats::{dialog::Dialog, entity_names::EntityNames},
+        id_mapping::DatIdMapping,
+    };
+
+    use crate::dat_descriptor::DatDescriptor;
+
+    use super::{DatChangeKind, InstallDiff};
+
+    #[test]
+    fn diff_fake_installs() -> Result<()> {
+        let mapping = DatIdMapping::get();
+        let mut old_install = FakeInstall::with_fixtures()?;
+        old_install.add_bytes(0x1FF00, vec![1, 2, 3, 4]);
+        let old = FixtureInstall::write(old_install)?;
+
+        let mut dialog = Dialog::fixture();
+        dialog
+            .entries
+            .insert(0, "Welcome to the updated zone.".to_string());
+        let mut new_install = FakeInstall::with_fixtures()?;
+        new_install.add(mapping.dialog.get_result(&2)?, &dialog)?;
+        new_install.add(mapping.entities.get_result(&2)?, &EntityNames::fixture())?;
+        let new = FixtureInstall::write(new_install)?;
+
+        let diff = InstallDiff::between(&old.dat_context, &new.dat_context)?;
+        assert_eq!((diff.added, diff.removed, diff.changed), (1, 1, 1));
+
+        let change_of = |dat_descriptor: DatDescriptor| {
+            diff.changes
+                .iter()
+                .find(|change| change.dat_descriptor == Some(dat_descriptor))
+                .unwrap()
+        };
+        let added = change_of(DatDescriptor::EntityNames(2));
+        assert!(matches!(added.kind, DatChangeKind::Added { .. }));
+        assert_eq!(added.zone_name.as_deref(), Some("Other Zone"));
+        assert!(matches!(
+            change_of(DatDescriptor::Dialog(2)).kind,
+            DatChangeKind::Changed { .. }
+        ));
+
+        let removed = diff
+            .changes
+            .iter()
+            .find(|change| change.dat_id == 0x1FF00)
+            .unwrap();
+        assert_eq!(removed.dat_descriptor, None);
+        assert!(matches!(removed.kind, DatChangeKind::Removed { .. }));
+
+        // DATs which only got another file because of the added DATs aren't reported as changed.
+        assert!(diff.moved > 0);
+        assert!(matches!(
+            change_of(DatDescriptor::MonsterSkillNames).kind,
+            DatChangeKind::Moved { .. }
+        ));
+
+        assert!(InstallDiff::between(&old.dat_context, &old.dat_context)?
+            .changes
+            .is_empty());
+
+        Ok(())
+    }
+}
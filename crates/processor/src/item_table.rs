@@ -0,0 +1,120 @@
+use std::path::Path;
+
+use anyhow::{anyhow, Result};
+use dats::views::item_table::{ItemRow, ItemTableView};
+
+/// Separator of the columns, by the extension of the table's file.
+fn delimiter(path: &Path) -> Result<u8> {
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("csv") => Ok(b','),
+        Some("tsv") => Ok(b'\t'),
+        _ => Err(anyhow!(
+            "Unsupported table format for {}, expected .csv or .tsv.",
+            path.display()
+        )),
+    }
+}
+
+/// Writes the rows of items as CSV or TSV, depending on the extension of the given path.
+pub fn write_item_table(path: &Path, view: &ItemTableView) -> Result<()> {
+    let mut writer = csv::WriterBuilder::new()
+        .delimiter(delimiter(path)?)
+        .from_path(path)
+        .map_err(|err| anyhow!("Could not create file at {}: {}", path.display(), err))?;
+
+    for row in &view.rows {
+        writer.serialize(row)?;
+    }
+    writer.flush()?;
+
+    Ok(())
+}
+
+pub fn read_item_table(path: &Path) -> Result<ItemTableView> {
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter(path)?)
+        .from_path(path)
+        .map_err(|err| anyhow!("Could not open file at {}: {}", path.display(), err))?;
+
+    let rows = reader
+        .deserialize::<ItemRow>()
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|err| anyhow!("Could not read {}: {}", path.display(), err))?;
+
+    Ok(ItemTableView { rows })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use anyhow::Result;
+    use dats::{
+        enums::ItemType, fixtures::FixtureInstall, formats::item_info::ItemInfoTable,
+        views::item_table::ItemTableView,
+    };
+
+    use crate::{dat_descriptor::DatDescriptor, raw_data_format::RawDataFormat};
+
+    use super::{read_item_table, write_item_table};
+
+    #[test]
+    fn table_roundtrip() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let mut view = ItemTableView::from_table(&ItemInfoTable::fixture());
+        view.rows[0].item_type = ItemType::Unknown(99);
+
+        for file_name in ["items.csv", "items.tsv"] {
+            let path = dir.path().join(file_name);
+            write_item_table(&path, &view)?;
+            let read = read_item_table(&path)?;
+
+            assert_eq!(serde_yaml::to_string(&read)?, serde_yaml::to_string(&view)?);
+        }
+
+        let csv = fs::read_to_string(dir.path().join("items.csv"))?;
+        assert!(csv.contains(",Excalipoor,CanEquip|Rare,"));
+        assert!(csv.contains(",WAR|PLD,"));
+        assert!(write_item_table(&dir.path().join("items.txt"), &view).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn import_into_raw_data() -> Result<()> {
+        let work_dir = tempfile::tempdir()?;
+        let fixture = FixtureInstall::new()?;
+        let dat_context = fixture.dat_context.clone();
+        let raw_data_root = work_dir.path().join("raw_data");
+        let table_path = work_dir.path().join("weapons.csv");
+
+        DatDescriptor::Weapons.export_item_table(&dat_context, None, table_path.clone())?;
+        let csv = fs::read_to_string(&table_path)?.replace(",240,", ",200,");
+        fs::write(&table_path, csv)?;
+
+        DatDescriptor::Weapons.import_item_table(
+            &dat_context,
+            raw_data_root.clone(),
+            table_path.clone(),
+            RawDataFormat::Yaml,
+        )?;
+        let yaml_path = raw_data_root.join("items/weapons.yml");
+        let yaml = fs::read_to_string(&yaml_path)?;
+        assert!(yaml.contains("delay: 200"));
+        assert!(yaml.contains("icon_bytes: kQABAgM"));
+
+        // Exports prefer the edited YAML over the DAT.
+        DatDescriptor::Weapons.export_item_table(
+            &dat_context,
+            Some(raw_data_root),
+            table_path.clone(),
+        )?;
+        assert!(fs::read_to_string(&table_path)?.contains(",200,"));
+
+        assert!(DatDescriptor::Dialog(1)
+            .export_item_table(&dat_context, None, table_path)
+            .is_err());
+
+        Ok(())
+    }
+}
@@ -1,3 +1,15 @@
 mod converters;
 pub mod dat_descriptor;
+pub mod diff;
+pub mod install_diff;
+pub mod item_table;
+pub mod merge;
 pub mod processor;
+pub mod project;
+pub mod raw_data_format;
+pub mod search;
+pub mod string_index;
+pub mod translation;
+pub mod validation;
+pub mod verification;
+pub mod xref;
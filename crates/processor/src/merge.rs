@@ -0,0 +1,264 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use anyhow::{anyhow, Result};
+use dats::entries::DatEntries;
+use serde::Serialize;
+use serde_yaml::{Mapping, Value};
+
+/// Keys of the mapping which replaces a conflicting entry or field in the merged YAML.
+/// Files with conflicts can't be converted, until each marked value is replaced by one of the
+/// three sides or a combination of them.
+pub const CONFLICT_OURS: &str = "<<<<<<< ours";
+pub const CONFLICT_BASE: &str = "||||||| base";
+pub const CONFLICT_THEIRS: &str = ">>>>>>> theirs";
+
+/// Result of merging our edits of a DAT with the changes of another version of it, relative
+/// to the version both started out from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DatMerge {
+    pub merged: Value,
+    /// Where the conflicts were marked, as `<entries field>.<key>` or the name of another field.
+    pub conflicts: Vec<String>,
+}
+
+impl DatMerge {
+    /// Merges entry by entry, taking over the changes of whichever side changed an entry.
+    /// Other fields of the format are merged as a whole.
+    pub fn between<T: DatEntries + Serialize>(base: &T, ours: &T, theirs: &T) -> Result<Self> {
+        let [base_value, ours_value, theirs_value] = [base, ours, theirs].map(to_mapping);
+        let (base_value, ours_value, theirs_value) = (base_value?, ours_value?, theirs_value?);
+
+        let mut conflicts = vec![];
+        let mut merged = Mapping::new();
+        for field in ours_value
+            .keys()
+            .chain(theirs_value.keys())
+            .filter(|field| field.as_str() != Some(T::ENTRIES_FIELD))
+        {
+            if merged.contains_key(field) {
+                continue;
+            }
+
+            let value = merge_values(
+                base_value.get(field),
+                ours_value.get(field),
+                theirs_value.get(field),
+            )
+            .unwrap_or_else(|conflict| {
+                conflicts.push(field.as_str().unwrap_or_default().to_string());
+                Some(conflict)
+            });
+            if let Some(value) = value {
+                merged.insert(field.clone(), value);
+            }
+        }
+
+        let [base_entries, ours_entries, theirs_entries] = [base, ours, theirs].map(|dat| {
+            dat.entries()
+                .into_iter()
+                .map(|(key, entry)| Ok((key, serde_yaml::to_value(entry)?)))
+                .collect::<Result<BTreeMap<_, _>>>()
+        });
+        let (base_entries, ours_entries, theirs_entries) =
+            (base_entries?, ours_entries?, theirs_entries?);
+
+        let keys = ours_entries
+            .keys()
+            .chain(theirs_entries.keys())
+            .chain(base_entries.keys())
+            .copied()
+            .collect::<BTreeSet<_>>();
+        let mut entries = vec![];
+        for key in keys {
+            let entry = merge_values(
+                base_entries.get(&key),
+                ours_entries.get(&key),
+                theirs_entries.get(&key),
+            )
+            .unwrap_or_else(|conflict| {
+                conflicts.push(format!("{}.{}", T::ENTRIES_FIELD, key));
+                Some(conflict)
+            });
+            if let Some(entry) = entry {
+                entries.push((key, entry));
+            }
+        }
+
+        // Entries are written back in the same shape as the format serializes them.
+        let entries = match ours_value.get(T::ENTRIES_FIELD) {
+            Some(Value::Sequence(_)) => {
+                Value::Sequence(entries.into_iter().map(|(_, entry)| entry).collect())
+            }
+            Some(Value::Mapping(_)) => Value::Mapping(
+                entries
+                    .into_iter()
+                    .map(|(key, entry)| (Value::from(key), entry))
+                    .collect(),
+            ),
+            _ => {
+                return Err(anyhow!(
+                    "Expected the entries in the field '{}'.",
+                    T::ENTRIES_FIELD
+                ))
+            }
+        };
+        merged.insert(Value::from(T::ENTRIES_FIELD), entries);
+
+        Ok(DatMerge {
+            merged: Value::Mapping(merged),
+            conflicts,
+        })
+    }
+
+    pub fn has_conflicts(&self) -> bool {
+        !self.conflicts.is_empty()
+    }
+}
+
+fn to_mapping<T: Serialize>(data: &T) -> Result<Mapping> {
+    match serde_yaml::to_value(data)? {
+        Value::Mapping(mapping) => Ok(mapping),
+        _ => Err(anyhow!(
+            "Only formats serialized as a mapping can be merged."
+        )),
+    }
+}
+
+/// Three-way merge of a single value, where `None` stands for a value that doesn't exist on
+/// that side. Returns the conflict marker if both sides changed the value differently.
+fn merge_values(
+    base: Option<&Value>,
+    ours: Option<&Value>,
+    theirs: Option<&Value>,
+) -> Result<Option<Value>, Value> {
+    if ours == theirs || theirs == base {
+        Ok(ours.cloned())
+    } else if ours == base {
+        Ok(theirs.cloned())
+    } else {
+        let side = |value: Option<&Value>| value.cloned().unwrap_or(Value::Null);
+        Err(Value::Mapping(Mapping::from_iter([
+            (Value::from(CONFLICT_OURS), side(ours)),
+            (Value::from(CONFLICT_BASE), side(base)),
+            (Value::from(CONFLICT_THEIRS), side(theirs)),
+        ])))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use anyhow::Result;
+    use dats::{
+        dat_format::DatFormat,
+        fixtures::FixtureInstall,
+        formats::{dialog::Dialog, item_info::ItemInfoTable},
+    };
+    use serde_yaml::Value;
+
+    use crate::{dat_descriptor::DatDescriptor, raw_data_format::RawDataFormat};
+
+    use super::{DatMerge, CONFLICT_BASE, CONFLICT_OURS, CONFLICT_THEIRS};
+
+    #[test]
+    fn merge_dialog() -> Result<()> {
+        let base = Dialog::fixture();
+
+        let mut ours = Dialog::fixture();
+        ours.entries.insert(0, "Our welcome.".to_string());
+        ours.entries.insert(1, "Our change.".to_string());
+        ours.entries.insert(10, "Our new entry.".to_string());
+
+        let mut theirs = Dialog::fixture();
+        theirs.entries.insert(1, "Their change.".to_string());
+        theirs.entries.insert(2, "Their other change.".to_string());
+        theirs.entries.insert(11, "Their new entry.".to_string());
+
+        let merge = DatMerge::between(&base, &ours, &theirs)?;
+        assert_eq!(merge.conflicts, vec!["entries.1".to_string()]);
+
+        let entries = &merge.merged["entries"];
+        assert_eq!(entries[0], Value::from("Our welcome."));
+        assert_eq!(entries[2], Value::from("Their other change."));
+        assert_eq!(entries[10], Value::from("Our new entry."));
+        assert_eq!(entries[11], Value::from("Their new entry."));
+        assert_eq!(entries[1][CONFLICT_OURS], Value::from("Our change."));
+        assert_eq!(
+            entries[1][CONFLICT_BASE],
+            Value::from(base.entries[&1].as_str())
+        );
+        assert_eq!(entries[1][CONFLICT_THEIRS], Value::from("Their change."));
+
+        // Without the conflict, the merged YAML is a regular dialog file again.
+        theirs.entries.insert(1, base.entries[&1].clone());
+        let merge = DatMerge::between(&base, &ours, &theirs)?;
+        assert!(!merge.has_conflicts());
+        let merged: Dialog = serde_yaml::from_value(merge.merged)?;
+        assert_eq!(merged.entries[&1], "Our change.");
+        assert_eq!(merged.entries[&11], "Their new entry.");
+
+        Ok(())
+    }
+
+    #[test]
+    fn merge_files_of_descriptor() -> Result<()> {
+        let work_dir = tempfile::tempdir()?;
+        let fixture = FixtureInstall::new()?;
+        let dat_context = fixture.dat_context.clone();
+
+        DatDescriptor::Dialog(1).dat_to_yaml(
+            dat_context.clone(),
+            work_dir.path().to_path_buf(),
+            RawDataFormat::Yaml,
+        )?;
+        let ours_path = work_dir
+            .path()
+            .join(DatDescriptor::Dialog(1).get_relative_path(&dat_context)? + ".yml");
+        let mut ours: Dialog = serde_yaml::from_str(&fs::read_to_string(&ours_path)?)?;
+        ours.entries.insert(0, "Our welcome.".to_string());
+        fs::write(&ours_path, serde_yaml::to_string(&ours)?)?;
+
+        let mut theirs = Dialog::fixture();
+        theirs.entries.insert(1, "Their change.".to_string());
+        let theirs_path = work_dir.path().join("theirs.DAT");
+        fs::write(&theirs_path, theirs.to_bytes()?)?;
+
+        let base_path = DatDescriptor::Dialog(1).dat_path(&dat_context)?;
+        let merge = DatDescriptor::Dialog(1).merge(
+            base_path,
+            ours_path.clone(),
+            theirs_path,
+            ours_path.clone(),
+        )?;
+        assert!(!merge.has_conflicts());
+
+        let merged: Dialog = serde_yaml::from_str(&fs::read_to_string(&ours_path)?)?;
+        assert_eq!(merged.entries[&0], "Our welcome.");
+        assert_eq!(merged.entries[&1], "Their change.");
+
+        Ok(())
+    }
+
+    #[test]
+    fn merge_removed_items() -> Result<()> {
+        let base = ItemInfoTable::fixture();
+        let ours = ItemInfoTable::fixture();
+        let theirs: ItemInfoTable = serde_yaml::from_value({
+            let mut value = serde_yaml::to_value(&base)?;
+            value["items"].as_sequence_mut().unwrap().pop();
+            value
+        })?;
+
+        let merge = DatMerge::between(&base, &ours, &theirs)?;
+        assert!(!merge.has_conflicts());
+        assert_eq!(
+            merge.merged["items"].as_sequence().map(Vec::len),
+            serde_yaml::to_value(&theirs)?["items"]
+                .as_sequence()
+                .map(Vec::len)
+        );
+
+        Ok(())
+    }
+}
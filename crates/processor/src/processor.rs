@@ -1,9 +1,12 @@
 use std::{
-    path::PathBuf,
+    path::{Path, PathBuf},
     sync::{mpsc::Sender, Arc, Mutex},
 };
 
-use crate::dat_descriptor::DatDescriptor;
+use crate::{
+    dat_descriptor::DatDescriptor, raw_data_format::RawDataFormat, validation::InvalidStrings,
+};
+use anyhow::Result;
 use dats::context::DatContext;
 use serde::{Deserialize, Serialize};
 use threadpool::ThreadPool;
@@ -26,6 +29,19 @@ pub struct DatProcessorMessage {
 pub enum DatProcessorOutputKind {
     Dat,
     Yaml,
+    Json,
+    Ron,
+    RoundTrip,
+}
+
+impl From<RawDataFormat> for DatProcessorOutputKind {
+    fn from(format: RawDataFormat) -> Self {
+        match format {
+            RawDataFormat::Yaml => DatProcessorOutputKind::Yaml,
+            RawDataFormat::Json => DatProcessorOutputKind::Json,
+            RawDataFormat::Ron => DatProcessorOutputKind::Ron,
+        }
+    }
 }
 
 #[derive(Debug, Clone, specta::Type, Serialize, Deserialize)]
@@ -53,11 +69,12 @@ impl DatProcessor {
         dat_descriptor: DatDescriptor,
         dat_context: Arc<DatContext>,
         raw_data_root_path: PathBuf,
+        format: RawDataFormat,
     ) {
         let tx = self.tx.clone();
         let start_message = DatProcessorMessage {
             dat_descriptor,
-            output_kind: DatProcessorOutputKind::Yaml,
+            output_kind: format.into(),
             state: DatProcessingState::Working,
         };
         if let Err(err) = tx.send(start_message) {
@@ -66,15 +83,15 @@ impl DatProcessor {
 
         self.pool.lock().unwrap().execute(move || {
             let res = dat_descriptor
-                .dat_to_yaml(dat_context, raw_data_root_path)
+                .dat_to_yaml(dat_context, raw_data_root_path, format)
                 .map(|path| DatProcessorMessage {
                     dat_descriptor,
-                    output_kind: DatProcessorOutputKind::Yaml,
+                    output_kind: format.into(),
                     state: DatProcessingState::Finished(path),
                 })
                 .unwrap_or_else(|err| DatProcessorMessage {
                     dat_descriptor,
-                    output_kind: DatProcessorOutputKind::Yaml,
+                    output_kind: format.into(),
                     state: DatProcessingState::Error(err.to_string()),
                 });
 
@@ -102,25 +119,105 @@ impl DatProcessor {
         }
 
         self.pool.lock().unwrap().execute(move || {
-            let res: DatProcessorMessage = dat_descriptor
-                .yaml_to_dat(dat_context, raw_data_root_path, dat_root_path)
+            let res: DatProcessorMessage =
+                Self::validate_strings(dat_descriptor, &dat_context, &raw_data_root_path)
+                    .and_then(|_| {
+                        dat_descriptor.yaml_to_dat(dat_context, raw_data_root_path, dat_root_path)
+                    })
+                    .map(|path| DatProcessorMessage {
+                        dat_descriptor,
+                        output_kind: DatProcessorOutputKind::Dat,
+                        state: DatProcessingState::Finished(path),
+                    })
+                    .unwrap_or_else(|err| DatProcessorMessage {
+                        dat_descriptor,
+                        output_kind: DatProcessorOutputKind::Dat,
+                        state: DatProcessingState::Error(err.to_string()),
+                    });
+
+            if let Err(err) = tx.send(res) {
+                eprintln!("Failed to notify about YAML to DAT result: {err}");
+            }
+        });
+    }
+
+    /// Pre-flight check of the strings in a DAT's raw data, so every problem in them gets
+    /// reported at once instead of only the first one the encoder fails on.
+    fn validate_strings(
+        dat_descriptor: DatDescriptor,
+        dat_context: &DatContext,
+        raw_data_root_path: &Path,
+    ) -> Result<()> {
+        let dat_descriptor = dat_descriptor.view_base().unwrap_or(dat_descriptor);
+        let problems = dat_descriptor.validate_strings(dat_context, raw_data_root_path)?;
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(InvalidStrings { problems }.into())
+        }
+    }
+
+    pub fn check_roundtrip(&self, dat_descriptor: DatDescriptor, dat_context: Arc<DatContext>) {
+        let tx = self.tx.clone();
+        let start_message = DatProcessorMessage {
+            dat_descriptor,
+            output_kind: DatProcessorOutputKind::RoundTrip,
+            state: DatProcessingState::Working,
+        };
+        if let Err(err) = tx.send(start_message) {
+            eprintln!("Failed to notify about round-trip check start: {err}");
+        }
+
+        self.pool.lock().unwrap().execute(move || {
+            let res = dat_descriptor
+                .check_roundtrip(dat_context)
                 .map(|path| DatProcessorMessage {
                     dat_descriptor,
-                    output_kind: DatProcessorOutputKind::Dat,
+                    output_kind: DatProcessorOutputKind::RoundTrip,
                     state: DatProcessingState::Finished(path),
                 })
                 .unwrap_or_else(|err| DatProcessorMessage {
                     dat_descriptor,
-                    output_kind: DatProcessorOutputKind::Dat,
+                    output_kind: DatProcessorOutputKind::RoundTrip,
                     state: DatProcessingState::Error(err.to_string()),
                 });
 
             if let Err(err) = tx.send(res) {
-                eprintln!("Failed to notify about YAML to DAT result: {err}");
+                eprintln!("Failed to notify about round-trip check result: {err}");
             }
         });
     }
 
+    /// Queues every supported DAT to be converted to raw data in the given format, including
+    /// every zone whose DAT matches the expected format. Returns the amount of queued DATs.
+    pub fn all_dats_to_yaml(
+        &mut self,
+        dat_context: Arc<DatContext>,
+        raw_data_root_path: &Path,
+        format: RawDataFormat,
+    ) -> usize {
+        self.is_preprocessing = true;
+
+        let mut dat_descriptors = DatDescriptor::all_standalone();
+        dat_descriptors.extend(DatDescriptor::all_quests(&dat_context));
+        dat_descriptors.extend(DatDescriptor::all_zoned(&dat_context));
+
+        let count = dat_descriptors.len();
+        for dat_descriptor in dat_descriptors {
+            self.dat_to_yaml(
+                dat_descriptor,
+                dat_context.clone(),
+                raw_data_root_path.to_path_buf(),
+                format,
+            );
+        }
+
+        self.is_preprocessing = false;
+        count
+    }
+
+    /// Queues a DAT to be generated for every raw data file in the given directory, which
+    /// may be in any of the supported formats. Returns the amount of queued DATs.
     pub fn all_yaml_to_dats(
         &mut self,
         dat_context: Arc<DatContext>,
@@ -129,8 +226,7 @@ impl DatProcessor {
     ) -> usize {
         self.is_preprocessing = true;
 
-        let mut count = 0;
-
+        let mut dat_descriptors = vec![];
         walkdir::WalkDir::new(&in_dir)
             .into_iter()
             .filter_map(|entry| {
@@ -151,20 +247,361 @@ impl DatProcessor {
                 dat_descriptor
             })
             .for_each(|dat_descriptor| {
-                let dat_context = dat_context.clone();
-                let raw_data_root_path = in_dir.clone();
-                let dat_root_path = out_dir.clone();
-                count += 1;
-
-                self.yaml_to_dat(
-                    dat_descriptor,
-                    dat_context,
-                    raw_data_root_path,
-                    dat_root_path,
-                );
+                // Views are generated together with the DAT they're part of.
+                let dat_descriptor = dat_descriptor.view_base().unwrap_or(dat_descriptor);
+                if !dat_descriptors.contains(&dat_descriptor) {
+                    dat_descriptors.push(dat_descriptor);
+                }
             });
 
+        let count = dat_descriptors.len();
+        for dat_descriptor in dat_descriptors {
+            self.yaml_to_dat(
+                dat_descriptor,
+                dat_context.clone(),
+                in_dir.clone(),
+                out_dir.clone(),
+            );
+        }
+
         self.is_preprocessing = false;
         count
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf, sync::mpsc::channel};
+
+    use anyhow::{anyhow, Result};
+    use dats::{
+        dat_format::DatFormat,
+        fixtures::FixtureInstall,
+        formats::{event::EventFile, menu_table::MenuTable},
+        views::{spell_info::SpellInfoView, DatView},
+    };
+
+    use crate::{dat_descriptor::DatDescriptor, raw_data_format::RawDataFormat};
+
+    use super::{DatProcessingState, DatProcessor, DatProcessorMessage};
+
+    fn wait_for_results(
+        rx: &std::sync::mpsc::Receiver<DatProcessorMessage>,
+        count: usize,
+    ) -> Result<Vec<PathBuf>> {
+        let mut paths = vec![];
+        while paths.len() < count {
+            let message = rx.recv()?;
+            match message.state {
+                DatProcessingState::Working => {}
+                DatProcessingState::Finished(path) => paths.push(path),
+                DatProcessingState::Error(err) => {
+                    return Err(anyhow!("{:?} failed: {}", message.dat_descriptor, err))
+                }
+            }
+        }
+
+        Ok(paths)
+    }
+
+    #[test]
+    fn zoned_descriptors_from_fake_install() -> Result<()> {
+        let fixture = FixtureInstall::new()?;
+        let dat_context = fixture.dat_context.clone();
+
+        assert_eq!(
+            DatDescriptor::all_zoned(&dat_context),
+            vec![
+                DatDescriptor::EntityNames(1),
+                DatDescriptor::Dialog(1),
+                DatDescriptor::Dialog(2),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn fake_install_roundtrip() -> Result<()> {
+        let raw_data_dir = tempfile::tempdir()?;
+        let dat_dir = tempfile::tempdir()?;
+
+        let fixture = FixtureInstall::new()?;
+        let dat_context = fixture.dat_context.clone();
+
+        let dat_descriptors = [
+            DatDescriptor::DataMenu,
+            DatDescriptor::AbilityNames,
+            DatDescriptor::AbilityDescriptions,
+            DatDescriptor::AreaNames,
+            DatDescriptor::DayNames,
+            DatDescriptor::PolMessages,
+            DatDescriptor::SpellNames,
+            DatDescriptor::SpellDescriptions,
+            DatDescriptor::StatusInfo,
+            DatDescriptor::TimeAndPronouns,
+            DatDescriptor::Weapons,
+            DatDescriptor::MonsterSkillNames,
+            DatDescriptor::EntityNames(1),
+            DatDescriptor::Dialog(1),
+            DatDescriptor::Dialog(2),
+        ];
+
+        let (tx, rx) = channel();
+        let mut processor = DatProcessor::new(tx);
+
+        for dat_descriptor in dat_descriptors {
+            processor.dat_to_yaml(
+                dat_descriptor,
+                dat_context.clone(),
+                raw_data_dir.path().to_path_buf(),
+                RawDataFormat::Yaml,
+            );
+        }
+        wait_for_results(&rx, dat_descriptors.len())?;
+
+        let count = processor.all_yaml_to_dats(
+            dat_context.clone(),
+            &raw_data_dir.path().to_path_buf(),
+            &dat_dir.path().to_path_buf(),
+        );
+        assert_eq!(count, dat_descriptors.len());
+        wait_for_results(&rx, count)?;
+
+        for (dat_id, dat_path) in fixture.install.dat_paths() {
+            let generated = fs::read(dat_dir.path().join(dat_path.to_path()))?;
+            assert_eq!(
+                Some(generated.as_slice()),
+                fixture.install.get_bytes(dat_id),
+                "{:?}",
+                dat_id
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn sub_dirs_match_relative_paths() -> Result<()> {
+        let fixture = FixtureInstall::new()?;
+        let dat_context = fixture.dat_context.clone();
+
+        let mut dat_descriptors = DatDescriptor::all_standalone();
+        dat_descriptors.extend(DatDescriptor::all_zoned(&dat_context));
+        dat_descriptors.push(DatDescriptor::QuestInfo(9));
+        for dat_descriptor in dat_descriptors {
+            let path = PathBuf::from(dat_descriptor.get_relative_path(&dat_context)?);
+            let sub_dir = path.parent().and_then(|parent| parent.to_str());
+            assert_eq!(
+                dat_descriptor.sub_dir(),
+                sub_dir.filter(|sub_dir| !sub_dir.is_empty()),
+                "{:?}",
+                dat_descriptor
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn quest_descriptors() -> Result<()> {
+        let fixture = FixtureInstall::new()?;
+        let dat_context = fixture.dat_context.clone();
+
+        let dat_descriptor = DatDescriptor::QuestInfo(9);
+        let path = dat_descriptor.get_relative_path(&dat_context)?;
+        assert_eq!(path, "quests/9");
+        assert_eq!(
+            DatDescriptor::from_path(&PathBuf::from(path + ".yml"), &PathBuf::new(), &dat_context),
+            Some(dat_descriptor)
+        );
+
+        assert!(DatDescriptor::QuestInfo(9)
+            .dat_to_yaml(dat_context.clone(), PathBuf::new(), RawDataFormat::Yaml)
+            .is_err());
+        assert!(DatDescriptor::all_quests(&dat_context).is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn event_descriptors() -> Result<()> {
+        let fixture = FixtureInstall::new()?;
+        let dat_context = fixture.dat_context.clone();
+
+        let dat_descriptor = DatDescriptor::Events(1);
+        let path = dat_descriptor.get_relative_path(&dat_context)?;
+        assert!(path.starts_with("events/"));
+        assert_eq!(
+            DatDescriptor::from_path(&PathBuf::from(path + ".yml"), &PathBuf::new(), &dat_context),
+            Some(dat_descriptor)
+        );
+
+        // The view refers to the zone's entity names and dialog.
+        let events = EventFile::from_base(&EventFile::fixture(), &dat_context)?;
+        assert_eq!(events.blocks[0].actor_name.as_deref(), Some("Test Guard"));
+
+        let mut edited = EventFile::fixture();
+        events.apply_to(&mut edited)?;
+        assert_eq!(edited.to_bytes()?, EventFile::fixture().to_bytes()?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn edited_spell_info() -> Result<()> {
+        let raw_data_dir = tempfile::tempdir()?;
+        let dat_dir = tempfile::tempdir()?;
+
+        let fixture = FixtureInstall::new()?;
+        let dat_context = fixture.dat_context.clone();
+
+        for dat_descriptor in [DatDescriptor::SpellInfo, DatDescriptor::AbilityInfo] {
+            dat_descriptor.check_roundtrip(dat_context.clone())?;
+            dat_descriptor.dat_to_yaml(
+                dat_context.clone(),
+                raw_data_dir.path().to_path_buf(),
+                RawDataFormat::Yaml,
+            )?;
+        }
+
+        let spell_info_path = raw_data_dir.path().join("spell_info.yml");
+        let mut spell_info: SpellInfoView =
+            serde_yaml::from_reader(fs::File::open(&spell_info_path)?)?;
+        assert_eq!(spell_info.spells[&1].name.as_deref(), Some("Test pass"));
+        spell_info.spells.get_mut(&3).unwrap().info.mp_cost = 99;
+        serde_yaml::to_writer(fs::File::create(&spell_info_path)?, &spell_info)?;
+
+        let (tx, rx) = channel();
+        let mut processor = DatProcessor::new(tx);
+        let count = processor.all_yaml_to_dats(
+            dat_context.clone(),
+            &raw_data_dir.path().to_path_buf(),
+            &dat_dir.path().to_path_buf(),
+        );
+        assert_eq!(count, 1);
+        let paths = wait_for_results(&rx, count)?;
+
+        let mut expected = MenuTable::fixture();
+        expected.spells_mut().nth(2).unwrap().mp_cost = 99;
+        assert_eq!(fs::read(&paths[0])?, expected.to_bytes()?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn spell_info_keeps_menu_edits() -> Result<()> {
+        let raw_data_dir = tempfile::tempdir()?;
+        let dat_dir = tempfile::tempdir()?;
+
+        let fixture = FixtureInstall::new()?;
+        let dat_context = fixture.dat_context.clone();
+
+        for dat_descriptor in [DatDescriptor::DataMenu, DatDescriptor::SpellInfo] {
+            dat_descriptor.dat_to_yaml(
+                dat_context.clone(),
+                raw_data_dir.path().to_path_buf(),
+                RawDataFormat::Yaml,
+            )?;
+        }
+
+        let menu_path = raw_data_dir.path().join("data_menu.yml");
+        let mut menu: MenuTable = serde_yaml::from_reader(fs::File::open(&menu_path)?)?;
+        menu.spells_mut().nth(1).unwrap().mp_cost = 77;
+        serde_yaml::to_writer(fs::File::create(&menu_path)?, &menu)?;
+
+        let spell_info_path = raw_data_dir.path().join("spell_info.yml");
+        let mut spell_info: SpellInfoView =
+            serde_yaml::from_reader(fs::File::open(&spell_info_path)?)?;
+        spell_info.spells.get_mut(&3).unwrap().info.mp_cost = 99;
+        serde_yaml::to_writer(fs::File::create(&spell_info_path)?, &spell_info)?;
+
+        let dat_path = DatDescriptor::DataMenu.yaml_to_dat(
+            dat_context.clone(),
+            raw_data_dir.path().to_path_buf(),
+            dat_dir.path().to_path_buf(),
+        )?;
+
+        let mut expected = MenuTable::fixture();
+        expected.spells_mut().nth(1).unwrap().mp_cost = 77;
+        expected.spells_mut().nth(2).unwrap().mp_cost = 99;
+        assert_eq!(fs::read(&dat_path)?, expected.to_bytes()?);
+
+        // The same spell edited differently in both files can't be combined.
+        menu.spells_mut().nth(2).unwrap().mp_cost = 50;
+        serde_yaml::to_writer(fs::File::create(&menu_path)?, &menu)?;
+
+        assert!(DatDescriptor::DataMenu
+            .yaml_to_dat(
+                dat_context,
+                raw_data_dir.path().to_path_buf(),
+                dat_dir.path().to_path_buf(),
+            )
+            .is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_strings_fail_only_their_dat() -> Result<()> {
+        let raw_data_dir = tempfile::tempdir()?;
+        let dat_dir = tempfile::tempdir()?;
+        let fixture = FixtureInstall::new()?;
+        let dat_context = fixture.dat_context.clone();
+
+        let (tx, rx) = channel();
+        let mut processor = DatProcessor::new(tx);
+        for dat_descriptor in [DatDescriptor::Dialog(1), DatDescriptor::Dialog(2)] {
+            processor.dat_to_yaml(
+                dat_descriptor,
+                dat_context.clone(),
+                raw_data_dir.path().to_path_buf(),
+                RawDataFormat::Yaml,
+            );
+        }
+        wait_for_results(&rx, 2)?;
+
+        let raw_data_path = RawDataFormat::Yaml.path_in(
+            raw_data_dir.path(),
+            &DatDescriptor::Dialog(1).get_relative_path(&dat_context)?,
+        );
+        let yaml = fs::read_to_string(&raw_data_path)?;
+        fs::write(
+            &raw_data_path,
+            yaml.replace("${name-player}", "${playr}")
+                .replace("${prompt}", "${prompt: 1}"),
+        )?;
+
+        let count = processor.all_yaml_to_dats(
+            dat_context.clone(),
+            &raw_data_dir.path().to_path_buf(),
+            &dat_dir.path().to_path_buf(),
+        );
+        assert_eq!(count, 2);
+
+        let mut finished = 0;
+        let mut errors = vec![];
+        while finished + errors.len() < count {
+            let message = rx.recv()?;
+            match message.state {
+                DatProcessingState::Working => {}
+                DatProcessingState::Finished(_) => finished += 1,
+                DatProcessingState::Error(err) => errors.push((message.dat_descriptor, err)),
+            }
+        }
+
+        assert_eq!(finished, 1);
+        assert_eq!(errors.len(), 1);
+        let (dat_descriptor, err) = &errors[0];
+        assert_eq!(*dat_descriptor, DatDescriptor::Dialog(1));
+        assert!(err.contains("Unknown tag 'playr'"), "{}", err);
+        assert!(err.contains("Tag 'prompt' takes no parameters"), "{}", err);
+        assert!(
+            err.contains(&raw_data_path.display().to_string()),
+            "{}",
+            err
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,94 @@
+use std::{
+    collections::HashMap,
+    fs::{self, File},
+    path::Path,
+};
+
+use anyhow::{anyhow, Result};
+use dats::{
+    base::ZoneId,
+    context::{DatContext, ZoneName},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::raw_data_format::RawDataFormat;
+
+pub const RAW_DATA_DIR: &str = "raw_data";
+pub const LOOKUP_TABLE_DIR: &str = "lookup_tables";
+pub const DAT_GENERATION_DIR: &str = "generated_dats";
+pub const ZONE_MAPPING_FILE: &str = "zones.yml";
+pub const PROJECT_SETTINGS_FILE: &str = "project.yml";
+
+/// Settings stored in the root of a project, which default for projects without them.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProjectSettings {
+    /// Format that DATs are converted into.
+    #[serde(default)]
+    pub raw_data_format: RawDataFormat,
+}
+
+impl ProjectSettings {
+    pub fn load(project_path: &Path) -> Result<Self> {
+        let settings_path = project_path.join(PROJECT_SETTINGS_FILE);
+        if !settings_path.exists() {
+            return Ok(ProjectSettings::default());
+        }
+
+        let settings_file = File::open(settings_path)
+            .map_err(|err| anyhow!("Unable to open project settings: {}", err))?;
+        serde_yaml::from_reader(settings_file)
+            .map_err(|err| anyhow!("Unable to read project settings: {}", err))
+    }
+
+    pub fn save(&self, project_path: &Path) -> Result<()> {
+        fs::create_dir_all(project_path)?;
+        let settings_file = File::create(project_path.join(PROJECT_SETTINGS_FILE))
+            .map_err(|err| anyhow!("Unable to create project settings: {}", err))?;
+
+        Ok(serde_yaml::to_writer(settings_file, self)?)
+    }
+}
+
+/// Loads the zone mappings stored in the lookup table directory of a project.
+pub fn load_zone_mappings(project_path: &Path) -> Result<HashMap<ZoneId, ZoneName>> {
+    let zone_map_file = project_path.join(LOOKUP_TABLE_DIR).join(ZONE_MAPPING_FILE);
+    let zone_file = File::open(zone_map_file)
+        .map_err(|err| anyhow!("Unable to open zone mapping file: {}", err))?;
+
+    serde_yaml::from_reader(zone_file)
+        .map_err(|err| anyhow!("Unable to read zone mapping file: {}", err))
+}
+
+/// Creates a DAT context from the lookup tables stored in a project,
+/// which doesn't require an FFXI install to be present.
+pub fn load_project_dat_context(project_path: &Path) -> Result<DatContext> {
+    let zones_mapping = load_zone_mappings(project_path)?;
+
+    DatContext::from_path_and_zone_mappings(project_path.join(LOOKUP_TABLE_DIR), zones_mapping)
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use crate::raw_data_format::RawDataFormat;
+
+    use super::ProjectSettings;
+
+    #[test]
+    fn settings_roundtrip() -> Result<()> {
+        let project_dir = tempfile::tempdir()?;
+        assert_eq!(
+            ProjectSettings::load(project_dir.path())?,
+            ProjectSettings::default()
+        );
+
+        let settings = ProjectSettings {
+            raw_data_format: RawDataFormat::Json,
+        };
+        settings.save(project_dir.path())?;
+        assert_eq!(ProjectSettings::load(project_dir.path())?, settings);
+
+        Ok(())
+    }
+}
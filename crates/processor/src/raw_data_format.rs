@@ -0,0 +1,218 @@
+use std::{
+    fmt::Display,
+    io::{Read, Write},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use anyhow::{anyhow, Result};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+/// Serialization format of the raw data files, which is selected per project.
+/// Files are read in the format of their extension, so a project can hold files of any of them.
+///
+/// TOML isn't supported, as its tables only have string keys and no null values, while most
+/// formats key their entries by ID and have optional fields.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, specta::Type, Serialize, Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum RawDataFormat {
+    #[default]
+    Yaml,
+    Json,
+    Ron,
+}
+
+impl Display for RawDataFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RawDataFormat::Yaml => write!(f, "YAML"),
+            RawDataFormat::Json => write!(f, "JSON"),
+            RawDataFormat::Ron => write!(f, "RON"),
+        }
+    }
+}
+
+impl FromStr for RawDataFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        RawDataFormat::from_extension(&str.to_lowercase())
+            .ok_or_else(|| anyhow!("Unknown raw data format: {}", str))
+    }
+}
+
+impl RawDataFormat {
+    pub const ALL: [RawDataFormat; 3] =
+        [RawDataFormat::Yaml, RawDataFormat::Json, RawDataFormat::Ron];
+
+    /// The extension of files written in this format.
+    pub fn extension(self) -> &'static str {
+        match self {
+            RawDataFormat::Yaml => "yml",
+            RawDataFormat::Json => "json",
+            RawDataFormat::Ron => "ron",
+        }
+    }
+
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension {
+            "yml" | "yaml" => Some(RawDataFormat::Yaml),
+            "json" => Some(RawDataFormat::Json),
+            "ron" => Some(RawDataFormat::Ron),
+            _ => None,
+        }
+    }
+
+    /// The format of a raw data file, by its extension.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        path.extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(RawDataFormat::from_extension)
+    }
+
+    /// Path of the file in this format for the given path relative to the raw data directory.
+    pub fn path_in(self, raw_data_root_path: &Path, relative_path: &str) -> PathBuf {
+        raw_data_root_path.join(format!("{}.{}", relative_path, self.extension()))
+    }
+
+    /// Finds the existing file for the given relative path, in any of the formats.
+    /// Having files in several formats is an error, as it's unclear which one is edited.
+    pub fn find_existing(
+        raw_data_root_path: &Path,
+        relative_path: &str,
+    ) -> Result<Option<PathBuf>> {
+        let mut paths = ["yml", "yaml", "json", "ron"]
+            .into_iter()
+            .map(|extension| raw_data_root_path.join(format!("{}.{}", relative_path, extension)))
+            .filter(|path| path.exists());
+
+        match (paths.next(), paths.next()) {
+            (Some(first), Some(second)) => Err(anyhow!(
+                "Both {} and {} exist, only one of them may be kept.",
+                first.display(),
+                second.display()
+            )),
+            (path, _) => Ok(path),
+        }
+    }
+
+    pub fn write<T: Serialize>(self, writer: impl Write, data: &T) -> Result<()> {
+        match self {
+            RawDataFormat::Yaml => serde_yaml::to_writer(writer, data)?,
+            RawDataFormat::Json => serde_json::to_writer_pretty(writer, data)?,
+            RawDataFormat::Ron => ron::Options::default().to_io_writer_pretty(
+                writer,
+                data,
+                ron::ser::PrettyConfig::default(),
+            )?,
+        }
+
+        Ok(())
+    }
+
+    pub fn read<T: DeserializeOwned>(self, reader: impl Read) -> Result<T> {
+        Ok(match self {
+            RawDataFormat::Yaml => serde_yaml::from_reader(reader)?,
+            RawDataFormat::Json => serde_json::from_reader(reader)?,
+            RawDataFormat::Ron => ron::de::from_reader(reader)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf};
+
+    use anyhow::Result;
+    use dats::fixtures::FixtureInstall;
+
+    use crate::dat_descriptor::DatDescriptor;
+
+    use super::RawDataFormat;
+
+    #[test]
+    fn fake_install_roundtrip_in_each_format() -> Result<()> {
+        let fixture = FixtureInstall::new()?;
+        let dat_context = fixture.dat_context.clone();
+
+        let mut dat_descriptors = DatDescriptor::all_standalone();
+        dat_descriptors.extend(DatDescriptor::all_zoned(&dat_context));
+        let dat_descriptors = dat_descriptors
+            .into_iter()
+            .filter(|dat_descriptor| dat_descriptor.check_roundtrip(dat_context.clone()).is_ok())
+            .collect::<Vec<_>>();
+        assert!(dat_descriptors.len() > 10);
+
+        for format in RawDataFormat::ALL {
+            let raw_data_dir = tempfile::tempdir()?;
+            let dat_dir = tempfile::tempdir()?;
+
+            for dat_descriptor in &dat_descriptors {
+                dat_descriptor.dat_to_yaml(
+                    dat_context.clone(),
+                    raw_data_dir.path().to_path_buf(),
+                    format,
+                )?;
+                let relative_path = dat_descriptor.get_relative_path(&dat_context)?;
+                let raw_data_path = format.path_in(raw_data_dir.path(), &relative_path);
+                assert!(raw_data_path.exists(), "{}", raw_data_path.display());
+                assert_eq!(
+                    DatDescriptor::from_path(
+                        &raw_data_path,
+                        &raw_data_dir.path().to_path_buf(),
+                        &dat_context
+                    ),
+                    Some(*dat_descriptor)
+                );
+
+                let dat_path = dat_descriptor.yaml_to_dat(
+                    dat_context.clone(),
+                    raw_data_dir.path().to_path_buf(),
+                    dat_dir.path().to_path_buf(),
+                )?;
+                assert_eq!(
+                    fs::read(&dat_path)?,
+                    fs::read(dat_descriptor.dat_path(&dat_context)?)?,
+                    "{:?} as {}",
+                    dat_descriptor,
+                    format
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn files_in_several_formats() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        assert_eq!(
+            RawDataFormat::find_existing(dir.path(), "dialog/zone")?,
+            None
+        );
+
+        fs::create_dir_all(dir.path().join("dialog"))?;
+        fs::write(dir.path().join("dialog/zone.json"), "{}")?;
+        assert_eq!(
+            RawDataFormat::find_existing(dir.path(), "dialog/zone")?,
+            Some(dir.path().join("dialog/zone.json"))
+        );
+
+        fs::write(dir.path().join("dialog/zone.yml"), "")?;
+        assert!(RawDataFormat::find_existing(dir.path(), "dialog/zone").is_err());
+
+        assert_eq!(
+            RawDataFormat::from_path(&PathBuf::from("items/weapons.yaml")),
+            Some(RawDataFormat::Yaml)
+        );
+        assert_eq!(
+            RawDataFormat::from_path(&PathBuf::from("items/weapons.toml")),
+            None
+        );
+        assert_eq!("JSON".parse::<RawDataFormat>()?, RawDataFormat::Json);
+
+        Ok(())
+    }
+}
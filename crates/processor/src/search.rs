@@ -0,0 +1,173 @@
+use std::{
+    ops::Range,
+    sync::{mpsc, Arc},
+};
+
+use anyhow::Result;
+use dats::context::DatContext;
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+
+use crate::dat_descriptor::DatDescriptor;
+
+/// Characters shown on each side of a match.
+const SNIPPET_CONTEXT: usize = 30;
+
+/// A string in a DAT which matched a search.
+#[derive(Debug, Clone, PartialEq, Eq, specta::Type, Serialize, Deserialize)]
+pub struct SearchMatch {
+    pub dat_descriptor: DatDescriptor,
+    pub entry_id: u32,
+    /// The matched text with some of the string around it.
+    pub snippet: String,
+}
+
+impl SearchMatch {
+    /// Matches the strings of a DAT, with at most one match for each string.
+    pub(crate) fn find_all(
+        dat_descriptor: DatDescriptor,
+        strings: &[(u32, String)],
+        pattern: &Regex,
+    ) -> Vec<SearchMatch> {
+        strings
+            .iter()
+            .filter_map(|(entry_id, string)| {
+                pattern.find(string).map(|found| SearchMatch {
+                    dat_descriptor,
+                    entry_id: *entry_id,
+                    snippet: SearchMatch::snippet(string, found.range()),
+                })
+            })
+            .collect()
+    }
+
+    pub(crate) fn snippet(string: &str, found: Range<usize>) -> String {
+        let start = string[..found.start]
+            .char_indices()
+            .rev()
+            .nth(SNIPPET_CONTEXT - 1)
+            .map(|(idx, _)| idx)
+            .unwrap_or(0);
+        let end = string[found.end..]
+            .char_indices()
+            .nth(SNIPPET_CONTEXT)
+            .map(|(idx, _)| found.end + idx)
+            .unwrap_or(string.len());
+
+        format!(
+            "{}{}{}",
+            if start > 0 { "..." } else { "" },
+            &string[start..end],
+            if end < string.len() { "..." } else { "" }
+        )
+    }
+}
+
+/// Builds a case-insensitive pattern, which matches the query literally unless it's a regex.
+pub fn search_pattern(query: &str, is_regex: bool) -> Result<Regex> {
+    let pattern = if is_regex {
+        query.to_string()
+    } else {
+        regex::escape(query)
+    };
+
+    Ok(RegexBuilder::new(&pattern).case_insensitive(true).build()?)
+}
+
+/// Every DAT whose strings can be searched.
+pub fn searchable_dats(dat_context: &DatContext) -> Vec<DatDescriptor> {
+    let mut dat_descriptors = DatDescriptor::all_standalone();
+    dat_descriptors.extend(DatDescriptor::all_quests(dat_context));
+    dat_descriptors.extend(DatDescriptor::all_zoned(dat_context));
+    dat_descriptors
+}
+
+/// Searches the strings of the given DATs in parallel. DATs which fail to load are skipped,
+/// and the matches are ordered by DAT descriptor and entry.
+pub fn search_dats(
+    dat_descriptors: Vec<DatDescriptor>,
+    dat_context: Arc<DatContext>,
+    pattern: &Regex,
+) -> Vec<SearchMatch> {
+    let pool = threadpool::Builder::new()
+        .thread_name("dat-search".to_string())
+        .build();
+    let (tx, rx) = mpsc::channel();
+
+    let total_count = dat_descriptors.len();
+    for dat_descriptor in dat_descriptors {
+        let tx = tx.clone();
+        let dat_context = dat_context.clone();
+        let pattern = pattern.clone();
+
+        pool.execute(move || {
+            let matches = dat_descriptor
+                .search_strings(&dat_context, &pattern)
+                .unwrap_or_default();
+            let _ = tx.send(matches);
+        });
+    }
+    drop(tx);
+
+    let mut matches = rx.iter().take(total_count).flatten().collect::<Vec<_>>();
+    matches.sort_by_key(|found| (found.dat_descriptor, found.entry_id));
+
+    matches
+}
+
+#[cfg(test)]
+mod tests {
+
+    use anyhow::Result;
+    use dats::fixtures::FixtureInstall;
+
+    use crate::dat_descriptor::DatDescriptor;
+
+    use super::{search_dats, search_pattern, searchable_dats, SearchMatch};
+
+    #[test]
+    fn snippets() {
+        let string = "a".repeat(40) + "match" + &"b".repeat(40);
+        assert_eq!(
+            SearchMatch::snippet(&string, 40..45),
+            format!("...{}match{}...", "a".repeat(30), "b".repeat(30))
+        );
+        assert_eq!(
+            SearchMatch::snippet("Can ya imagine it", 4..6),
+            "Can ya imagine it"
+        );
+    }
+
+    #[test]
+    fn search_fake_install() -> Result<()> {
+        let fixture = FixtureInstall::new()?;
+        let dat_context = fixture.dat_context.clone();
+
+        let pattern = search_pattern("test", false)?;
+        let matches = search_dats(searchable_dats(&dat_context), dat_context.clone(), &pattern);
+        assert!(matches.contains(&SearchMatch {
+            dat_descriptor: DatDescriptor::Dialog(1),
+            entry_id: 0,
+            snippet: "Welcome to the test zone.".to_string(),
+        }));
+        assert!(matches
+            .iter()
+            .any(|found| found.dat_descriptor == DatDescriptor::EntityNames(1)));
+
+        let pattern = search_pattern(r"^You are feeling \w+\.$", true)?;
+        let matches = search_dats(searchable_dats(&dat_context), dat_context, &pattern);
+        assert_eq!(
+            matches,
+            vec![SearchMatch {
+                dat_descriptor: DatDescriptor::StatusInfo,
+                entry_id: 1,
+                snippet: "You are feeling weak.".to_string(),
+            }]
+        );
+
+        assert!(search_pattern("(unclosed", true).is_err());
+        assert!(search_pattern("(unclosed", false).is_ok());
+
+        Ok(())
+    }
+}
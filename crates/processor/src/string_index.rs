@@ -0,0 +1,307 @@
+use std::{
+    collections::HashMap,
+    fs::{self, File},
+    io::{BufReader, BufWriter},
+    path::{Path, PathBuf},
+    sync::{mpsc, Arc},
+    time::SystemTime,
+};
+
+use anyhow::{anyhow, Result};
+use dats::context::DatContext;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    dat_descriptor::DatDescriptor,
+    search::{searchable_dats, SearchMatch},
+};
+
+pub const STRING_INDEX_FILE: &str = "string_index.json";
+
+/// Decoded strings of every searchable DAT, which only have to be decoded again once their
+/// DAT changes.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StringIndex {
+    ffxi_path: PathBuf,
+    /// The VTABLE/FTABLE files the DAT paths were resolved with.
+    lookup_tables: Vec<FileStamp>,
+    dats: Vec<IndexedDat>,
+    /// Context with the DAT paths of the lookup tables above, if they had to be re-read.
+    #[serde(skip)]
+    reloaded_context: Option<Arc<DatContext>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct FileStamp {
+    path: PathBuf,
+    size: u64,
+    modified: SystemTime,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct IndexedDat {
+    dat_descriptor: DatDescriptor,
+    stamp: FileStamp,
+    strings: Vec<(u32, String)>,
+}
+
+/// What an update of the index had to do.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct IndexUpdate {
+    pub lookup_tables_changed: bool,
+    pub decoded: usize,
+    pub reused: usize,
+}
+
+impl FileStamp {
+    fn of(path: &Path) -> Result<Self> {
+        let metadata = fs::metadata(path)?;
+        Ok(FileStamp {
+            path: path.to_path_buf(),
+            size: metadata.len(),
+            modified: metadata.modified()?,
+        })
+    }
+}
+
+impl StringIndex {
+    pub fn load(path: &Path) -> Result<Self> {
+        let file = File::open(path)
+            .map_err(|err| anyhow!("Could not open file at {}: {}", path.display(), err))?;
+        Ok(serde_json::from_reader(BufReader::new(file))?)
+    }
+
+    /// Loads the index at the given path, or starts an empty one if it can't be read.
+    pub fn load_or_default(path: &Path) -> Self {
+        Self::load(path).unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let file = File::create(path)
+            .map_err(|err| anyhow!("Could not create file at {}: {}", path.display(), err))?;
+        serde_json::to_writer(BufWriter::new(file), self)?;
+
+        Ok(())
+    }
+
+    /// Decodes the DATs which changed since the last update. The DATs and their paths are
+    /// looked up again once the VTABLE/FTABLE files change, with the lookup tables re-read
+    /// in case the given context was created before they changed. The re-read paths are
+    /// kept for the following updates.
+    pub fn update(&mut self, dat_context: &Arc<DatContext>) -> Result<IndexUpdate> {
+        if self.ffxi_path != dat_context.ffxi_path {
+            *self = StringIndex {
+                ffxi_path: dat_context.ffxi_path.clone(),
+                ..Default::default()
+            };
+        }
+
+        let lookup_tables = DatContext::lookup_table_paths(&dat_context.ffxi_path)
+            .iter()
+            .map(|path| FileStamp::of(path))
+            .collect::<Result<Vec<_>>>()?;
+        let lookup_tables_changed = lookup_tables != self.lookup_tables;
+
+        let (dat_context, dat_descriptors) = if lookup_tables_changed {
+            let mut reloaded_context = DatContext::clone(dat_context);
+            reloaded_context.id_map = DatContext::build_rom_id_map(&dat_context.ffxi_path)?;
+            let reloaded_context = Arc::new(reloaded_context);
+            self.reloaded_context = Some(reloaded_context.clone());
+            (reloaded_context.clone(), searchable_dats(&reloaded_context))
+        } else {
+            let dat_descriptors = self.dats.iter().map(|dat| dat.dat_descriptor).collect();
+            let dat_context = self
+                .reloaded_context
+                .clone()
+                .unwrap_or_else(|| dat_context.clone());
+            (dat_context, dat_descriptors)
+        };
+
+        let mut previous = self
+            .dats
+            .drain(..)
+            .map(|dat| (dat.dat_descriptor, dat))
+            .collect::<HashMap<_, _>>();
+
+        let mut update = IndexUpdate {
+            lookup_tables_changed,
+            ..Default::default()
+        };
+        let mut dats = vec![];
+        let mut changed = vec![];
+        for dat_descriptor in dat_descriptors {
+            let Ok(stamp) = dat_descriptor
+                .dat_path(&dat_context)
+                .and_then(|path| FileStamp::of(&path))
+            else {
+                continue;
+            };
+
+            match previous.remove(&dat_descriptor) {
+                Some(dat) if dat.stamp == stamp => {
+                    update.reused += 1;
+                    dats.push(dat);
+                }
+                _ => changed.push((dat_descriptor, stamp)),
+            }
+        }
+
+        update.decoded = changed.len();
+        dats.extend(Self::decode_all(changed, dat_context));
+        dats.sort_by_key(|dat| dat.dat_descriptor);
+
+        self.lookup_tables = lookup_tables;
+        self.dats = dats;
+
+        Ok(update)
+    }
+
+    /// Decodes the given DATs in parallel. DATs which fail to load are kept without strings,
+    /// so that they're only tried again once they change.
+    fn decode_all(
+        dats: Vec<(DatDescriptor, FileStamp)>,
+        dat_context: Arc<DatContext>,
+    ) -> Vec<IndexedDat> {
+        let pool = threadpool::Builder::new()
+            .thread_name("string-indexer".to_string())
+            .build();
+        let (tx, rx) = mpsc::channel();
+
+        let total_count = dats.len();
+        for (dat_descriptor, stamp) in dats {
+            let tx = tx.clone();
+            let dat_context = dat_context.clone();
+
+            pool.execute(move || {
+                let strings = dat_descriptor
+                    .collect_strings(&dat_context)
+                    .unwrap_or_default();
+                let _ = tx.send(IndexedDat {
+                    dat_descriptor,
+                    stamp,
+                    strings,
+                });
+            });
+        }
+        drop(tx);
+
+        rx.iter().take(total_count).collect()
+    }
+
+    /// Searches the indexed strings, with the matches ordered by DAT descriptor and entry.
+    pub fn search(&self, pattern: &Regex) -> Vec<SearchMatch> {
+        self.dats
+            .iter()
+            .flat_map(|dat| SearchMatch::find_all(dat.dat_descriptor, &dat.strings, pattern))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use anyhow::Result;
+    use dats::{
+        dat_format::DatFormat,
+        fixtures::{FakeInstall, FixtureInstall},
+        formats::{dialog::Dialog, entity_names::EntityNames},
+        id_mapping::DatIdMapping,
+    };
+
+    use crate::{dat_descriptor::DatDescriptor, search::search_pattern};
+
+    use super::{StringIndex, STRING_INDEX_FILE};
+
+    #[test]
+    fn incremental_updates() -> Result<()> {
+        let index_dir = tempfile::tempdir()?;
+        let fixture = FixtureInstall::new()?;
+        let dat_context = fixture.dat_context.clone();
+
+        let mut index = StringIndex::default();
+        let update = index.update(&dat_context)?;
+        assert!(update.lookup_tables_changed);
+        assert_eq!(update.reused, 0);
+        assert!(update.decoded > 0);
+
+        let pattern = search_pattern("test zone", false)?;
+        let in_dialog = |index: &StringIndex| {
+            index
+                .search(&pattern)
+                .iter()
+                .any(|found| found.dat_descriptor == DatDescriptor::Dialog(1))
+        };
+        assert!(in_dialog(&index));
+
+        let index_path = index_dir.path().join(STRING_INDEX_FILE);
+        index.save(&index_path)?;
+        let mut index = StringIndex::load(&index_path)?;
+
+        let unchanged = index.update(&dat_context)?;
+        assert!(!unchanged.lookup_tables_changed);
+        assert_eq!(unchanged.decoded, 0);
+        assert_eq!(unchanged.reused, update.decoded);
+
+        // Only the edited DAT is decoded again.
+        let dialog_path = DatDescriptor::Dialog(1).dat_path(&dat_context)?;
+        let mut dialog = Dialog::fixture();
+        dialog.entries.insert(
+            0,
+            "Welcome to the edited zone, which is much longer.".to_string(),
+        );
+        fs::write(&dialog_path, dialog.to_bytes()?)?;
+
+        let edited = index.update(&dat_context)?;
+        assert_eq!(edited.decoded, 1);
+        assert!(!in_dialog(&index));
+        assert_eq!(
+            index.search(&search_pattern("edited zone", false)?)[0].dat_descriptor,
+            DatDescriptor::Dialog(1)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn lookup_tables_changed() -> Result<()> {
+        let fixture = FixtureInstall::new()?;
+        let stale_context = fixture.dat_context.clone();
+
+        let mut index = StringIndex::default();
+        index.update(&stale_context)?;
+
+        // Adding a DAT rewrites the lookup tables, which the context doesn't know of.
+        let mut install = FakeInstall::with_fixtures()?;
+        let entities = DatIdMapping::get().entities.get_result(&2)?;
+        install.add(entities, &EntityNames::fixture())?;
+        install.write_to(fixture.path())?;
+
+        let added = index.update(&stale_context)?;
+        assert!(added.lookup_tables_changed);
+        assert!(!stale_context.id_map.contains_key(&entities.into()));
+
+        // The DAT is still found by later updates through the re-read lookup tables.
+        let entities_path = fixture
+            .path()
+            .join(install.dat_paths()[&entities.into()].to_path());
+        let mut entity_names = fs::read(&entities_path)?;
+        entity_names.extend([0; 4]);
+        fs::write(entities_path, entity_names)?;
+
+        let edited = index.update(&stale_context)?;
+        assert!(!edited.lookup_tables_changed);
+        assert_eq!(edited.decoded, 1);
+        assert!(index
+            .search(&search_pattern("moogle", false)?)
+            .iter()
+            .any(|found| found.dat_descriptor == DatDescriptor::EntityNames(2)));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,827 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    path::Path,
+    sync::OnceLock,
+};
+
+use anyhow::{anyhow, Result};
+use dats::context::DatContext;
+use encoding::language::Language;
+use regex::Regex;
+
+use crate::{
+    dat_descriptor::DatDescriptor, raw_data_format::RawDataFormat, search::searchable_dats,
+};
+
+/// Encoder tags like `${name-player}` and control characters, which translations have to keep.
+static PLACEHOLDER_REGEX: OnceLock<Regex> = OnceLock::new();
+
+const XLIFF_NAMESPACE: &str = "urn:oasis:names:tc:xliff:document:1.2";
+/// Placeholders of control characters, whose content is the character's code, e.g. `U+0007`.
+const XLIFF_CHAR_TYPE: &str = "x-char";
+
+/// Files that translators exchange with their CAT tools.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TranslationFormat {
+    Po,
+    Xliff,
+}
+
+impl TranslationFormat {
+    /// The format of a translation file, by its extension.
+    pub fn from_path(path: &Path) -> Result<Self> {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("po" | "pot") => Ok(TranslationFormat::Po),
+            Some("xlf" | "xliff") => Ok(TranslationFormat::Xliff),
+            _ => Err(anyhow!(
+                "Unsupported translation format for {}, expected .po, .pot, .xlf or .xliff.",
+                path.display()
+            )),
+        }
+    }
+}
+
+/// A translatable string of a DAT.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TranslationUnit {
+    /// Identifies the string as `<descriptor>#<entry id>`, e.g. `Dialog(243)#12`. Further
+    /// strings of the same entry get their index appended, like `Weapons#20713.3`.
+    pub context: String,
+    pub source: String,
+    /// The translated string, if it was translated.
+    pub target: Option<String>,
+}
+
+impl TranslationUnit {
+    /// Units of the strings of a DAT, without translations.
+    pub(crate) fn from_strings(
+        dat_descriptor: DatDescriptor,
+        strings: &[(u32, &str)],
+    ) -> Vec<Self> {
+        unit_contexts(
+            dat_descriptor,
+            strings.iter().map(|(entry_id, _)| *entry_id),
+        )
+        .into_iter()
+        .zip(strings)
+        .map(|(context, (_, source))| TranslationUnit {
+            context,
+            source: source.to_string(),
+            target: None,
+        })
+        .collect()
+    }
+
+    /// The descriptor part of the context, as the `Debug` name of the descriptor.
+    fn descriptor_name(&self) -> &str {
+        self.context
+            .split_once('#')
+            .map(|(descriptor_name, _)| descriptor_name)
+            .unwrap_or(&self.context)
+    }
+}
+
+fn unit_contexts(
+    dat_descriptor: DatDescriptor,
+    entry_ids: impl Iterator<Item = u32>,
+) -> Vec<String> {
+    let mut string_counts = HashMap::new();
+    entry_ids
+        .map(|entry_id| {
+            let index = string_counts.entry(entry_id).or_insert(0);
+            let context = match *index {
+                0 => format!("{:?}#{}", dat_descriptor, entry_id),
+                index => format!("{:?}#{}.{}", dat_descriptor, entry_id, index),
+            };
+            *index += 1;
+            context
+        })
+        .collect()
+}
+
+/// Replaces the strings of a DAT with the translations of its units.
+/// Returns how many strings were replaced.
+pub(crate) fn apply_translations(
+    dat_descriptor: DatDescriptor,
+    strings: Vec<(u32, &mut String)>,
+    units: &[TranslationUnit],
+) -> Result<usize> {
+    let mut units = units
+        .iter()
+        .map(|unit| (unit.context.as_str(), unit))
+        .collect::<HashMap<_, _>>();
+
+    let contexts = unit_contexts(
+        dat_descriptor,
+        strings.iter().map(|(entry_id, _)| *entry_id),
+    );
+    let mut count = 0;
+    for (context, (_, string)) in contexts.into_iter().zip(strings) {
+        let Some(unit) = units.remove(context.as_str()) else {
+            continue;
+        };
+        let Some(target) = unit.target.as_ref().filter(|target| !target.is_empty()) else {
+            continue;
+        };
+
+        if unit.source != *string {
+            return Err(anyhow!(
+                "The string of {} changed since it was exported, it has to be exported again.",
+                context
+            ));
+        }
+        check_placeholders(&context, string, target)?;
+
+        *string = target.clone();
+        count += 1;
+    }
+
+    if let Some(context) = units.keys().min() {
+        return Err(anyhow!("There's no string {} to translate.", context));
+    }
+
+    Ok(count)
+}
+
+fn placeholder_regex() -> &'static Regex {
+    PLACEHOLDER_REGEX
+        .get_or_init(|| Regex::new(r"\$\{[^}]*\}|[\x00-\x08\x0B\x0C\x0E-\x1F\x7F]").unwrap())
+}
+
+fn placeholders(text: &str) -> Vec<&str> {
+    placeholder_regex()
+        .find_iter(text)
+        .map(|found| found.as_str())
+        .collect()
+}
+
+/// Translations may move the placeholders of their source, but not change them.
+fn check_placeholders(context: &str, source: &str, target: &str) -> Result<()> {
+    let mut source_placeholders = placeholders(source);
+    let mut target_placeholders = placeholders(target);
+    source_placeholders.sort();
+    target_placeholders.sort();
+
+    if source_placeholders != target_placeholders {
+        return Err(anyhow!(
+            "The translation of {} has to keep the placeholders {}, but has {}.",
+            context,
+            po_escape(&source_placeholders.join(" ")),
+            po_escape(&target_placeholders.join(" "))
+        ));
+    }
+
+    Ok(())
+}
+
+/// Translation units of several DATs, as read from or written to a translation file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TranslationCatalog {
+    pub units: Vec<TranslationUnit>,
+}
+
+impl TranslationCatalog {
+    /// Writes the units as PO or XLIFF, depending on the extension of the given path.
+    pub fn write_to_path(&self, path: &Path, source_language: Language) -> Result<()> {
+        let text = match TranslationFormat::from_path(path)? {
+            TranslationFormat::Po => self.to_po(),
+            TranslationFormat::Xliff => self.to_xliff(source_language),
+        };
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, text)
+            .map_err(|err| anyhow!("Could not create file at {}: {}", path.display(), err))
+    }
+
+    pub fn read_from_path(path: &Path) -> Result<Self> {
+        let format = TranslationFormat::from_path(path)?;
+        let text = fs::read_to_string(path)
+            .map_err(|err| anyhow!("Could not open file at {}: {}", path.display(), err))?;
+
+        match format {
+            TranslationFormat::Po => Self::from_po(&text),
+            TranslationFormat::Xliff => Self::from_xliff(&text),
+        }
+        .map_err(|err| anyhow!("Could not read {}: {}", path.display(), err))
+    }
+
+    /// Gettext catalog with one entry for each unit, whose placeholders are listed in a comment.
+    pub fn to_po(&self) -> String {
+        let mut po = String::from(
+            "msgid \"\"\nmsgstr \"\"\n\"Content-Type: text/plain; charset=UTF-8\\n\"\n",
+        );
+
+        for unit in &self.units {
+            po.push('\n');
+            let placeholders = placeholders(&unit.source);
+            if !placeholders.is_empty() {
+                po.push_str(&format!(
+                    "#. Placeholders: {}\n",
+                    po_escape(&placeholders.join(" "))
+                ));
+            }
+            po.push_str(&po_field("msgctxt", &unit.context));
+            po.push_str(&po_field("msgid", &unit.source));
+            po.push_str(&po_field(
+                "msgstr",
+                unit.target.as_deref().unwrap_or_default(),
+            ));
+        }
+
+        po
+    }
+
+    /// Reads the entries of a gettext catalog. Fuzzy entries are treated as untranslated.
+    pub fn from_po(text: &str) -> Result<Self> {
+        let mut units = vec![];
+        let mut entry = PoEntry::default();
+        let mut field = None;
+
+        for (line_index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            let error = |message: &str| anyhow!("{} on line {}.", message, line_index + 1);
+
+            if line.is_empty() {
+                continue;
+            } else if let Some(comment) = line.strip_prefix('#') {
+                if entry.msgstr.is_some() {
+                    std::mem::take(&mut entry).finish(&mut units)?;
+                }
+                if let Some(flags) = comment.strip_prefix(',') {
+                    entry.is_fuzzy |= flags.split(',').any(|flag| flag.trim() == "fuzzy");
+                }
+                continue;
+            }
+
+            let (keyword, quoted) = match line.split_once(char::is_whitespace) {
+                Some((keyword, quoted)) if !line.starts_with('"') => (Some(keyword), quoted),
+                _ => (None, line),
+            };
+            let string = po_unescape(quoted.trim()).map_err(|err| error(&err.to_string()))?;
+
+            match keyword {
+                None => {
+                    let field = match field {
+                        Some(PoField::Context) => entry.msgctxt.as_mut(),
+                        Some(PoField::Id) => entry.msgid.as_mut(),
+                        Some(PoField::Str) => entry.msgstr.as_mut(),
+                        None => None,
+                    };
+                    field
+                        .ok_or_else(|| error("String without a keyword"))?
+                        .push_str(&string);
+                }
+                Some("msgctxt") | Some("msgid") if entry.msgstr.is_some() => {
+                    std::mem::take(&mut entry).finish(&mut units)?;
+                    field = Some(entry.set(keyword.unwrap(), string));
+                }
+                Some(keyword @ ("msgctxt" | "msgid" | "msgstr")) => {
+                    field = Some(entry.set(keyword, string));
+                }
+                Some(keyword) => {
+                    return Err(error(&format!("Unsupported keyword '{}'", keyword)));
+                }
+            }
+        }
+        entry.finish(&mut units)?;
+
+        Ok(TranslationCatalog { units })
+    }
+
+    /// XLIFF 1.2 document with one unit for each string. Placeholders are written as inline
+    /// `<ph>` elements, which CAT tools protect from being edited.
+    pub fn to_xliff(&self, source_language: Language) -> String {
+        let mut xliff = format!(
+            concat!(
+                "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+                "<xliff version=\"1.2\" xmlns=\"{}\">\n",
+                "  <file original=\"dats\" source-language=\"{}\" datatype=\"plaintext\">\n",
+                "    <body>\n"
+            ),
+            XLIFF_NAMESPACE,
+            language_code(source_language)
+        );
+
+        for unit in &self.units {
+            let context = xml_escape(&unit.context);
+            xliff.push_str(&format!(
+                "      <trans-unit id=\"{}\" resname=\"{}\" xml:space=\"preserve\">\n",
+                context, context
+            ));
+            xliff.push_str(&format!(
+                "        <source>{}</source>\n",
+                xliff_segments(&unit.source)
+            ));
+            if let Some(target) = &unit.target {
+                xliff.push_str(&format!(
+                    "        <target>{}</target>\n",
+                    xliff_segments(target)
+                ));
+            }
+            xliff.push_str("      </trans-unit>\n");
+        }
+
+        xliff.push_str("    </body>\n  </file>\n</xliff>\n");
+        xliff
+    }
+
+    /// Reads the units of an XLIFF 1.2 document. The placeholders of targets are restored
+    /// from the placeholders with the same ID in their source.
+    pub fn from_xliff(text: &str) -> Result<Self> {
+        let document = roxmltree::Document::parse(text)?;
+
+        let units = document
+            .descendants()
+            .filter(|node| node.has_tag_name("trans-unit"))
+            .map(|node| {
+                let context = node
+                    .attribute("resname")
+                    .or_else(|| node.attribute("id"))
+                    .ok_or_else(|| anyhow!("Translation unit without an ID."))?
+                    .to_string();
+                let child = |name: &str| node.children().find(|child| child.has_tag_name(name));
+
+                let mut source_placeholders = HashMap::new();
+                let source = child("source")
+                    .ok_or_else(|| anyhow!("Translation unit {} has no source.", context))
+                    .and_then(|source| {
+                        xliff_text(source, &mut |placeholder| {
+                            let content = xliff_placeholder_content(placeholder)?;
+                            if let Some(id) = placeholder.attribute("id") {
+                                source_placeholders.insert(id.to_string(), content.clone());
+                            }
+                            Ok(content)
+                        })
+                    })?;
+                let target = child("target")
+                    .map(|target| {
+                        xliff_text(target, &mut |placeholder| {
+                            placeholder
+                                .attribute("id")
+                                .and_then(|id| source_placeholders.get(id))
+                                .cloned()
+                                .ok_or_else(|| {
+                                    anyhow!(
+                                        "The translation of {} has a placeholder which isn't in its source.",
+                                        context
+                                    )
+                                })
+                        })
+                    })
+                    .transpose()?
+                    .filter(|target| !target.is_empty());
+
+                Ok(TranslationUnit {
+                    context,
+                    source,
+                    target,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(TranslationCatalog { units })
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum PoField {
+    Context,
+    Id,
+    Str,
+}
+
+#[derive(Debug, Default)]
+struct PoEntry {
+    msgctxt: Option<String>,
+    msgid: Option<String>,
+    msgstr: Option<String>,
+    is_fuzzy: bool,
+}
+
+impl PoEntry {
+    fn set(&mut self, keyword: &str, string: String) -> PoField {
+        let (field, value) = match keyword {
+            "msgctxt" => (PoField::Context, &mut self.msgctxt),
+            "msgid" => (PoField::Id, &mut self.msgid),
+            _ => (PoField::Str, &mut self.msgstr),
+        };
+        *value = Some(string);
+        field
+    }
+
+    /// Adds the entry as a unit, skipping the header and entries which weren't started yet.
+    fn finish(self, units: &mut Vec<TranslationUnit>) -> Result<()> {
+        let Some(source) = self.msgid else {
+            return Ok(());
+        };
+        let Some(context) = self.msgctxt else {
+            if source.is_empty() {
+                return Ok(());
+            }
+            return Err(anyhow!("The entry of \"{}\" has no msgctxt.", source));
+        };
+
+        units.push(TranslationUnit {
+            context,
+            source,
+            target: self
+                .msgstr
+                .filter(|target| !target.is_empty() && !self.is_fuzzy),
+        });
+
+        Ok(())
+    }
+}
+
+/// A keyword with its string, which is split into one line for each line of the string.
+fn po_field(keyword: &str, text: &str) -> String {
+    if !text.trim_end_matches('\n').contains('\n') {
+        return format!("{} \"{}\"\n", keyword, po_escape(text));
+    }
+
+    let mut field = format!("{} \"\"\n", keyword);
+    for line in text.split_inclusive('\n') {
+        field.push_str(&format!("\"{}\"\n", po_escape(line)));
+    }
+    field
+}
+
+fn po_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for char in text.chars() {
+        match char {
+            '\\' => escaped.push_str("\\\\"),
+            '"' => escaped.push_str("\\\""),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            char if char.is_control() => escaped.push_str(&format!("\\x{:02x}", char as u32)),
+            char => escaped.push(char),
+        }
+    }
+    escaped
+}
+
+fn po_unescape(quoted: &str) -> Result<String> {
+    let text = quoted
+        .strip_prefix('"')
+        .and_then(|text| text.strip_suffix('"'))
+        .ok_or_else(|| anyhow!("Expected a quoted string"))?;
+
+    let mut unescaped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(char) = chars.next() {
+        if char != '\\' {
+            unescaped.push(char);
+            continue;
+        }
+
+        match chars.next() {
+            Some('\\') => unescaped.push('\\'),
+            Some('"') => unescaped.push('"'),
+            Some('n') => unescaped.push('\n'),
+            Some('r') => unescaped.push('\r'),
+            Some('t') => unescaped.push('\t'),
+            Some('x') => {
+                let code = chars.by_ref().take(2).collect::<String>();
+                let char = u32::from_str_radix(&code, 16)
+                    .ok()
+                    .and_then(char::from_u32)
+                    .ok_or_else(|| anyhow!("Invalid escaped character \\x{}", code))?;
+                unescaped.push(char);
+            }
+            other => {
+                return Err(anyhow!(
+                    "Unsupported escape sequence \\{}",
+                    other.map(String::from).unwrap_or_default()
+                ))
+            }
+        }
+    }
+
+    Ok(unescaped)
+}
+
+fn xml_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for char in text.chars() {
+        match char {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            // Parsers would turn carriage returns into line feeds otherwise.
+            '\r' => escaped.push_str("&#13;"),
+            char => escaped.push(char),
+        }
+    }
+    escaped
+}
+
+/// The text with its placeholders as numbered `<ph>` elements. Control characters can't be
+/// written in XML, so their placeholders contain their code instead.
+fn xliff_segments(text: &str) -> String {
+    let mut segments = String::new();
+    let mut text_start = 0;
+    for (index, found) in placeholder_regex().find_iter(text).enumerate() {
+        segments.push_str(&xml_escape(&text[text_start..found.start()]));
+        text_start = found.end();
+
+        let placeholder = found.as_str();
+        if placeholder.starts_with('$') {
+            segments.push_str(&format!(
+                "<ph id=\"{}\">{}</ph>",
+                index + 1,
+                xml_escape(placeholder)
+            ));
+        } else {
+            let code = placeholder.chars().next().unwrap_or_default() as u32;
+            segments.push_str(&format!(
+                "<ph id=\"{}\" ctype=\"{}\">U+{:04X}</ph>",
+                index + 1,
+                XLIFF_CHAR_TYPE,
+                code
+            ));
+        }
+    }
+    segments.push_str(&xml_escape(&text[text_start..]));
+    segments
+}
+
+fn xliff_placeholder_content(placeholder: roxmltree::Node) -> Result<String> {
+    let content = placeholder.text().unwrap_or_default();
+    if placeholder.attribute("ctype") != Some(XLIFF_CHAR_TYPE) {
+        return Ok(content.to_string());
+    }
+
+    content
+        .strip_prefix("U+")
+        .and_then(|code| u32::from_str_radix(code, 16).ok())
+        .and_then(char::from_u32)
+        .map(String::from)
+        .ok_or_else(|| anyhow!("Invalid character placeholder {}.", content))
+}
+
+/// The text of a source or target, with the text of each placeholder given by the callback.
+/// Markup around text, like `<g>` or `<mrk>`, is left out.
+fn xliff_text(
+    node: roxmltree::Node,
+    placeholder_text: &mut dyn FnMut(roxmltree::Node) -> Result<String>,
+) -> Result<String> {
+    let mut text = String::new();
+    for child in node.children() {
+        if child.is_text() {
+            text.push_str(child.text().unwrap_or_default());
+        } else if child.has_tag_name("ph") {
+            text.push_str(&placeholder_text(child)?);
+        } else if child.has_tag_name("g") || child.has_tag_name("mrk") {
+            text.push_str(&xliff_text(child, placeholder_text)?);
+        } else if child.is_element() {
+            return Err(anyhow!(
+                "Unsupported inline element <{}>.",
+                child.tag_name().name()
+            ));
+        }
+    }
+    Ok(text)
+}
+
+fn language_code(language: Language) -> &'static str {
+    match language {
+        Language::English => "en",
+        Language::Japanese => "ja",
+    }
+}
+
+/// Writes every translatable string of the given DATs to a PO or XLIFF file, depending on the
+/// extension of the path. The raw data files of DATs are exported instead when present.
+/// Returns the amount of exported strings.
+pub fn export_translations(
+    dat_context: &DatContext,
+    dat_descriptors: &[DatDescriptor],
+    raw_data_root_path: Option<&Path>,
+    path: &Path,
+) -> Result<usize> {
+    let mut catalog = TranslationCatalog::default();
+    for dat_descriptor in dat_descriptors {
+        catalog
+            .units
+            .extend(dat_descriptor.translation_units(dat_context, raw_data_root_path)?);
+    }
+
+    catalog.write_to_path(path, Language::current())?;
+    Ok(catalog.units.len())
+}
+
+/// Writes the translated units of a PO or XLIFF file into the raw data files of their DATs.
+/// DATs without a file yet get one in the given format. Returns how many strings of each DAT
+/// were translated.
+pub fn import_translations(
+    dat_context: &DatContext,
+    raw_data_root_path: &Path,
+    path: &Path,
+    format: RawDataFormat,
+) -> Result<Vec<(DatDescriptor, usize)>> {
+    let catalog = TranslationCatalog::read_from_path(path)?;
+
+    let descriptors_by_name = searchable_dats(dat_context)
+        .into_iter()
+        .map(|dat_descriptor| (format!("{:?}", dat_descriptor), dat_descriptor))
+        .collect::<HashMap<_, _>>();
+
+    let mut units_by_descriptor = BTreeMap::<DatDescriptor, Vec<TranslationUnit>>::new();
+    for unit in catalog.units {
+        let dat_descriptor = descriptors_by_name
+            .get(unit.descriptor_name())
+            .ok_or_else(|| anyhow!("Unknown DAT of the translation unit {}.", unit.context))?;
+        units_by_descriptor
+            .entry(*dat_descriptor)
+            .or_default()
+            .push(unit);
+    }
+
+    units_by_descriptor
+        .into_iter()
+        .filter(|(_, units)| units.iter().any(|unit| unit.target.is_some()))
+        .map(|(dat_descriptor, units)| {
+            let count = dat_descriptor.apply_translations(
+                dat_context,
+                raw_data_root_path.to_path_buf(),
+                &units,
+                format,
+            )?;
+            Ok((dat_descriptor, count))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use anyhow::Result;
+    use dats::{
+        entries::DatEntries,
+        fixtures::FixtureInstall,
+        formats::{dialog::Dialog, status_info::StatusInfoTable},
+    };
+    use encoding::language::Language;
+
+    use crate::{dat_descriptor::DatDescriptor, raw_data_format::RawDataFormat};
+
+    use super::{export_translations, import_translations, TranslationCatalog, TranslationUnit};
+
+    fn fixture_catalog() -> TranslationCatalog {
+        TranslationCatalog {
+            units: vec![
+                TranslationUnit {
+                    context: "Dialog(1)#1".to_string(),
+                    source: "Can ya imagine it, ${name-player}?\n\"Quoted\"\u{7}${prompt}"
+                        .to_string(),
+                    target: Some(
+                        "Stell dir vor, ${name-player}!\n\"Zitat\"\u{7}${prompt}".to_string(),
+                    ),
+                },
+                TranslationUnit {
+                    context: "Weapons#20713.1".to_string(),
+                    source: "Excalipoor & <co>".to_string(),
+                    target: None,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn po_roundtrip() -> Result<()> {
+        let catalog = fixture_catalog();
+        let po = catalog.to_po();
+        assert!(po.contains("#. Placeholders: ${name-player} \\x07 ${prompt}\n"));
+        assert!(po.contains(
+            "msgctxt \"Dialog(1)#1\"\nmsgid \"\"\n\"Can ya imagine it, ${name-player}?\\n\"\n"
+        ));
+        assert_eq!(TranslationCatalog::from_po(&po)?, catalog);
+
+        // Fuzzy translations still have to be reviewed.
+        let fuzzy = po.replace("#. Placeholders", "#, fuzzy\n#. Placeholders");
+        assert_eq!(TranslationCatalog::from_po(&fuzzy)?.units[0].target, None);
+        assert!(TranslationCatalog::from_po("msgid \"Hello\"\nmsgstr \"Hallo\"\n").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn xliff_roundtrip() -> Result<()> {
+        let catalog = fixture_catalog();
+        let xliff = catalog.to_xliff(Language::English);
+        assert!(xliff.contains(
+            "<source>Can ya imagine it, <ph id=\"1\">${name-player}</ph>?\n&quot;Quoted&quot;<ph id=\"2\" ctype=\"x-char\">U+0007</ph><ph id=\"3\">${prompt}</ph></source>"
+        ));
+        assert_eq!(TranslationCatalog::from_xliff(&xliff)?, catalog);
+
+        // Placeholders are restored from the source, even if a tool changed their content.
+        let reordered = xliff.replace(
+            "<target>Stell dir vor, <ph id=\"1\">${name-player}</ph>!",
+            "<target><ph id=\"1\">changed</ph>, stell dir vor!",
+        );
+        assert_eq!(
+            TranslationCatalog::from_xliff(&reordered)?.units[0]
+                .target
+                .as_deref(),
+            Some("${name-player}, stell dir vor!\n\"Zitat\"\u{7}${prompt}")
+        );
+        assert!(TranslationCatalog::from_xliff(&xliff.replace(
+            "ph id=\"3\">${prompt}</ph></target>",
+            "ph id=\"9\"/></target>"
+        ))
+        .is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn translate_fake_install() -> Result<()> {
+        let work_dir = tempfile::tempdir()?;
+        let fixture = FixtureInstall::new()?;
+        let dat_context = fixture.dat_context.clone();
+        let raw_data_root = work_dir.path().join("raw_data");
+        let po_path = work_dir.path().join("strings.po");
+
+        let count = export_translations(
+            &dat_context,
+            &[
+                DatDescriptor::Dialog(1),
+                DatDescriptor::Weapons,
+                DatDescriptor::StatusInfo,
+            ],
+            None,
+            &po_path,
+        )?;
+        let mut catalog = TranslationCatalog::read_from_path(&po_path)?;
+        assert_eq!(
+            count,
+            Dialog::fixture().entries.len() + 2 * 4 + StatusInfoTable::fixture().entries().len()
+        );
+        assert!(catalog
+            .units
+            .iter()
+            .any(|unit| unit.context == "Weapons#20713.3"));
+
+        for unit in &mut catalog.units {
+            unit.target = match unit.context.as_str() {
+                "Dialog(1)#0" => Some("Willkommen in der Testzone.".to_string()),
+                "Weapons#20713" => Some("Excalipauvre".to_string()),
+                _ => None,
+            };
+        }
+        catalog.write_to_path(&po_path, Language::English)?;
+
+        let imported =
+            import_translations(&dat_context, &raw_data_root, &po_path, RawDataFormat::Json)?;
+        assert_eq!(
+            imported,
+            vec![(DatDescriptor::Weapons, 1), (DatDescriptor::Dialog(1), 1)]
+        );
+
+        let dialog_path = RawDataFormat::Json.path_in(
+            &raw_data_root,
+            &DatDescriptor::Dialog(1).get_relative_path(&dat_context)?,
+        );
+        let dialog: Dialog = serde_json::from_str(&fs::read_to_string(dialog_path)?)?;
+        assert_eq!(dialog.entries[&0], "Willkommen in der Testzone.");
+        assert_eq!(dialog.entries[&1], Dialog::fixture().entries[&1]);
+
+        let items = fs::read_to_string(raw_data_root.join("items/weapons.json"))?;
+        assert!(items.contains("\"Excalipauvre\""));
+
+        // Translations of strings which changed since the export aren't applied.
+        assert!(
+            import_translations(&dat_context, &raw_data_root, &po_path, RawDataFormat::Json)
+                .is_err()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn placeholders_are_kept() -> Result<()> {
+        let work_dir = tempfile::tempdir()?;
+        let fixture = FixtureInstall::new()?;
+        let dat_context = fixture.dat_context.clone();
+        let xliff_path = work_dir.path().join("dialog.xlf");
+
+        export_translations(&dat_context, &[DatDescriptor::Dialog(1)], None, &xliff_path)?;
+        let mut catalog = TranslationCatalog::read_from_path(&xliff_path)?;
+        catalog.units[1].target = Some("Stell dir vor, {player}?${prompt}".to_string());
+        let po_path = work_dir.path().join("dialog.po");
+        catalog.write_to_path(&po_path, Language::English)?;
+
+        let err = import_translations(&dat_context, work_dir.path(), &po_path, RawDataFormat::Yaml)
+            .unwrap_err();
+        assert!(err.to_string().contains("Dialog(1)#1"), "{}", err);
+
+        Ok(())
+    }
+}
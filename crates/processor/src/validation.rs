@@ -0,0 +1,126 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    path::PathBuf,
+};
+
+use encoding::validation::ValidationError;
+
+/// A string of a raw data file which can't be encoded as written.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StringProblem {
+    pub raw_data_path: PathBuf,
+    pub entry_id: u32,
+    pub error: ValidationError,
+}
+
+impl Display for StringProblem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: entry {}, {}",
+            self.raw_data_path.display(),
+            self.entry_id,
+            self.error
+        )
+    }
+}
+
+/// Every problem found in the strings of a DAT before generating it.
+#[derive(Debug, Clone, thiserror::Error)]
+pub struct InvalidStrings {
+    pub problems: Vec<StringProblem>,
+}
+
+impl Display for InvalidStrings {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Found {} problem(s) in strings:", self.problems.len())?;
+        for problem in &self.problems {
+            write!(f, "\n  {}", problem)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use anyhow::Result;
+    use dats::fixtures::FixtureInstall;
+    use encoding::validation::ValidationErrorKind;
+
+    use crate::{dat_descriptor::DatDescriptor, raw_data_format::RawDataFormat};
+
+    #[test]
+    fn fixture_strings_are_valid() -> Result<()> {
+        let fixture = FixtureInstall::new()?;
+        let dat_context = fixture.dat_context.clone();
+        let raw_data_dir = tempfile::tempdir()?;
+
+        let mut dat_descriptors = DatDescriptor::all_standalone();
+        dat_descriptors.extend(DatDescriptor::all_zoned(&dat_context));
+        for dat_descriptor in dat_descriptors {
+            if dat_descriptor
+                .dat_to_yaml(
+                    dat_context.clone(),
+                    raw_data_dir.path().to_path_buf(),
+                    RawDataFormat::Yaml,
+                )
+                .is_err()
+            {
+                continue;
+            }
+
+            assert_eq!(
+                dat_descriptor.validate_strings(&dat_context, raw_data_dir.path())?,
+                vec![],
+                "{:?}",
+                dat_descriptor
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn problems_of_edited_strings() -> Result<()> {
+        let fixture = FixtureInstall::new()?;
+        let dat_context = fixture.dat_context.clone();
+        let raw_data_dir = tempfile::tempdir()?;
+
+        let dat_descriptor = DatDescriptor::Dialog(1);
+        dat_descriptor.dat_to_yaml(
+            dat_context.clone(),
+            raw_data_dir.path().to_path_buf(),
+            RawDataFormat::Yaml,
+        )?;
+        let raw_data_path = RawDataFormat::Yaml.path_in(
+            raw_data_dir.path(),
+            &dat_descriptor.get_relative_path(&dat_context)?,
+        );
+
+        let yaml = fs::read_to_string(&raw_data_path)?;
+        fs::write(
+            &raw_data_path,
+            yaml.replacen("${name-player}", "${name-playr}", 1)
+                .replacen("${prompt}", "${number: 300}", 1),
+        )?;
+
+        let problems = dat_descriptor.validate_strings(&dat_context, raw_data_dir.path())?;
+        assert_eq!(problems.len(), 2, "{:?}", problems);
+        assert!(problems
+            .iter()
+            .all(|problem| problem.raw_data_path == raw_data_path));
+        assert_eq!(
+            problems[0].error.kind,
+            ValidationErrorKind::UnknownTag("name-playr".to_string())
+        );
+        assert!(matches!(
+            problems[1].error.kind,
+            ValidationErrorKind::InvalidParameter { .. }
+        ));
+
+        Ok(())
+    }
+}
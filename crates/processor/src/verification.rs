@@ -0,0 +1,292 @@
+use std::{
+    fs::{self, File},
+    io::BufWriter,
+    path::{Path, PathBuf},
+    sync::{mpsc, Arc},
+};
+
+use anyhow::{anyhow, Result};
+use dats::{base::DatError, context::DatContext, dat_format::RoundTripMismatch};
+use serde::Serialize;
+
+use crate::dat_descriptor::DatDescriptor;
+
+/// Result of checking that DATs are re-encoded byte-identically after being parsed.
+#[derive(Debug, Clone, Serialize)]
+pub struct VerificationReport {
+    pub passed: usize,
+    pub failed: usize,
+    pub results: Vec<VerificationResult>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct VerificationResult {
+    pub dat_descriptor: DatDescriptor,
+    pub name: String,
+    #[serde(flatten)]
+    pub outcome: VerificationOutcome,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum VerificationOutcome {
+    Passed {
+        path: PathBuf,
+    },
+    /// The DAT was parsed, but the re-encoded bytes did not match the original.
+    Mismatch {
+        path: PathBuf,
+        original_length: usize,
+        re_encoded_length: usize,
+        first_diff_offset: Option<usize>,
+        context_offset: usize,
+        original_context: String,
+        re_encoded_context: String,
+    },
+    /// The DAT could not be parsed or re-encoded at all.
+    Error {
+        message: String,
+    },
+}
+
+impl VerificationOutcome {
+    pub fn is_passed(&self) -> bool {
+        matches!(self, VerificationOutcome::Passed { .. })
+    }
+
+    fn from_error(err: anyhow::Error) -> Self {
+        let mismatch = match err.downcast_ref::<DatError>() {
+            Some(DatError::DatLoadFailed(_, inner)) => inner.downcast_ref::<RoundTripMismatch>(),
+            _ => err.downcast_ref::<RoundTripMismatch>(),
+        };
+
+        match mismatch {
+            Some(mismatch) => VerificationOutcome::Mismatch {
+                path: mismatch.path.clone(),
+                original_length: mismatch.original_len,
+                re_encoded_length: mismatch.re_encoded_len,
+                first_diff_offset: mismatch.first_diff_idx,
+                context_offset: mismatch.context_start,
+                original_context: to_hex(&mismatch.original_context),
+                re_encoded_context: to_hex(&mismatch.re_encoded_context),
+            },
+            None => VerificationOutcome::Error {
+                message: err.to_string(),
+            },
+        }
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|byte| format!("{:02X}", byte))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+impl VerificationReport {
+    /// Writes the report as JSON or YAML, depending on the extension of the given path.
+    pub fn write_to_path(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or_default();
+
+        let writer =
+            match extension {
+                "json" | "yml" | "yaml" => BufWriter::new(File::create(path).map_err(|err| {
+                    anyhow!("Could not create file at {}: {}", path.display(), err)
+                })?),
+                _ => {
+                    return Err(anyhow!(
+                        "Unsupported report format '{}', expected .json, .yml or .yaml.",
+                        extension
+                    ))
+                }
+            };
+
+        if extension == "json" {
+            serde_json::to_writer_pretty(writer, self)?;
+        } else {
+            serde_yaml::to_writer(writer, self)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Runs the checked parse of every given DAT in parallel, calling `on_result` as each one finishes.
+/// The results in the returned report are ordered by DAT descriptor.
+pub fn verify_dats(
+    dat_descriptors: Vec<DatDescriptor>,
+    dat_context: Arc<DatContext>,
+    mut on_result: impl FnMut(&VerificationResult),
+) -> VerificationReport {
+    let pool = threadpool::Builder::new()
+        .thread_name("dat-verifier".to_string())
+        .build();
+    let (tx, rx) = mpsc::channel();
+
+    let total_count = dat_descriptors.len();
+    for (idx, dat_descriptor) in dat_descriptors.iter().copied().enumerate() {
+        let tx = tx.clone();
+        let dat_context = dat_context.clone();
+
+        pool.execute(move || {
+            let outcome = match dat_descriptor.check_roundtrip(dat_context.clone()) {
+                Ok(path) => VerificationOutcome::Passed { path },
+                Err(err) => VerificationOutcome::from_error(err),
+            };
+
+            let _ = tx.send((
+                idx,
+                VerificationResult {
+                    dat_descriptor,
+                    name: result_name(dat_descriptor, &dat_context),
+                    outcome,
+                },
+            ));
+        });
+    }
+    drop(tx);
+
+    let mut results_by_job: Vec<Option<VerificationResult>> = vec![None; total_count];
+    for (idx, result) in rx.iter().take(total_count) {
+        on_result(&result);
+        results_by_job[idx] = Some(result);
+    }
+
+    // A worker which panicked never sent its result, which still counts as a failure.
+    let mut results = Vec::with_capacity(total_count);
+    for (result, dat_descriptor) in results_by_job.into_iter().zip(dat_descriptors) {
+        let result = result.unwrap_or_else(|| {
+            let result = VerificationResult {
+                dat_descriptor,
+                name: result_name(dat_descriptor, &dat_context),
+                outcome: VerificationOutcome::Error {
+                    message: "Verification panicked before finishing.".to_string(),
+                },
+            };
+            on_result(&result);
+            result
+        });
+        results.push(result);
+    }
+
+    results.sort_by_key(|result| result.dat_descriptor);
+
+    let passed = results
+        .iter()
+        .filter(|result| result.outcome.is_passed())
+        .count();
+
+    VerificationReport {
+        passed,
+        failed: results.len() - passed,
+        results,
+    }
+}
+
+fn result_name(dat_descriptor: DatDescriptor, dat_context: &DatContext) -> String {
+    dat_descriptor
+        .get_relative_path(dat_context)
+        .unwrap_or_else(|_| format!("{:?}", dat_descriptor))
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use dats::{
+        fixtures::{FakeInstall, FixtureInstall},
+        id_mapping::DatIdMapping,
+    };
+
+    use crate::dat_descriptor::DatDescriptor;
+
+    use super::{verify_dats, VerificationOutcome};
+
+    #[test]
+    fn mismatch_and_error() -> Result<()> {
+        let mapping = DatIdMapping::get();
+        let mut install = FakeInstall::with_fixtures()?;
+
+        // Trailing bytes after the entity names are dropped when re-encoding.
+        let entities = mapping.entities.get_result(&1)?;
+        let mut entity_names = install.get_bytes(entities).unwrap().to_vec();
+        entity_names.extend([0; 4]);
+        install.add_bytes(entities, entity_names);
+        install.add_bytes(&mapping.status_info, vec![0xFF; 3]);
+
+        let fixture = FixtureInstall::write(install)?;
+
+        let mut reported = 0;
+        let report = verify_dats(
+            vec![
+                DatDescriptor::StatusInfo,
+                DatDescriptor::EntityNames(1),
+                DatDescriptor::PolMessages,
+            ],
+            fixture.dat_context.clone(),
+            |_| reported += 1,
+        );
+
+        assert_eq!(reported, 3);
+        assert_eq!((report.passed, report.failed), (1, 2));
+
+        let outcomes = report
+            .results
+            .iter()
+            .map(|result| (result.dat_descriptor, &result.outcome))
+            .collect::<Vec<_>>();
+        assert!(
+            matches!(
+                outcomes[..],
+                [
+                    (
+                        DatDescriptor::PolMessages,
+                        VerificationOutcome::Passed { .. }
+                    ),
+                    (DatDescriptor::StatusInfo, VerificationOutcome::Error { .. }),
+                    (
+                        DatDescriptor::EntityNames(1),
+                        VerificationOutcome::Mismatch { .. }
+                    ),
+                ]
+            ),
+            "{:#?}",
+            outcomes
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn zone_failing_the_format_check() -> Result<()> {
+        let mapping = DatIdMapping::get();
+        let mut install = FakeInstall::with_fixtures()?;
+        install.add_bytes(mapping.dialog.get_result(&1)?, vec![0xFF; 3]);
+
+        let fixture = FixtureInstall::write(install)?;
+        let dat_descriptors = DatDescriptor::all_zoned(&fixture.dat_context);
+        assert!(dat_descriptors.contains(&DatDescriptor::Dialog(1)));
+
+        // Each job is reported, even for the same DAT.
+        let report = verify_dats(
+            vec![DatDescriptor::Dialog(1), DatDescriptor::Dialog(1)],
+            fixture.dat_context.clone(),
+            |_| {},
+        );
+        assert_eq!((report.passed, report.failed), (0, 2));
+        assert!(report
+            .results
+            .iter()
+            .all(|result| matches!(result.outcome, VerificationOutcome::Error { .. })));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,268 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use anyhow::{anyhow, Result};
+use dats::{
+    base::{DatByZone, ZoneId},
+    context::DatContext,
+    dat_format::DatFormat,
+    formats::{
+        dialog::Dialog,
+        entity_names::EntityNames,
+        event::{EventFile, Instruction},
+    },
+    id_mapping::DatIdMapping,
+};
+use serde::{Deserialize, Serialize};
+
+/// Where the dialog entries and entities of each zone are used, for checking the impact
+/// of editing them.
+#[derive(Debug, Clone, Default, specta::Type, Serialize, Deserialize)]
+pub struct XrefIndex {
+    pub zones: BTreeMap<ZoneId, ZoneXref>,
+}
+
+#[derive(Debug, Clone, Default, specta::Type, Serialize, Deserialize)]
+pub struct ZoneXref {
+    pub zone_id: ZoneId,
+    pub dialog: BTreeMap<u32, DialogXref>,
+    pub entities: BTreeMap<u32, EntityXref>,
+}
+
+#[derive(Debug, Clone, Default, specta::Type, Serialize, Deserialize)]
+pub struct DialogXref {
+    pub text: String,
+    pub references: Vec<XrefLocation>,
+}
+
+#[derive(Debug, Clone, Default, specta::Type, Serialize, Deserialize)]
+pub struct EntityXref {
+    pub name: String,
+    pub references: Vec<XrefLocation>,
+}
+
+/// An event of the zone. The instruction is only set for references from within the code,
+/// while entities are referenced by every event of their own block.
+#[derive(Debug, Clone, PartialEq, Eq, specta::Type, Serialize, Deserialize)]
+pub struct XrefLocation {
+    pub actor_id: u32,
+    pub event_id: u16,
+    pub instruction: Option<u32>,
+}
+
+impl ZoneXref {
+    pub fn new(
+        zone_id: ZoneId,
+        dialog: Option<&Dialog>,
+        entities: Option<&EntityNames>,
+        events: Option<&EventFile>,
+    ) -> Self {
+        let mut zone_xref = ZoneXref {
+            zone_id,
+            dialog: dialog
+                .map(|dialog| {
+                    dialog
+                        .entries
+                        .iter()
+                        .map(|(id, text)| {
+                            let entry = DialogXref {
+                                text: text.clone(),
+                                references: vec![],
+                            };
+                            (*id, entry)
+                        })
+                        .collect()
+                })
+                .unwrap_or_default(),
+            entities: entities
+                .map(|entities| {
+                    entities
+                        .names
+                        .iter()
+                        .map(|name| {
+                            let entry = EntityXref {
+                                name: name.name().to_string(),
+                                references: vec![],
+                            };
+                            (name.id(), entry)
+                        })
+                        .collect()
+                })
+                .unwrap_or_default(),
+        };
+
+        for block in events.iter().flat_map(|events| &events.blocks) {
+            for event in &block.events {
+                let location = XrefLocation {
+                    actor_id: block.actor_id,
+                    event_id: event.id,
+                    instruction: None,
+                };
+                if let Some(entity) = zone_xref.entities.get_mut(&block.actor_id) {
+                    entity.references.push(location.clone());
+                }
+
+                for (idx, instruction) in event.instructions.iter().enumerate() {
+                    if let Instruction::Message { message_id, .. } = instruction {
+                        if let Some(entry) = zone_xref.dialog.get_mut(&(*message_id as u32)) {
+                            entry.references.push(XrefLocation {
+                                instruction: Some(idx as u32),
+                                ..location.clone()
+                            });
+                        }
+                    }
+                }
+            }
+        }
+
+        zone_xref
+    }
+
+    /// Joins the DATs of the given zone. DATs which the zone doesn't have, or which can't be
+    /// loaded, are left out, e.g. zones without events still list their dialog and entities,
+    /// just without any references.
+    pub fn load(dat_context: &DatContext, zone_id: ZoneId) -> Result<Self> {
+        let mapping = DatIdMapping::get();
+        let dialog = load_zone_dat(dat_context, &mapping.dialog, zone_id);
+        let entities = load_zone_dat(dat_context, &mapping.entities, zone_id);
+        let events = load_zone_dat(dat_context, &mapping.events, zone_id);
+
+        if dialog.is_none() && entities.is_none() && events.is_none() {
+            return Err(anyhow!(
+                "No dialog, entities or events for zone {}.",
+                zone_id
+            ));
+        }
+
+        Ok(Self::new(
+            zone_id,
+            dialog.as_ref(),
+            entities.as_ref(),
+            events.as_ref(),
+        ))
+    }
+
+    /// Dialog entries which aren't used by any event.
+    pub fn unreferenced_dialog(&self) -> impl Iterator<Item = (&u32, &DialogXref)> {
+        self.dialog
+            .iter()
+            .filter(|(_, entry)| entry.references.is_empty())
+    }
+}
+
+fn load_zone_dat<T: DatFormat>(
+    dat_context: &DatContext,
+    dat_by_zone: &DatByZone<T>,
+    zone_id: ZoneId,
+) -> Option<T> {
+    dat_by_zone
+        .get(&zone_id)
+        .and_then(|dat| dat_context.get_data_from_dat(dat).ok())
+        .map(|extracted| extracted.dat)
+}
+
+impl XrefIndex {
+    /// Builds the index of every named zone with dialog, entities or events.
+    pub fn build(dat_context: &DatContext) -> Self {
+        let mapping = DatIdMapping::get();
+        let zone_ids = mapping
+            .dialog
+            .map
+            .keys()
+            .chain(mapping.entities.map.keys())
+            .chain(mapping.events.map.keys())
+            .filter(|zone_id| dat_context.zone_id_to_name.contains_key(zone_id))
+            .copied()
+            .collect::<BTreeSet<_>>();
+
+        let zones = zone_ids
+            .into_iter()
+            .filter_map(|zone_id| {
+                ZoneXref::load(dat_context, zone_id)
+                    .ok()
+                    .map(|zone_xref| (zone_id, zone_xref))
+            })
+            .collect();
+
+        XrefIndex { zones }
+    }
+
+    pub fn dialog_references(&self, zone_id: ZoneId, dialog_id: u32) -> Option<&DialogXref> {
+        self.zones.get(&zone_id)?.dialog.get(&dialog_id)
+    }
+
+    pub fn entity_references(&self, zone_id: ZoneId, entity_id: u32) -> Option<&EntityXref> {
+        self.zones.get(&zone_id)?.entities.get(&entity_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use dats::{
+        fixtures::FixtureInstall,
+        formats::{dialog::Dialog, entity_names::EntityNames, event::EventFile},
+    };
+
+    use super::{XrefIndex, XrefLocation, ZoneXref};
+
+    #[test]
+    fn fixture_references() {
+        let zone_xref = ZoneXref::new(
+            1,
+            Some(&Dialog::fixture()),
+            Some(&EntityNames::fixture()),
+            Some(&EventFile::fixture()),
+        );
+
+        assert_eq!(
+            zone_xref.dialog[&1].references,
+            vec![XrefLocation {
+                actor_id: 0x01001000,
+                event_id: 0,
+                instruction: Some(1),
+            }]
+        );
+        assert!(zone_xref.dialog[&0].references.is_empty());
+        assert_eq!(zone_xref.unreferenced_dialog().count(), 2);
+
+        let guard = &zone_xref.entities[&0x01001000];
+        assert_eq!(guard.name, "Test Guard");
+        assert_eq!(guard.references.len(), 3);
+        assert!(zone_xref.entities[&0x01001002].references.is_empty());
+    }
+
+    #[test]
+    fn index_from_fake_install() -> Result<()> {
+        let fixture = FixtureInstall::new()?;
+        let dat_context = fixture.dat_context.clone();
+
+        let index = XrefIndex::build(&dat_context);
+        assert_eq!(index.zones.keys().copied().collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(
+            index
+                .dialog_references(1, 0)
+                .map(|entry| entry.text.as_str()),
+            Some("Welcome to the test zone.")
+        );
+        assert_eq!(
+            index
+                .entity_references(1, 0x01001001)
+                .map(|entity| entity.name.as_str()),
+            Some("Moogle")
+        );
+        assert!(index.dialog_references(1, 100).is_none());
+
+        // The fake install has no event DATs, so nothing is referenced.
+        let zone_xref = ZoneXref::load(&dat_context, 1)?;
+        assert_eq!(
+            zone_xref.unreferenced_dialog().count(),
+            zone_xref.dialog.len()
+        );
+        assert!(zone_xref
+            .entities
+            .values()
+            .all(|entity| entity.references.is_empty()));
+
+        Ok(())
+    }
+}